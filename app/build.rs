@@ -1,5 +1,4 @@
 
-use std::env;
 use std::fs::{self, File};
 use std::io::Write;
 use std::path::{Path, PathBuf};
@@ -20,7 +19,7 @@ fn main() {
         Ok(entries) => {
             for entry in entries.flatten() {
                 let path = entry.path();
-                if !path.is_file() || path.extension().map_or(false, |e| e == "rs") == false {
+                if !path.is_file() || path.extension().is_none_or(|e| e != "rs") {
                     continue;
                 }
 
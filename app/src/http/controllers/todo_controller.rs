@@ -1,52 +1,61 @@
 // use std::vec;
 use axum::extract::{RawPathParams, RawQuery, State};
 use axum::http::StatusCode;
-use axum::response::{IntoResponse};
+use axum::response::{IntoResponse, Response};
 use rustavel_core::state::AppState;
 use axum::Json;
-use time::PrimitiveDateTime;
-// use rustavel_core::sql::query::QueryDsl;
+use rustavel_core::col;
+use rustavel_core::db::schema::Schema;
+use rustavel_core::sql::query::QueryDsl;
 use crate::models::Todo;
-use rustavel_core::facades::datetime::now_primitive;
+use serde::Deserialize;
 
+#[derive(Deserialize)]
+pub struct StoreTodo {
+    pub title: String,
+}
 
-pub async fn index(State(_state): State<AppState>) -> impl IntoResponse {
-    // just for test now
-    let tasks =  vec!(
-        Todo{
-            id: 1,
-            title: "create router".to_string(),
-            done: true,
-            created_at: now_primitive(),
-            updated_at: now_primitive(),
-
-        },
-        Todo{
-            id: 2,
-            title: "create schema".to_string(),
-            done: true,
-            created_at: now_primitive(),
-            updated_at: now_primitive(),
-        },
-        Todo{
-            id: 3,
-            title: "create controller".to_string(),
-            done: false,
-            created_at: now_primitive(),
-            updated_at: now_primitive(),
-        },
+fn db_error(error: impl std::fmt::Debug) -> Response {
+    (StatusCode::INTERNAL_SERVER_ERROR, format!("{:?}", error)).into_response()
+}
 
-    );
-    (StatusCode::OK, Json(tasks))
-    // (StatusCode::OK, "so so")
+pub async fn index(State(_state): State<AppState>) -> Response {
+    let schema = match Schema::new().await {
+        Ok(schema) => schema,
+        Err(e) => return db_error(e),
+    };
+    let mut tasks = Vec::new();
+    let result = Todo::all()
+        .chunk(&schema, 100, |todos| {
+            tasks.extend(todos);
+            async { Ok(()) }
+        })
+        .await;
+    match result {
+        Ok(()) => (StatusCode::OK, Json(tasks)).into_response(),
+        Err(e) => db_error(e),
+    }
 }
 pub async fn create(State(_state): State<AppState>) -> impl IntoResponse {
     // Todo::all();
     println!("so so...");
     (StatusCode::OK, "to create called")
 }
-pub async fn store(State(_state): State<AppState>) -> impl IntoResponse {
-    (StatusCode::OK, "to store called")
+pub async fn store(State(_state): State<AppState>, Json(todo): Json<StoreTodo>) -> Response {
+    let schema = match Schema::new().await {
+        Ok(schema) => schema,
+        Err(e) => return db_error(e),
+    };
+    // created_at / updated_at are filled by the insert builder
+    let result = Todo::insert()
+        .set(col!(Todo::title), todo.title)
+        .set(col!(Todo::done), false)
+        .execute(&schema)
+        .await;
+    match result {
+        Ok(()) => (StatusCode::CREATED, "todo stored").into_response(),
+        Err(e) => db_error(e),
+    }
 }
 pub async fn edit(State(_state): State<AppState>, params: RawPathParams) -> impl IntoResponse {
 
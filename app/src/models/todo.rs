@@ -1,4 +1,23 @@
 use super::Todo;
+use rustavel_core::facades::datetime::now_app;
+use rustavel_core::facades::faker::Faker;
+use rustavel_core::mvc::factory::HasFactory;
+use time::Duration;
+
 impl Todo {
 
-}
\ No newline at end of file
+}
+
+impl HasFactory for Todo {
+    fn definition(faker: &mut Faker) -> Self {
+        let now = now_app();
+        let created_at = faker.date_time_between(now - Duration::days(30), now);
+        Todo {
+            id: 0,
+            title: faker.sentence(4),
+            done: faker.boolean(),
+            created_at,
+            updated_at: faker.date_time_between(created_at, now),
+        }
+    }
+}
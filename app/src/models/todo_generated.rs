@@ -1,7 +1,8 @@
-use rustavel_core::mvc::model::Model;
+use macros::Model;
 use serde::{Deserialize, Serialize};
 use time::PrimitiveDateTime;
-#[derive(Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Clone, Serialize, Deserialize, sqlx::FromRow, Model)]
+#[model(table = "todos", primary_key = "id", timestamps)]
 pub struct Todo {
     pub id: u64,
     pub title: String,
@@ -9,18 +10,3 @@ pub struct Todo {
     pub created_at: PrimitiveDateTime,
     pub updated_at: PrimitiveDateTime,
 }
-
-impl Model for Todo {
-    type PrimaryKey = u64;
-
-    fn table() -> &'static str {
-        "todos"
-    }
-    fn primary_key() -> &'static str {
-        "id"
-    }
-    fn columns() -> &'static [&'static str] {
-        &["id", "title", "done", "created_at", "updated_at"]
-    }
-}
-
@@ -6,6 +6,8 @@ mod routes;
 
 
 use rustavel_core::config::CONFIG;
+use rustavel_core::db::rules::verify_all;
+use rustavel_core::http::tenancy::identify_tenant;
 use rustavel_core::logger;
 use rustavel_core::state::AppState;
 // use crate::http::controllers::test_controller::register;
@@ -29,9 +31,18 @@ async fn main() {
 
     let state = AppState { routes: routes_map };
 
+    // `unique:` / `exists:` rules of any `CheckMate` struct pointing at tables or columns that don't exist
+    if let Err(missing) = verify_all().await {
+        for message in missing {
+            logger::warn(&format!("validation rule: {}", message));
+        }
+    }
+
     // take type annotation
     let app = built.router
         .layer(DefaultBodyLimit::max(CONFIG.app.max_upload_size))
+        // passes through unless TENANCY_MODE is set
+        .layer(axum::middleware::from_fn(identify_tenant))
         .with_state(state);
 
     //
@@ -17,8 +17,32 @@ fn confirm(message: &str) -> bool {
         .interact()
         .unwrap()
 }
+
+/// Ask before touching the database when `APP_ENV` is production.
+fn confirm_production(message: &str) {
+    if CONFIG.app.env == "production" && !confirm(message) {
+        title(TitleKind::Info,"Cancelled...");
+        std::process::exit(0);
+    }
+}
+
+/// Compile and run the `database` binary with the given flags, returning its exit code.
+fn run_database(flags: &[&str]) -> i32 {
+    let mut args  = vec!["run", "--package", "rustavel-db", "--bin", "database"];
+    if !flags.is_empty() {
+        args.push("--");
+        args.extend_from_slice(flags);
+    }
+    ProcessCommand::new("cargo")
+        .args(args)
+        .status()
+        .unwrap()
+        .code()
+        .unwrap_or(1)
+}
 use crate::general::lib::{generate_laravel_app_key, set_env_value};
 use crate::make::model::{model, NewModelArgs};
+use crate::make::seeder::{seeder, NewSeederArgs};
 
 #[derive(Parser)]
 #[command(name = "artisan")]
@@ -32,10 +56,14 @@ enum Commands {
     //// app key generate
     KeyGenerate,
     Migrate {
-        /// rollback step count
+        /// rollback batch count
         #[arg(long, default_value_t = 0)]
         rollback: i64,
 
+        /// rollback this many migrations, whatever batch they're in
+        #[arg(long, default_value_t = 0)]
+        step: i64,
+
         ///  Drop all tables and re-run all migrations
         #[arg(long)]
         fresh: bool,
@@ -44,6 +72,111 @@ enum Commands {
         #[arg(long)]
         passive: bool,
 
+        /// Run seeders after migrations
+        #[arg(long)]
+        seed: bool,
+
+        /// Accept edited migrations by storing their current checksums
+        #[arg(long)]
+        repair_checksums: bool,
+
+    },
+    /// Show the status of each migration
+    #[command(name = "migrate:status")]
+    MigrateStatus,
+    /// Check pending migrations for unsafe or invalid definitions, exits non-zero on errors
+    #[command(name = "migrate:lint")]
+    MigrateLint,
+    /// Rollback all migrations
+    #[command(name = "migrate:reset")]
+    MigrateReset,
+    /// Rollback all migrations and run them again
+    #[command(name = "migrate:refresh")]
+    MigrateRefresh {
+        /// Run seeders afterwards
+        #[arg(long)]
+        seed: bool,
+    },
+    /// Dump the database structure to database/schema, loaded by `migrate` on an empty database
+    #[command(name = "schema:dump")]
+    SchemaDump {
+        /// Delete the migration files the dump contains, not available with tenancy
+        #[arg(long)]
+        prune: bool,
+    },
+    /// Write an ER diagram and a per-table markdown reference of the database
+    #[command(name = "schema:docs")]
+    SchemaDocs {
+        /// Diagram format
+        #[arg(long, default_value = "mermaid", value_parser = ["mermaid", "dot", "markdown"])]
+        format: String,
+        /// Output directory (default: database/docs)
+        #[arg(long)]
+        output: Option<String>,
+    },
+    /// Dump the database as plain SQL, no mysqldump / sqlite3 needed
+    #[command(name = "db:dump")]
+    DbDump {
+        /// Only these tables (comma separated)
+        #[arg(long)]
+        tables: Option<String>,
+        /// Rows only
+        #[arg(long, conflicts_with = "schema_only")]
+        data_only: bool,
+        /// Structure only
+        #[arg(long)]
+        schema_only: bool,
+        /// Output file (default: database/dumps/dump-<date>.sql)
+        #[arg(long)]
+        output: Option<String>,
+    },
+    /// Run an SQL file written by db:dump
+    #[command(name = "db:restore")]
+    DbRestore {
+        file: String,
+    },
+    /// Show engine, version, size, table count and open connections
+    #[command(name = "db:show")]
+    DbShow {
+        /// Print JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show columns, indexes, foreign keys and row count of a table
+    #[command(name = "db:table")]
+    DbTable {
+        name: String,
+        /// Print JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Create the database named in DB_DATABASE
+    #[command(name = "db:create")]
+    DbCreate,
+    /// Drop all views and tables
+    #[command(name = "db:wipe")]
+    DbWipe,
+    /// Run the migrations of every tenant in TENANTS
+    #[command(name = "tenants:migrate")]
+    TenantsMigrate {
+        /// rollback batch count
+        #[arg(long, default_value_t = 0)]
+        rollback: i64,
+
+        ///  Drop all tables and re-run all migrations
+        #[arg(long)]
+        fresh: bool,
+
+        /// Show the status of each migration instead
+        #[arg(long)]
+        status: bool,
+    },
+    /// Seed the database with records
+    #[command(name = "db:seed")]
+    DbSeed {
+        /// The seeder to run (default: DatabaseSeeder)
+        #[arg(long)]
+        class: Option<String>,
     },
     Serv,
     Make {
@@ -58,7 +191,9 @@ enum Commands {
 enum MakeCmd {
     /// Create a new migration file
     Migration(NewMigArgs),
-    Model(NewModelArgs)
+    Model(NewModelArgs),
+    /// Create a new seeder file
+    Seeder(NewSeederArgs),
 }
 
 
@@ -73,12 +208,9 @@ async fn main() {
     match cli.command {
 
         Commands::KeyGenerate => {
-            if !CONFIG.app.key.is_empty() {
-                if !confirm("Are you sure you want to regenerate key?") {
-
-                    title(TitleKind::Error,"Application key set successfully.");
-                    std::process::exit(0);
-                }
+            if !CONFIG.app.key.is_empty() && !confirm("Are you sure you want to regenerate key?") {
+                title(TitleKind::Error,"Application key set successfully.");
+                std::process::exit(0);
             }
 
             let app_key = generate_laravel_app_key();
@@ -93,36 +225,131 @@ async fn main() {
             }
 
         }
-        Commands::Migrate  { rollback, fresh, passive } => {
+        Commands::Migrate  { rollback, step, fresh, passive, seed, repair_checksums } => {
 
-            if CONFIG.app.env == "production" {
-                if !confirm("Are you sure you want to run migration in production mode?") {
+            confirm_production("Are you sure you want to run migration in production mode?");
 
-                    title(TitleKind::Info,"Cancelled...");
-                    std::process::exit(0);
-                }
+            let rollback_str = rollback.to_string();
+            let step_str = step.to_string();
+            let mut flags = vec![];
+            if rollback != 0 {
+                flags.push("--rollback");
+                flags.push(&rollback_str);
             }
-            let mut args  = vec!["run", "--package", "rustavel-db", "--bin", "database"];
-            if rollback > 0 || fresh || passive {
-                args.push("--");
+            if step != 0 {
+                flags.push("--step");
+                flags.push(&step_str);
+            }
+            if fresh {
+                flags.push("--fresh");
+            }
+            if passive {
+                flags.push("--passive");
+            }
+            if seed {
+                flags.push("--seed");
+            }
+            if repair_checksums {
+                flags.push("--repair-checksums");
+            }
+            std::process::exit(run_database(&flags));
+        }
+        Commands::MigrateStatus => {
+            std::process::exit(run_database(&["--status"]));
+        }
+        Commands::MigrateLint => {
+            std::process::exit(run_database(&["--lint"]));
+        }
+        Commands::MigrateReset => {
+            confirm_production("Are you sure you want to rollback all migrations in production mode?");
+            std::process::exit(run_database(&["--reset"]));
+        }
+        Commands::MigrateRefresh { seed } => {
+            confirm_production("Are you sure you want to refresh migrations in production mode?");
+            let flags: &[&str] = if seed { &["--refresh", "--seed"] } else { &["--refresh"] };
+            std::process::exit(run_database(flags));
+        }
+        Commands::SchemaDump { prune } => {
+            let flags: &[&str] = if prune { &["--schema-dump", "--prune"] } else { &["--schema-dump"] };
+            std::process::exit(run_database(flags));
+        }
+        Commands::SchemaDocs { format, output } => {
+            let mut flags = vec!["--schema-docs", format.as_str()];
+            if let Some(output) = &output {
+                flags.push("--output");
+                flags.push(output);
+            }
+            std::process::exit(run_database(&flags));
+        }
+        Commands::DbDump { tables, data_only, schema_only, output } => {
+            let mut flags = vec!["--db-dump"];
+            if let Some(tables) = &tables {
+                flags.push("--tables");
+                flags.push(tables);
+            }
+            if data_only {
+                flags.push("--data-only");
+            }
+            if schema_only {
+                flags.push("--schema-only");
+            }
+            if let Some(output) = &output {
+                flags.push("--output");
+                flags.push(output);
+            }
+            std::process::exit(run_database(&flags));
+        }
+        Commands::DbRestore { file } => {
+            confirm_production("Are you sure you want to restore a dump in production mode?");
+            std::process::exit(run_database(&["--db-restore", &file]));
+        }
+        Commands::DbShow { json } => {
+            let flags: &[&str] = if json { &["--db-show", "--json"] } else { &["--db-show"] };
+            std::process::exit(run_database(flags));
+        }
+        Commands::DbTable { name, json } => {
+            let mut flags = vec!["--db-table", name.as_str()];
+            if json {
+                flags.push("--json");
+            }
+            std::process::exit(run_database(&flags));
+        }
+        Commands::DbCreate => {
+            std::process::exit(run_database(&["--db-create"]));
+        }
+        Commands::DbWipe => {
+            confirm_production("Are you sure you want to drop all tables and views in production mode?");
+            std::process::exit(run_database(&["--db-wipe"]));
+        }
+        Commands::TenantsMigrate { rollback, fresh, status } => {
+            if !status {
+                confirm_production("Are you sure you want to migrate every tenant in production mode?");
             }
-            let rollback_str = rollback.to_string();
 
+            let rollback_str = rollback.to_string();
+            let mut flags = vec!["--tenants"];
+            if status {
+                flags.push("--status");
+            }
             if rollback != 0 {
-                args.push("--rollback");
-                args.push(&rollback_str);
+                flags.push("--rollback");
+                flags.push(&rollback_str);
             }
             if fresh {
-                args.push("--fresh");
+                flags.push("--fresh");
             }
-            if passive {
-                args.push("--passive");
+            std::process::exit(run_database(&flags));
+        }
+        Commands::DbSeed { class } => {
+
+            confirm_production("Are you sure you want to seed database in production mode?");
+
+            let mut flags = vec!["--seed-only"];
+            if let Some(class) = &class {
+                flags.push("--class");
+                flags.push(class);
             }
-            // compile and run database
-            ProcessCommand::new("cargo")
-                .args(args)
-                .status()
-                .unwrap();
+            run_database(&flags);
         }
         Commands::Serv => {
             println!("Starting rustavel-app with hot-reload (cargo watch)...");
@@ -154,11 +381,17 @@ async fn main() {
                     });
                 },
                 MakeCmd::Model(args) => {
-                    let _ = model(&args).await.unwrap_or_else(|e| {
+                    model(&args).await.unwrap_or_else(|e| {
                         println!("{:?}",e);
                         title(TitleKind::Error, &format!("model error: {:?}", e));
                     });
                 }
+                MakeCmd::Seeder(args) => {
+                    seeder(&args).await.unwrap_or_else(|e| {
+                        println!("{:?}",e);
+                        title(TitleKind::Error, &format!("seeder error: {:?}", e));
+                    });
+                }
             }
         }
         // add another command here :)
@@ -1,4 +1,5 @@
 pub mod migration;
 pub mod model;
+pub mod seeder;
 mod make_error;
-
+mod register;
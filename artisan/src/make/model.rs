@@ -28,22 +28,21 @@ struct ModelContext {
     fields: String,
     table: String,
     pkey: String,
-    field_list: String,
 }
 
 
 pub async fn model(args: &NewModelArgs) -> Result<(), MakeError> {
     let model_name = Str::ucfirst( &Str::singular(&args.name) );
+    let table = Str::plural_studly(&model_name,3).to_lowercase();
 
     let mut env = Environment::new();
     env.add_template("model_generated", MODEL_GENERATED_TEMPLATE)?;
 
     let ctx = ModelContext {
         name: model_name.clone(),
-        fields: "".to_string(),
-        field_list: "".to_string(),
-        table: "".to_string(),
-        pkey: "".to_string(),
+        fields: "pub id: u64,".to_string(),
+        table: table.clone(),
+        pkey: "id".to_string(),
     };
 
     let rendered = env.get_template("model_generated")?.render(ctx)?;
@@ -63,7 +62,6 @@ pub async fn model(args: &NewModelArgs) -> Result<(), MakeError> {
 
     if args.has_migration {
         let migration_name = format!("{}Create", &model_name);
-        let table = Str::plural_studly(&model_name,3).to_lowercase();
         println!("Creating migration {}", table);
         let mig_args = NewMigArgs{
             name: migration_name,
@@ -0,0 +1,61 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Register a new module and its struct in a `mod.rs` that uses placeholders.
+///
+/// This function does:
+/// 1. Read `mod.rs` at `mod_rs_path`.
+/// 2. Validate that both placeholders exist.
+/// 3. Check duplicates for module and struct.
+/// 4. Append `pub mod <module>;` and `Box::new(<struct_path> {})` before the placeholders.
+pub fn register_in_mod_rs(
+    mod_rs_path: &Path,
+    module_name: &str,
+    struct_path: &str,
+    mod_placeholder: &str,
+    trait_placeholder: &str,
+) -> io::Result<()> {
+    // Read content
+    let content = fs::read_to_string(mod_rs_path)?;
+
+    // Validate placeholders exist
+    if !content.contains(mod_placeholder) {
+        return Err(io::Error::other(
+            format!("Placeholder '{}' not found in mod.rs", mod_placeholder),
+        ));
+    }
+    if !content.contains(trait_placeholder) {
+        return Err(io::Error::other(
+            format!("Placeholder '{}' not found in mod.rs", trait_placeholder),
+        ));
+    }
+
+    // Check duplicates
+    if content.contains(&format!("pub mod {};", module_name)) {
+        return Err(io::Error::other(
+            format!("Module '{}' already exists in mod.rs", module_name),
+        ));
+    }
+    if content.contains(&format!("Box::new({} {{}})", struct_path)) {
+        return Err(io::Error::other(
+            format!("Struct '{}' already exists in mod.rs", struct_path),
+        ));
+    }
+
+    // Append module and struct before placeholders
+    let new_content = content
+        .replace(
+            mod_placeholder,
+            &format!("pub mod {};\n{}", module_name, mod_placeholder),
+        )
+        .replace(
+            trait_placeholder,
+            &format!("Box::new({} {{}}),\n        {}", struct_path, trait_placeholder),
+        );
+
+    // Write back to mod.rs
+    fs::write(mod_rs_path, new_content)?;
+
+    Ok(())
+}
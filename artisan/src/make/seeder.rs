@@ -0,0 +1,90 @@
+use clap::Args;
+use minijinja::Environment;
+use illuminate_string::Str;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::Instant;
+use rustavel_core::facades::terminal_ui::{operation, Status};
+use rustavel_core::facades::file_content::FileContent;
+use crate::make::make_error::MakeError;
+use crate::make::register::register_in_mod_rs;
+
+const SEEDER_TEMPLATE: &str = include_str!("templates/seeder.rs.j2");
+
+#[derive(Args, Debug)]
+#[command(about = "Create a new seeder file")]
+pub struct NewSeederArgs {
+    /// The name of the seeder
+    pub name: String,
+}
+
+#[derive(serde::Serialize)]
+struct SeederContext {
+    name: String,
+}
+
+/// Create and persist a new seeder file from CLI (artisan) input.
+///
+/// This function does:
+/// 1. Studly-case the seeder name (`Seeder` suffix is added when missing).
+/// 2. Render the seeder source code using the seeder template.
+/// 3. Write it to `database/src/seeders/<snake_name>.rs`.
+/// 4. Register the new seeder in `database/src/seeders/mod.rs`.
+/// 5. Report the operation status and execution time.
+pub async fn seeder(args: &NewSeederArgs) -> Result<(), MakeError> {
+    let start = Instant::now();
+
+    let mut name = Str::studly(&args.name);
+    if !name.ends_with("Seeder") {
+        name.push_str("Seeder");
+    }
+    let file_name = Str::snake(&name, "_");
+
+    let target_path = std::env::current_dir()?
+        .join("database/src/seeders")
+        .join(format!("{}.rs", file_name));
+
+    if target_path.exists() {
+        return Err(MakeError::Io(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("Seeder '{}' already exists", target_path.display()),
+        )));
+    }
+
+    let mut env = Environment::new();
+    env.add_template("seeder", SEEDER_TEMPLATE)?;
+    let rendered = env
+        .get_template("seeder")?
+        .render(SeederContext { name: name.clone() })?;
+
+    if let Some(parent) = target_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    FileContent::put(target_path.to_str().unwrap(), &rendered).await?;
+
+    register_new_seeder(&file_name, &name)?;
+
+    operation(
+        &format!("seeder created: {}", name),
+        start.elapsed(),
+        Status::Done,
+    );
+
+    Ok(())
+}
+
+/// Update `database/src/seeders/mod.rs` to register a new seeder.
+pub fn register_new_seeder(module_name: &str, struct_raw: &str) -> io::Result<()> {
+    let mod_rs_path: PathBuf = std::env::current_dir()?
+        .join("database/src/seeders/mod.rs");
+
+    register_in_mod_rs(
+        &mod_rs_path,
+        module_name,
+        &format!("{}::{}", module_name, struct_raw),
+        "// #[placeholder-add-seeder-mods] DO NOT REMOVE THIS COMMENT, OTHERWISE AUTOMATIC ADD WILL BREAK",
+        "// #[placeholder-add-seeder-trait] DO NOT REMOVE THIS COMMENT, OTHERWISE AUTOMATIC ADD WILL BREAK",
+    )
+}
@@ -6,6 +6,14 @@ pub enum DatabaseEngine {
     Sqlite,
 }
 
+/// What `migrate` does when an applied migration was edited (`DB_MIGRATION_CHECKSUM`).
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChecksumPolicy {
+    Warn,
+    Fail,
+    Off,
+}
+
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub struct DatabaseConfig {
@@ -18,6 +26,19 @@ pub struct DatabaseConfig {
     pub prefix: String,
     pub collection: String,
     pub charset: String,
+    /// Seconds `migrate` waits for another migrator to finish (`DB_MIGRATION_LOCK_TIMEOUT`)
+    pub migration_lock_timeout: u64,
+    /// Seconds after which a SQLite migration lock is taken over, its holder is assumed dead (`DB_MIGRATION_LOCK_STALE_AFTER`)
+    pub migration_lock_stale_after: u64,
+    pub migration_checksum: ChecksumPolicy,
+    /// Log every statement (`DB_LOG_QUERIES`), `None` follows `APP_DEBUG`
+    pub log_queries: Option<bool>,
+    /// Statements taking at least this many milliseconds are logged as warnings (`DB_SLOW_QUERY_MS`)
+    pub slow_query_ms: Option<u64>,
+    /// Seconds the table / column names used by `unique:` and `exists:` are cached (`DB_SCHEMA_CACHE_TTL`)
+    pub schema_cache_ttl: u64,
+    /// Times a transaction is run again after a deadlock or serialization failure (`DB_TRANSACTION_RETRIES`)
+    pub transaction_retries: u32,
 }
 
 impl Default for DatabaseConfig {
@@ -32,6 +53,13 @@ impl Default for DatabaseConfig {
             prefix: "".into(),
             collection: "utf8mb4_unicode_ci".into(),
             charset: "utf8mb4".into(),
+            migration_lock_timeout: 60,
+            migration_lock_stale_after: 3600,
+            migration_checksum: ChecksumPolicy::Warn,
+            log_queries: None,
+            slow_query_ms: None,
+            schema_cache_ttl: 60,
+            transaction_retries: 3,
         }
     }
 }
@@ -77,14 +105,33 @@ impl DatabaseConfig {
         if let Ok(v) = env::var("DB_CHARSET") {
             cfg.charset = v;
         }
+        if let Ok(v) = env::var("DB_MIGRATION_LOCK_TIMEOUT") {
+            cfg.migration_lock_timeout = v.parse().expect("DB_MIGRATION_LOCK_TIMEOUT must be a number");
+        }
+        if let Ok(v) = env::var("DB_MIGRATION_LOCK_STALE_AFTER") {
+            cfg.migration_lock_stale_after = v.parse().expect("DB_MIGRATION_LOCK_STALE_AFTER must be a number");
+        }
+        if let Ok(v) = env::var("DB_MIGRATION_CHECKSUM") {
+            if let Some(policy) = ChecksumPolicy::from_str(&v) {
+                cfg.migration_checksum = policy;
+            } else {
+                eprintln!("Invalid DB_MIGRATION_CHECKSUM value: {}", v);
+            }
+        }
 
-        // make DATABASE_URL
-        if cfg.connection == DatabaseEngine::Mysql {
-
-
-        } else if cfg.connection == DatabaseEngine::Sqlite {
-
+        if let Ok(v) = env::var("DB_LOG_QUERIES") {
+            cfg.log_queries = Some(v == "true" || v == "1");
         }
+        if let Ok(v) = env::var("DB_SLOW_QUERY_MS") {
+            cfg.slow_query_ms = Some(v.parse().expect("DB_SLOW_QUERY_MS must be a number"));
+        }
+        if let Ok(v) = env::var("DB_SCHEMA_CACHE_TTL") {
+            cfg.schema_cache_ttl = v.parse().expect("DB_SCHEMA_CACHE_TTL must be a number");
+        }
+        if let Ok(v) = env::var("DB_TRANSACTION_RETRIES") {
+            cfg.transaction_retries = v.parse().expect("DB_TRANSACTION_RETRIES must be a number");
+        }
+
         cfg
     }
 }
@@ -98,3 +145,14 @@ impl DatabaseEngine {
         }
     }
 }
+
+impl ChecksumPolicy {
+    fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "warn" => Some(ChecksumPolicy::Warn),
+            "fail" => Some(ChecksumPolicy::Fail),
+            "off" => Some(ChecksumPolicy::Off),
+            _ => None,
+        }
+    }
+}
@@ -2,11 +2,13 @@ use once_cell::sync::Lazy;
 
 pub mod app;
 pub mod database;
+pub mod tenancy;
 
 #[derive(Debug)]
 pub struct Config {
     pub app: app::AppConfig,
     pub database: database::DatabaseConfig,
+    pub tenancy: tenancy::TenancyConfig,
     // pub cache: CacheConfig, [sample how to add another one next time]
 }
 
@@ -17,9 +19,10 @@ impl Config {
         Self {
             app: app::AppConfig::from_env(),
             database: database::DatabaseConfig::from_env(),
+            tenancy: tenancy::TenancyConfig::from_env(),
             // cache: CacheConfig::from_env(), [sample how to add another one next time]
         }
     }
 }
 
-pub static CONFIG: Lazy<Config> = Lazy::new(|| Config::load());
+pub static CONFIG: Lazy<Config> = Lazy::new(Config::load);
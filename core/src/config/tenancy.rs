@@ -0,0 +1,113 @@
+use std::env;
+
+/// How a tenant's data is kept apart (`TENANCY_MODE`).
+#[derive(Debug, Clone, PartialEq)]
+pub enum TenancyMode {
+    /// Single tenant, `identify_tenant` lets every request through.
+    Off,
+    /// Tenants share the database, each table is prefixed (`TENANCY_PREFIX`).
+    Prefix,
+    /// Every tenant has its own database (`TENANCY_DATABASE`).
+    Database,
+}
+
+/// Where `identify_tenant` reads the tenant id from (`TENANCY_RESOLVER`).
+#[derive(Debug, Clone, PartialEq)]
+pub enum TenantResolver {
+    /// `acme.example.com` is tenant `acme`.
+    Subdomain,
+    /// The `TENANCY_HEADER` header, `X-Tenant` by default.
+    Header,
+    /// The first path segment, `/acme/posts` is tenant `acme`.
+    Path,
+}
+
+#[derive(Debug, Clone)]
+pub struct TenancyConfig {
+    pub mode: TenancyMode,
+    pub resolver: TenantResolver,
+    pub header: String,
+    /// Table prefix of a tenant, `{tenant}` is replaced by its id
+    pub prefix: String,
+    /// Database (SQLite: file) of a tenant, `{tenant}` is replaced by its id
+    pub database: String,
+    /// Registered tenant ids (`TENANTS=acme,globex`), anything else is a 404
+    pub tenants: Vec<String>,
+}
+
+impl Default for TenancyConfig {
+    fn default() -> Self {
+        Self {
+            mode: TenancyMode::Off,
+            resolver: TenantResolver::Subdomain,
+            header: "X-Tenant".into(),
+            prefix: "{tenant}_".into(),
+            database: "tenant_{tenant}".into(),
+            tenants: Vec::new(),
+        }
+    }
+}
+
+impl TenancyConfig {
+    pub fn from_env() -> Self {
+        let mut cfg = Self::default();
+
+        if let Ok(v) = env::var("TENANCY_MODE") {
+            if let Some(mode) = TenancyMode::from_str(&v) {
+                cfg.mode = mode;
+            } else {
+                eprintln!("Invalid TENANCY_MODE value: {}", v);
+            }
+        }
+        if let Ok(v) = env::var("TENANCY_RESOLVER") {
+            if let Some(resolver) = TenantResolver::from_str(&v) {
+                cfg.resolver = resolver;
+            } else {
+                eprintln!("Invalid TENANCY_RESOLVER value: {}", v);
+            }
+        }
+        if let Ok(v) = env::var("TENANCY_HEADER") {
+            cfg.header = v;
+        }
+        if let Ok(v) = env::var("TENANCY_PREFIX") {
+            cfg.prefix = v;
+        }
+        if let Ok(v) = env::var("TENANCY_DATABASE") {
+            cfg.database = v;
+        }
+        if let Ok(v) = env::var("TENANTS") {
+            cfg.tenants = v
+                .split(',')
+                .map(|tenant| tenant.trim().to_string())
+                .filter(|tenant| !tenant.is_empty())
+                .collect();
+        }
+        cfg
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.mode != TenancyMode::Off
+    }
+}
+
+impl TenancyMode {
+    fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "off" | "" => Some(TenancyMode::Off),
+            "prefix" => Some(TenancyMode::Prefix),
+            "database" => Some(TenancyMode::Database),
+            _ => None,
+        }
+    }
+}
+
+impl TenantResolver {
+    fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "subdomain" => Some(TenantResolver::Subdomain),
+            "header" => Some(TenantResolver::Header),
+            "path" => Some(TenantResolver::Path),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,88 @@
+//! Structure of an existing database, read back from the catalog
+//! (`information_schema` on MySQL, `pragma_*` functions on SQLite).
+//!
+//! Filled by `Schema::get_table_info()` / `Schema::introspect()`; used by
+//! `schema:docs`, the `db:*` commands and anything else that needs to look at live tables.
+
+use serde::Serialize;
+
+/// One table with its columns, indexes and outgoing foreign keys.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TableInfo {
+    pub name: String,
+    /// Empty when the table has no comment, always empty on SQLite
+    pub comment: String,
+    pub columns: Vec<ColumnInfo>,
+    pub indexes: Vec<IndexInfo>,
+    pub foreign_keys: Vec<ForeignKeyInfo>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ColumnInfo {
+    pub name: String,
+    /// Type as the database reports it, e.g. `varchar(127)` or `integer`
+    pub data_type: String,
+    pub nullable: bool,
+    /// Default expression as the database reports it, `None` when there is none
+    pub default: Option<String>,
+    pub primary: bool,
+    /// Empty when the column has no comment, always empty on SQLite
+    pub comment: String,
+}
+
+/// Index with its columns in key order; MySQL lists the primary key as `PRIMARY`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IndexInfo {
+    pub name: String,
+    pub columns: Vec<String>,
+    pub unique: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ForeignKeyInfo {
+    pub column: String,
+    pub foreign_table: String,
+    pub referenced_column: String,
+}
+
+/// Connection summary shown by `db:show`, filled by `Schema::overview()`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DatabaseOverview {
+    /// `MySQL` or `SQLite`
+    pub engine: String,
+    pub version: String,
+    pub database: String,
+    /// Data plus indexes in bytes
+    pub size_bytes: i64,
+    pub tables: usize,
+    pub views: usize,
+    /// `None` on SQLite, which has no server
+    pub open_connections: Option<i64>,
+}
+
+impl ColumnInfo {
+    /// Blob / binary columns, dumped as hex literals.
+    pub fn is_binary(&self) -> bool {
+        let data_type = self.data_type.to_lowercase();
+        data_type.contains("blob") || data_type.contains("binary")
+    }
+
+    /// Integer, decimal, floating point and boolean columns.
+    pub fn is_numeric(&self) -> bool {
+        let data_type = self.data_type.to_lowercase();
+        ["int", "dec", "numeric", "real", "float", "double", "bool"]
+            .iter()
+            .any(|t| data_type.contains(t))
+    }
+}
+
+impl TableInfo {
+    pub fn column(&self, name: &str) -> Option<&ColumnInfo> {
+        self.columns.iter().find(|c| c.name == name)
+    }
+
+    /// Whether `column` references another table.
+    pub fn is_foreign(&self, column: &str) -> bool {
+        self.foreign_keys.iter().any(|k| k.column == column)
+    }
+}
@@ -0,0 +1,441 @@
+//! Static checks of migration definitions, run by `migrate` before any of their
+//! SQL and by `artisan migrate:lint`.
+//!
+//! `Table::validate()` checks one definition on its own. `MigrationLinter` also
+//! follows the tables created and altered so far, to check foreign keys and
+//! columns added to tables that may already hold rows.
+//!
+//! # Examples
+//! ```rust,ignore
+//! let mut linter = MigrationLinter::new(DatabaseEngine::Mysql, "");
+//! schema.create("posts", |table| {
+//!     table.id();
+//!     table.text("body").index();
+//! });
+//! let issues = linter.lint("m_2025_02_01_1200_create_posts", schema.current_table().unwrap());
+//! assert_eq!(issues[0].rule, "text-index");
+//! ```
+
+use crate::config::database::DatabaseEngine;
+use crate::db::introspection::TableInfo;
+use crate::db::table::{Column, ColumnDataType, ColumnOption, DefaultValue, ForeignKey, Table, TableAction};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Longest `string()` column MySQL can index with `utf8mb4`.
+pub const MAX_INDEXED_LENGTH: i32 = 255;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Worth a look, doesn't stop `migrate`
+    Warning,
+    /// Fails or corrupts on a real database, `migrate` refuses to run
+    Error,
+}
+
+/// One problem found in a migration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LintIssue {
+    /// Empty when the table was checked outside of a migration
+    pub migration: String,
+    pub table: String,
+    pub column: Option<String>,
+    pub severity: Severity,
+    /// Short stable name of the check, e.g. `text-index`
+    pub rule: &'static str,
+    pub message: String,
+}
+
+impl LintIssue {
+    fn error(table: &Table, column: Option<&str>, rule: &'static str, message: String) -> Self {
+        Self::new(Severity::Error, table, column, rule, message)
+    }
+
+    fn warning(table: &Table, column: Option<&str>, rule: &'static str, message: String) -> Self {
+        Self::new(Severity::Warning, table, column, rule, message)
+    }
+
+    fn new(severity: Severity, table: &Table, column: Option<&str>, rule: &'static str, message: String) -> Self {
+        Self {
+            migration: String::new(),
+            table: table.name.clone(),
+            column: column.map(str::to_string),
+            severity,
+            rule,
+            message,
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for LintIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.migration.is_empty() {
+            write!(f, "{}: ", self.migration)?;
+        }
+        match &self.column {
+            Some(column) => write!(f, "{}.{}", self.table, column)?,
+            None => write!(f, "{}", self.table)?,
+        }
+        write!(f, ": {} [{}]", self.message, self.rule)
+    }
+}
+
+/// Checks migrations in the order they run, keeping track of the tables they define.
+#[derive(Debug)]
+pub struct MigrationLinter {
+    engine: DatabaseEngine,
+    prefix: String,
+    /// Column name and `normalize_type()` of every known table, by prefixed name
+    tables: HashMap<String, Vec<(String, String)>>,
+    /// Tables may exist that the linter can't see, e.g. from a schema dump
+    open_world: bool,
+}
+
+impl MigrationLinter {
+    /// A linter starting from an empty database; `prefix` is the `Schema` prefix.
+    pub fn new(engine: DatabaseEngine, prefix: impl Into<String>) -> Self {
+        Self {
+            engine,
+            prefix: prefix.into(),
+            tables: HashMap::new(),
+            open_world: false,
+        }
+    }
+
+    /// Start from the tables that already exist, as read by `Schema::introspect()`.
+    pub fn with_tables(mut self, tables: &[TableInfo]) -> Self {
+        for table in tables {
+            let columns = table
+                .columns
+                .iter()
+                .map(|column| (column.name.clone(), normalize_type(&column.data_type, &self.engine)))
+                .collect();
+            self.tables.insert(table.name.clone(), columns);
+        }
+        self
+    }
+
+    /// Don't report foreign keys to unknown tables, for a database the linter
+    /// only partly knows, like one about to load a schema dump.
+    pub fn allow_unknown_tables(mut self) -> Self {
+        self.open_world = true;
+        self
+    }
+
+    /// Problems of `table`, the definition of `migration`; then records what it changed.
+    pub fn lint(&mut self, migration: &str, table: &Table) -> Vec<LintIssue> {
+        let mut issues = check_table(table, &self.engine);
+        issues.extend(self.check_foreign_keys(table));
+        if table.action == TableAction::Alter && self.engine == DatabaseEngine::Mysql {
+            issues.extend(rebuild_warnings(table));
+        }
+        self.record(table);
+        for issue in &mut issues {
+            issue.migration = migration.to_string();
+        }
+        issues
+    }
+
+    fn record(&mut self, table: &Table) {
+        let columns = self.tables.entry(table.name.clone()).or_default();
+        if table.action == TableAction::Create {
+            columns.clear();
+        }
+        columns.retain(|(name, _)| !table.drop_columns.contains(name));
+        for column in &table.columns {
+            for (name, type_name) in expand(column, &self.engine) {
+                columns.retain(|(known, _)| *known != name);
+                columns.push((name, type_name));
+            }
+        }
+    }
+
+    fn check_foreign_keys(&self, table: &Table) -> Vec<LintIssue> {
+        let mut issues = Vec::new();
+        let known = self.tables.get(&table.name);
+        let type_of = |name: &str| {
+            table
+                .columns
+                .iter()
+                .flat_map(|column| expand(column, &self.engine))
+                .chain(known.into_iter().flatten().cloned())
+                .find(|(known, _)| known == name)
+                .map(|(_, type_name)| type_name)
+        };
+
+        for key in &table.foreign_keys {
+            let column = Some(key.column_name.as_str());
+            if is_incomplete(key) {
+                // reported by `check_table()`
+                continue;
+            }
+            let Some(local_type) = type_of(&key.column_name) else {
+                issues.push(LintIssue::error(
+                    table,
+                    column,
+                    "foreign-key-missing-column",
+                    format!("foreign key column `{}` is not defined on the table", key.column_name),
+                ));
+                continue;
+            };
+
+            // `on()` takes the name with or without the prefix
+            let foreign = [format!("{}{}", self.prefix, key.foreign_table), key.foreign_table.clone()]
+                .into_iter()
+                .find(|name| *name == table.name || self.tables.contains_key(name));
+            let Some(foreign) = foreign else {
+                if !self.open_world {
+                    issues.push(LintIssue::error(
+                        table,
+                        column,
+                        "foreign-key-missing-table",
+                        format!("references table `{}`, which no earlier migration creates", key.foreign_table),
+                    ));
+                }
+                continue;
+            };
+            let foreign_type = if foreign == table.name {
+                type_of(&key.referenced_column)
+            } else {
+                self.tables[&foreign]
+                    .iter()
+                    .find(|(name, _)| *name == key.referenced_column)
+                    .map(|(_, type_name)| type_name.clone())
+            };
+            match foreign_type {
+                None => issues.push(LintIssue::error(
+                    table,
+                    column,
+                    "foreign-key-missing-reference",
+                    format!("references `{}.{}`, which doesn't exist", key.foreign_table, key.referenced_column),
+                )),
+                Some(foreign_type) if foreign_type != local_type => issues.push(LintIssue::error(
+                    table,
+                    column,
+                    "foreign-key-type-mismatch",
+                    format!(
+                        "is {} but references `{}.{}` of type {}",
+                        local_type, key.foreign_table, key.referenced_column, foreign_type
+                    ),
+                )),
+                Some(_) => {}
+            }
+        }
+        issues
+    }
+}
+
+/// Problems of a single definition that need no other table to spot.
+pub(crate) fn check_table(table: &Table, engine: &DatabaseEngine) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+    for column in &table.columns {
+        let name = Some(column.name.as_str());
+        let indexed = column.index || column.unique;
+        match (&column.data_type, &column.option) {
+            (ColumnDataType::DTNone, _) => {
+                issues.push(LintIssue::error(table, name, "unknown-type", "column has no type".to_string()));
+            }
+            (ColumnDataType::DTString, ColumnOption::Length(length)) if *length <= 0 => {
+                issues.push(LintIssue::error(
+                    table,
+                    name,
+                    "invalid-length",
+                    format!("length must be positive, got {}", length),
+                ));
+            }
+            (ColumnDataType::DTString, ColumnOption::Length(length)) if *length > MAX_INDEXED_LENGTH && indexed => {
+                issues.push(LintIssue::error(
+                    table,
+                    name,
+                    "index-too-long",
+                    format!(
+                        "string of {} characters can't be {}, MySQL keys hold {} utf8mb4 characters",
+                        length,
+                        if column.unique { "unique" } else { "indexed" },
+                        MAX_INDEXED_LENGTH
+                    ),
+                ));
+            }
+            (
+                ColumnDataType::DTText
+                | ColumnDataType::DTTinyText
+                | ColumnDataType::DTMediumText
+                | ColumnDataType::DTLongText
+                | ColumnDataType::DTJson,
+                _,
+            ) if indexed => {
+                issues.push(LintIssue::error(
+                    table,
+                    name,
+                    "text-index",
+                    "MySQL can't index a TEXT column without a prefix length, use `string()`".to_string(),
+                ));
+            }
+            (ColumnDataType::DTBoolean, _) if column.unique => {
+                issues.push(LintIssue::warning(
+                    table,
+                    name,
+                    "unique-boolean",
+                    "a unique boolean allows at most two rows".to_string(),
+                ));
+            }
+            _ => {}
+        }
+        if column.unsigned && column.is_string_type() {
+            issues.push(LintIssue::warning(
+                table,
+                name,
+                "unsigned-string",
+                "`unsigned()` has no effect on a text column".to_string(),
+            ));
+        }
+
+        // rows already in the table would have no value for the new column
+        let adds_column = table.action == TableAction::Alter && !column.change;
+        let fills_itself = matches!(
+            column.data_type,
+            ColumnDataType::DTId | ColumnDataType::DTTimestamps | ColumnDataType::DTSoftDelete
+        );
+        if adds_column && !fills_itself && !column.nullable && column.default == DefaultValue::None {
+            let message = match engine {
+                DatabaseEngine::Sqlite => "SQLite can't add a NOT NULL column without a default",
+                DatabaseEngine::Mysql => "existing rows get an implicit zero value",
+            };
+            issues.push(LintIssue::error(
+                table,
+                name,
+                "not-null-without-default",
+                format!("added as NOT NULL without a default: {}, use `nullable()` or a default", message),
+            ));
+        }
+    }
+    for key in table.foreign_keys.iter().filter(|key| is_incomplete(key)) {
+        issues.push(LintIssue::error(
+            table,
+            Some(key.column_name.as_str()).filter(|c| !c.is_empty()),
+            "foreign-key-incomplete",
+            "foreign key needs a column, `on(table)` and `reference(column)`".to_string(),
+        ));
+    }
+    issues
+}
+
+fn is_incomplete(key: &ForeignKey) -> bool {
+    key.column_name.is_empty() || key.foreign_table.is_empty() || key.referenced_column.is_empty()
+}
+
+/// Changes MySQL makes by copying the whole table, locking writes on large tables.
+fn rebuild_warnings(table: &Table) -> Vec<LintIssue> {
+    let rebuild = |what: String| format!("{} rebuilds the whole table on MySQL, slow on large tables", what);
+    let mut issues = Vec::new();
+    for column in table.columns.iter().filter(|c| c.change) {
+        issues.push(LintIssue::warning(
+            table,
+            Some(&column.name),
+            "mysql-table-rebuild",
+            rebuild("changing a column".to_string()),
+        ));
+    }
+    for column in &table.drop_columns {
+        issues.push(LintIssue::warning(
+            table,
+            Some(column),
+            "mysql-table-rebuild",
+            rebuild("dropping a column".to_string()),
+        ));
+    }
+    for key in &table.foreign_keys {
+        issues.push(LintIssue::warning(
+            table,
+            Some(&key.column_name),
+            "mysql-table-rebuild",
+            rebuild("adding a foreign key with `foreign_key_checks` on".to_string()),
+        ));
+    }
+    if !table.fulltext.is_empty() {
+        issues.push(LintIssue::warning(
+            table,
+            None,
+            "mysql-table-rebuild",
+            rebuild("adding a full-text index".to_string()),
+        ));
+    }
+    issues
+}
+
+/// The columns a definition creates, with their `normalize_type()` names.
+fn expand(column: &Column, engine: &DatabaseEngine) -> Vec<(String, String)> {
+    let sql_type = |name: &str, unsigned: bool| {
+        normalize_type(&format!("{}{}", name, if unsigned { " unsigned" } else { "" }), engine)
+    };
+    let unsigned = column.unsigned;
+    let named = |type_name: &str| vec![(column.name.clone(), sql_type(type_name, unsigned))];
+    match column.data_type {
+        ColumnDataType::DTId => vec![(column.name.clone(), sql_type("bigint", true))],
+        ColumnDataType::DTTimestamps => vec![
+            ("created_at".to_string(), sql_type("timestamp", false)),
+            ("updated_at".to_string(), sql_type("timestamp", false)),
+        ],
+        ColumnDataType::DTMorph => vec![
+            (format!("{}_type", column.name), sql_type("varchar", false)),
+            (format!("{}_id", column.name), sql_type("bigint", true)),
+        ],
+        ColumnDataType::DTBoolean => named("bit"),
+        ColumnDataType::DTTinyInteger => named("tinyint"),
+        ColumnDataType::DTSmallInteger => named("smallint"),
+        ColumnDataType::DTMediumInteger => named("mediumint"),
+        ColumnDataType::DTInteger => named("int"),
+        ColumnDataType::DTBigInteger => named("bigint"),
+        ColumnDataType::DTFloat => named("float"),
+        ColumnDataType::DTDouble => named("double"),
+        ColumnDataType::DTDecimal => named("decimal"),
+        ColumnDataType::DTString => named("varchar"),
+        ColumnDataType::DTText => named("text"),
+        ColumnDataType::DTTinyText => named("tinytext"),
+        ColumnDataType::DTMediumText => named("mediumtext"),
+        ColumnDataType::DTLongText | ColumnDataType::DTJson => named("longtext"),
+        ColumnDataType::DTDate => named("date"),
+        ColumnDataType::DTDateTime => named("datetime"),
+        ColumnDataType::DTTime => named("time"),
+        ColumnDataType::DTTimestamp | ColumnDataType::DTSoftDelete => named("timestamp"),
+        ColumnDataType::DTEnum => named("enum"),
+        ColumnDataType::DTSet => named("set"),
+        ColumnDataType::DTNone => vec![],
+    }
+}
+
+/// `BIGINT(20) UNSIGNED` -> `bigint unsigned`. SQLite compares by type affinity,
+/// so there it is `integer`, `text`, `real` or `numeric`.
+fn normalize_type(data_type: &str, engine: &DatabaseEngine) -> String {
+    let lower = data_type.to_lowercase();
+    let base = lower
+        .split(|c: char| c == '(' || c.is_whitespace())
+        .next()
+        .unwrap_or_default();
+    match engine {
+        DatabaseEngine::Mysql => {
+            let base = if base == "integer" { "int" } else { base };
+            if lower.contains("unsigned") {
+                format!("{} unsigned", base)
+            } else {
+                base.to_string()
+            }
+        }
+        DatabaseEngine::Sqlite => {
+            let affinity = if base.contains("int") {
+                "integer"
+            } else if ["char", "clob", "text", "enum", "set", "json"].iter().any(|t| base.contains(t)) {
+                "text"
+            } else if ["real", "floa", "doub"].iter().any(|t| base.contains(t)) {
+                "real"
+            } else {
+                "numeric"
+            };
+            affinity.to_string()
+        }
+    }
+}
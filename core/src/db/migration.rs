@@ -0,0 +1,25 @@
+use crate::db::schema::Schema;
+use crate::sql::database_client::DbError;
+use async_trait::async_trait;
+
+/// One schema change, as written under `database/src/migrations`.
+#[async_trait]
+pub trait Migration: Send + Sync {
+    async fn up(&self, schema: &mut Schema) -> Result<(), DbError>;
+    async fn down(&self, schema: &mut Schema) -> Result<(), DbError>;
+    fn name(&self) -> &'static str;
+}
+
+/// One row of the `migrations` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MigrationRecord {
+    pub migration: String,
+    pub batch: i64,
+    /// `None` for rows written before checksums existed
+    pub checksum: Option<String>,
+}
+
+/// CRC32 of a migration's generated SQL, as stored in `migrations.checksum`.
+pub fn checksum(sql: &str) -> String {
+    format!("{:08x}", crc32fast::hash(sql.as_bytes()))
+}
@@ -1,13 +1,27 @@
 use crate::db::schema::Schema;
 use tokio::sync::OnceCell;
 
+pub mod introspection;
+pub mod lint;
+pub mod migration;
+pub mod rules;
 pub mod schema;
+pub mod schema_cache;
 pub mod table;
+pub mod tenancy;
+pub mod transaction;
 
 pub static SCHEMA: OnceCell<Schema> = OnceCell::const_new();
 
+/// The shared `Schema`, or the current tenant's while a request is scoped to one.
 pub async fn get_static_schema() -> &'static Schema {
-    SCHEMA
-        .get_or_init(|| async { Schema::new().await.unwrap() })
-        .await
+    try_get_static_schema().await.unwrap()
+}
+
+/// `get_static_schema()` returning the connection error instead of panicking.
+pub async fn try_get_static_schema() -> Result<&'static Schema, sqlx::Error> {
+    if let Some(tenant) = tenancy::current() {
+        return tenancy::schema(&tenant).await;
+    }
+    SCHEMA.get_or_try_init(Schema::new).await
 }
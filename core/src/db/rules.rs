@@ -0,0 +1,50 @@
+//! The `unique:` / `exists:` rules of every `#[derive(CheckMate)]` struct.
+//!
+//! The derive registers the `(table, column)` pairs a struct looks up, so one
+//! `verify_all()` at startup covers every struct, not just the ones called by hand.
+
+use crate::db::schema::Schema;
+use crate::db::try_get_static_schema;
+
+/// The tables and columns one struct's rules look up, registered by `#[derive(CheckMate)]`.
+#[derive(Debug)]
+pub struct DbRules {
+    /// The struct, for the messages
+    pub owner: &'static str,
+    pub columns: &'static [(&'static str, &'static str)],
+}
+
+inventory::collect!(DbRules);
+
+/// Every registered struct, in no particular order.
+pub fn registered() -> Vec<&'static DbRules> {
+    inventory::iter::<DbRules>.into_iter().collect()
+}
+
+/// Checks the rules of every `CheckMate` struct against the shared `Schema`.
+///
+/// Returns one message per missing table or column, starting with the struct's name.
+///
+/// # Examples
+/// ```rust,ignore
+/// if let Err(missing) = rustavel_core::db::rules::verify_all().await {
+///     for message in missing {
+///         logger::warn(&message);
+///     }
+/// }
+/// ```
+pub async fn verify_all() -> Result<(), Vec<String>> {
+    let schema = try_get_static_schema().await.map_err(|e| vec![e.to_string()])?;
+    verify_all_on(schema).await
+}
+
+/// `verify_all()` against a given schema, e.g. a `TestDatabase`'s.
+pub async fn verify_all_on(schema: &Schema) -> Result<(), Vec<String>> {
+    let mut missing = Vec::new();
+    for rules in registered() {
+        if let Err(messages) = schema.verify_columns(rules.columns).await {
+            missing.extend(messages.into_iter().map(|message| format!("{}: {}", rules.owner, message)));
+        }
+    }
+    if missing.is_empty() { Ok(()) } else { Err(missing) }
+}
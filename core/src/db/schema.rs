@@ -1,75 +1,35 @@
 use crate::config::database::DatabaseEngine;
+use crate::config::tenancy::TenancyMode;
 use crate::config::{CONFIG};
-use crate::db::table::{Table, TableAction};
+use crate::db::introspection::{ColumnInfo, DatabaseOverview, ForeignKeyInfo, IndexInfo, TableInfo};
+use crate::db::migration::MigrationRecord;
+use crate::db::schema_cache::{self, SchemaCache, SchemaSnapshot};
+use crate::db::table::{ColumnDataType, Table, TableAction};
+use crate::db::tenancy::Tenant;
 use crate::facades::terminal_ui::{Status, operation};
 use crate::logger;
-use crate::sql::database_client::{DatabaseClient, DbError, MySqlClient, SqliteClient};
+use crate::sql::database_client::{DatabaseClient, DbError, DbLock, MySqlClient, SqliteClient, LOCK_TABLE};
+use crate::sql::dry_run::DryRunClient;
+use crate::sql::executor::QueryExecutor;
 use crate::sql::generator::SqlGenerator;
 use crate::sql::mysql::MySqlGenerator;
+use crate::sql::query_log::LoggingClient;
+use crate::sql::script::{join_statements, split_statements, DumpMode};
 use crate::sql::sqlite::SqliteGenerator;
+use crate::sql::value::{DbRow, DbValue};
 use futures::future::join_all;
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
 use illuminate_string::Str;
+use regex::Regex;
 use sqlx::{MySqlPool, SqlitePool};
 use std::collections::HashMap;
-use tokio::sync::OnceCell;
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::time::Instant;
 
-static TABLES: OnceCell<Vec<String>> = OnceCell::const_new();
-static COLUMNS: OnceCell<Vec<String>> = OnceCell::const_new();
-
-pub async fn get_tables() -> &'static Vec<String> {
-    TABLES
-        .get_or_init(|| async {
-            // println!("get_tables");
-            match Schema::new().await {
-                Ok(schema) => {
-                    // If fetching tables fails, return empty vector instead of panicking
-                    schema.get_tables().await.unwrap_or_default()
-                }
-                Err(e) => {
-                    // Log the error and return empty vector
-                    eprintln!("Failed to initialize schema: {}", e);
-                    Vec::new()
-                }
-            }
-        })
-        .await
-}
-
-pub async fn get_columns() -> &'static Vec<String> {
-    COLUMNS
-        .get_or_init(|| async {
-            // println!("get_columns");
-            let schema = match Schema::new().await {
-                Ok(schema) => schema,
-                Err(e) => {
-                    eprintln!("Failed to initialize schema: {}", e);
-                    return Vec::new();
-                }
-            };
-
-            // Fetch tables safely
-            let tables = schema.get_tables().await.unwrap_or_default();
-
-            let mut result = Vec::new();
-
-            for table in tables {
-                match schema.get_column_listing(&table).await {
-                    Ok(columns) => {
-                        for column in columns {
-                            result.push(format!("{}.{}", table, column));
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("Failed to fetch columns for table {}: {:?}", table, e);
-                    }
-                }
-            }
-
-            result
-        })
-        .await
-}
+/// Metadata cache lifetime of the `from_*_pool` schemas, which don't read `CONFIG`.
+const DEFAULT_SCHEMA_CACHE_TTL: Duration = Duration::from_secs(60);
 
 #[derive(Debug)]
 pub struct Schema {
@@ -79,58 +39,298 @@ pub struct Schema {
     debug: bool,
     tables: HashMap<String, Table>,
     current: Option<Table>,
+    metadata: SchemaCache,
+    /// The real client and the recorder standing in for it, between `begin_dry_run()` and `end_dry_run()`
+    dry_run: Option<(Box<dyn DatabaseClient + Send + Sync>, DryRunClient)>,
 }
 
 impl Schema {
     pub async fn new() -> Result<Self, sqlx::Error> {
+        Self::connect(&CONFIG.database.database, true, CONFIG.database.prefix.clone()).await
+    }
+
+    /// Connects without selecting `DB_DATABASE`, so `create_database()` can run
+    /// before it exists. On SQLite the database file is created if missing.
+    pub async fn without_database() -> Result<Self, sqlx::Error> {
+        Self::connect(&CONFIG.database.database, false, CONFIG.database.prefix.clone()).await
+    }
+
+    /// Connects to the tables of `tenant`: its own prefix or its own database,
+    /// depending on `TENANCY_MODE`.
+    ///
+    /// # Behavior
+    /// - Prefix mode uses `DB_DATABASE` with the `TENANCY_PREFIX` prefix.
+    /// - Database mode uses the `TENANCY_DATABASE` database with `DB_PREFIX`;
+    ///   a missing SQLite file is created.
+    /// - With tenancy off this is the same as `Schema::new()`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use rustavel_core::db::schema::Schema;
+    /// use rustavel_core::db::tenancy::Tenant;
+    ///
+    /// async fn run() {
+    ///     let s = Schema::for_tenant(&Tenant::new("acme")).await.unwrap();
+    ///     println!("{}", s.migrations_table());
+    /// }
+    /// ```
+    pub async fn for_tenant(tenant: &Tenant) -> Result<Self, sqlx::Error> {
+        match CONFIG.tenancy.mode {
+            TenancyMode::Off => Self::new().await,
+            TenancyMode::Prefix => Self::connect(&CONFIG.database.database, true, tenant.prefix()).await,
+            TenancyMode::Database => {
+                // SQLite connects with `mode=rwc` only without a database selected
+                let select = CONFIG.database.connection == DatabaseEngine::Mysql;
+                Self::connect(&tenant.database(), select, CONFIG.database.prefix.clone()).await
+            }
+        }
+    }
+
+    async fn connect(database: &str, select_database: bool, prefix: String) -> Result<Self, sqlx::Error> {
         let debug = CONFIG.app.debug;
 
-        let (generator, client): (Box<dyn SqlGenerator>, Box<dyn DatabaseClient>) =
+        let (generator, client, connection): (Box<dyn SqlGenerator>, Box<dyn DatabaseClient>, &'static str) =
             match CONFIG.database.connection {
                 DatabaseEngine::Mysql => {
-                    let con_string = format!(
-                        "mysql://{}:{}@{}:{}/{}",
+                    let mut con_string = format!(
+                        "mysql://{}:{}@{}:{}",
                         CONFIG.database.username,
                         CONFIG.database.password,
                         CONFIG.database.host,
                         CONFIG.database.port,
-                        CONFIG.database.database,
                     );
+                    if select_database {
+                        con_string = format!("{}/{}", con_string, database);
+                    }
 
                     let pool = MySqlPool::connect(&con_string).await.map_err(|e| {
                         logger::error(&format!("MySQL connection error: {}", e));
                         e
                     })?;
 
-                    (Box::new(MySqlGenerator), Box::new(MySqlClient { pool }))
+                    (Box::new(MySqlGenerator), Box::new(MySqlClient { pool }), "mysql")
                 }
 
                 DatabaseEngine::Sqlite => {
-                    let con_string = format!("sqlite://{}", CONFIG.database.database);
+                    let mut con_string = format!("sqlite://{}", database);
+                    if !select_database {
+                        con_string.push_str("?mode=rwc");
+                    }
 
                     let pool = SqlitePool::connect(&con_string).await.map_err(|e| {
                         logger::error(&format!("SQLite connection error: {}", e));
                         e
                     })?;
 
-                    (Box::new(SqliteGenerator), Box::new(SqliteClient { pool }))
+                    (Box::new(SqliteGenerator), Box::new(SqliteClient { pool }), "sqlite")
                 }
             };
+        let client = LoggingClient::new(
+            client,
+            connection,
+            CONFIG.database.log_queries.unwrap_or(debug),
+            CONFIG.database.slow_query_ms.map(Duration::from_millis),
+        );
 
         Ok(Self {
-            prefix: CONFIG.database.prefix.clone(),
+            prefix,
             generator,
-            client,
+            client: Box::new(client),
             debug,
             tables: HashMap::new(),
             current: None,
+            metadata: SchemaCache::new(Duration::from_secs(CONFIG.database.schema_cache_ttl)),
+            dry_run: None,
         })
     }
 
-    fn fix_table_name(&self, table_name: &str) -> String {
+    /// Builds a `Schema` on an existing SQLite pool, without reading `CONFIG`.
+    ///
+    /// Used by `testing::TestDatabase`; errors are returned but not logged, and
+    /// queries only reach `DB::listen` listeners.
+    pub fn from_sqlite_pool(pool: SqlitePool, prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+            generator: Box::new(SqliteGenerator),
+            client: Box::new(LoggingClient::new(Box::new(SqliteClient { pool }), "sqlite", false, None)),
+            debug: false,
+            tables: HashMap::new(),
+            current: None,
+            metadata: SchemaCache::new(DEFAULT_SCHEMA_CACHE_TTL),
+            dry_run: None,
+        }
+    }
+
+    /// Builds a `Schema` on an existing MySQL pool, without reading `CONFIG`.
+    pub fn from_mysql_pool(pool: MySqlPool, prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+            generator: Box::new(MySqlGenerator),
+            client: Box::new(LoggingClient::new(Box::new(MySqlClient { pool }), "mysql", false, None)),
+            debug: false,
+            tables: HashMap::new(),
+            current: None,
+            metadata: SchemaCache::new(DEFAULT_SCHEMA_CACHE_TTL),
+            dry_run: None,
+        }
+    }
+
+    pub(crate) fn fix_table_name(&self, table_name: &str) -> String {
         format!("{}{}", self.prefix, table_name)
     }
 
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// The migrations repository table, prefixed so every tenant prefix keeps its own.
+    pub fn migrations_table(&self) -> String {
+        self.fix_table_name("migrations")
+    }
+
+    pub(crate) fn client(&self) -> &(dyn DatabaseClient + Send + Sync) {
+        self.client.as_ref()
+    }
+
+    pub(crate) fn generator(&self) -> &(dyn SqlGenerator + Send + Sync) {
+        self.generator.as_ref()
+    }
+
+    /// Executes a parameterised statement, e.g. one built by the write builders.
+    ///
+    /// Errors are logged when `self.debug` is true and returned to the caller.
+    pub async fn execute_params(&self, sql: &str, params: &[DbValue]) -> Result<(), DbError> {
+        self.client.execute_params(sql, params).await.map_err(|e| {
+            if self.debug {
+                logger::error(&format!("{:?}", e));
+            }
+            e
+        })
+    }
+
+    /// `execute_params()` for an `INSERT`, returning the auto-increment key it generated.
+    pub async fn execute_insert(&self, sql: &str, params: &[DbValue]) -> Result<i64, DbError> {
+        self.client.execute_insert(sql, params).await.map_err(|e| {
+            if self.debug {
+                logger::error(&format!("{:?}", e));
+            }
+            e
+        })
+    }
+
+    /// Runs a parameterised query, e.g. one built by the read builders, and returns its rows.
+    pub async fn fetch_rows(&self, sql: &str, params: &[DbValue]) -> Result<Vec<DbRow>, DbError> {
+        self.client.fetch_rows(sql, params).await.map_err(|e| {
+            if self.debug {
+                logger::error(&format!("{:?}", e));
+            }
+            e
+        })
+    }
+
+    /// `fetch_rows()` read as a stream, for results too large to hold in memory.
+    ///
+    /// # Examples
+    /// ```rust,ignore
+    /// let mut rows = schema.fetch_stream("SELECT * FROM `logs`".to_string(), vec![]);
+    /// while let Some(row) = rows.next().await {
+    ///     export(row?)?;
+    /// }
+    /// ```
+    pub fn fetch_stream(&self, sql: String, params: Vec<DbValue>) -> BoxStream<'_, Result<DbRow, DbError>> {
+        let debug = self.debug;
+        self.client
+            .fetch_stream(sql, params)
+            .inspect_err(move |e| {
+                if debug {
+                    logger::error(&format!("{:?}", e));
+                }
+            })
+            .boxed()
+    }
+
+    /// Runs a parameterised `SELECT COUNT(*) ...` and returns the count.
+    pub async fn fetch_count(&self, sql: &str, params: &[DbValue]) -> Result<i64, DbError> {
+        self.client.fetch_count_params(sql, params).await.map_err(|e| {
+            if self.debug {
+                logger::error(&format!("{:?}", e));
+            }
+            e
+        })
+    }
+
+    /// Inserts a single row into a table (prefix applied).
+    ///
+    /// Meant for seeders and tests where no model type is around.
+    /// Application code should prefer `Model::insert()` so timestamps are handled.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use rustavel_core::db::schema::Schema;
+    /// async fn run()  {
+    ///     let s = Schema::new().await.unwrap();
+    ///     s.insert("todos", &[("title", "write docs"), ("done", "0")]).await.unwrap();
+    /// }
+    /// ```
+    pub async fn insert(
+        &self,
+        table_name: impl Into<String>,
+        values: &[(&str, &str)],
+    ) -> Result<(), DbError> {
+        let columns: Vec<&str> = values.iter().map(|(c, _)| *c).collect();
+        let params: Vec<DbValue> = values.iter().map(|(_, v)| DbValue::from(*v)).collect();
+        let sql = self
+            .generator
+            .insert(&self.fix_table_name(&table_name.into()), &columns);
+        self.execute_params(&sql, &params).await
+    }
+
+    /// Counts the rows of a table matching all given column/value pairs (prefix applied).
+    ///
+    /// # Examples
+    /// ```rust
+    /// use rustavel_core::db::schema::Schema;
+    /// async fn run()  {
+    ///     let s = Schema::new().await.unwrap();
+    ///     let done = s.count_where("todos", &[("done", "1")]).await.unwrap();
+    ///     println!("{} todos done", done);
+    /// }
+    /// ```
+    pub async fn count_where(
+        &self,
+        table_name: impl Into<String>,
+        values: &[(&str, &str)],
+    ) -> Result<i64, DbError> {
+        let columns: Vec<&str> = values.iter().map(|(c, _)| *c).collect();
+        let params: Vec<DbValue> = values.iter().map(|(_, v)| DbValue::from(*v)).collect();
+        let sql = self
+            .generator
+            .count_where(&self.fix_table_name(&table_name.into()), &columns);
+        self.client.fetch_count_params(&sql, &params).await.map_err(|e| {
+            if self.debug {
+                logger::error(&format!("{:?}", e));
+            }
+            e
+        })
+    }
+
+    /// Removes every row of a table (prefix applied), keeping its structure.
+    ///
+    /// # Notes
+    /// - MySQL uses `TRUNCATE TABLE`, which also resets auto increment.
+    /// - SQLite has no `TRUNCATE`, rows are removed with a plain `DELETE`.
+    pub async fn truncate(&self, table_name: impl Into<String>) -> Result<(), DbError> {
+        let sql = self
+            .generator
+            .truncate(&self.fix_table_name(&table_name.into()));
+        self.client.execute(&sql).await.map_err(|e| {
+            if self.debug {
+                logger::error(&format!("{:?}", e));
+            }
+            e
+        })
+    }
+
     /// Drops a table if it exists.
     ///
     /// This method:
@@ -624,6 +824,31 @@ impl Schema {
             })
     }
 
+    /// Drops the full-text index made by `table.fulltext(...)`, e.g. in a migration's `down()`.
+    ///
+    /// # Behavior
+    /// - MySQL drops the `FULLTEXT` index.
+    /// - SQLite drops the `<table>_fts` table and its triggers, which `drop_table()` leaves behind.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use rustavel_core::db::schema::Schema;
+    /// async fn run()  {
+    ///     let s = Schema::new().await.unwrap();
+    ///     s.drop_fulltext("posts").await.unwrap();
+    ///     s.drop_table("posts").await.unwrap();
+    /// }
+    /// ```
+    pub async fn drop_fulltext(&self, table_name: impl Into<String>) -> Result<(), DbError> {
+        let sql = self.generator.drop_fulltext(&self.fix_table_name(&table_name.into()));
+        self.client.execute(&sql).await.map_err(|e| {
+            if self.debug {
+                logger::error(&format!("{:?}", e));
+            }
+            e
+        })
+    }
+
     /// Drops a specific view from the database.
     ///
     /// This method:
@@ -673,14 +898,15 @@ impl Schema {
                 e
             })
     }
-    /// Drops all tables in the database.
+    /// Drops all tables of this schema's prefix.
     ///
     /// This method:
-    /// - Uses the database generator to create a SQL command to drop all tables.
-    /// - Executes the drop all tables command using the database client.
+    /// - Lists the tables whose name starts with `prefix()`, the migrations table included.
+    /// - Drops each of them with `drop_table()`.
     ///
     /// # Behavior
-    /// - Attempts to remove all tables from the database.
+    /// - Attempts to remove every table of the prefix; other tenants' tables are kept,
+    ///   though with an empty prefix every table matches.
     /// - Returns success if all tables are dropped or no tables exist.
     /// - Logs any errors if debug mode is enabled.
     ///
@@ -709,12 +935,17 @@ impl Schema {
 
         let tables = self.get_tables().await?;
         let drop_futures: Vec<_> = tables
-            .into_iter()
+            .iter()
+            // a running `migrate --fresh` holds its lock in this table
+            .filter(|table| table.as_str() != LOCK_TABLE)
+            // `drop_table()` adds the prefix back
+            .filter_map(|table| table.strip_prefix(self.prefix.as_str()))
             .map(|table| self.drop_table(table))
             .collect();
 
         // execute all each other
         let results = join_all(drop_futures).await;
+        schema_cache::invalidate();
 
         // check result
         for res in results {
@@ -724,14 +955,15 @@ impl Schema {
         Ok(())
     }
 
-    /// Drops all views in the database.
+    /// Drops all views of this schema's prefix.
     ///
     /// This method:
-    /// - Uses the database generator to create a SQL command to drop all views.
-    /// - Executes the drop all views command using the database client.
+    /// - Lists the views whose name starts with `prefix()`.
+    /// - Drops each of them with `drop_view()`.
     ///
     /// # Behavior
-    /// - Attempts to remove all views from the database.
+    /// - Attempts to remove every view of the prefix; other tenants' views are kept,
+    ///   though with an empty prefix every view matches.
     /// - Returns success if all views are dropped or no views exist.
     /// - Logs any errors if debug mode is enabled.
     ///
@@ -757,10 +989,16 @@ impl Schema {
     /// - Requires appropriate database permissions.
     pub async fn drop_all_views(&self) -> Result<(), DbError> {
         let views = self.get_views().await?;
-        let drop_futures: Vec<_> = views.into_iter().map(|view| self.drop_view(view)).collect();
+        let drop_futures: Vec<_> = views
+            .iter()
+            // `drop_view()` adds the prefix back
+            .filter_map(|view| view.strip_prefix(self.prefix.as_str()))
+            .map(|view| self.drop_view(view))
+            .collect();
 
         // execute all each other
         let results = join_all(drop_futures).await;
+        schema_cache::invalidate();
 
         // check result
         for res in results {
@@ -1298,13 +1536,12 @@ impl Schema {
     /// # Notes
     /// - This method enables flexible table definitions and can be called multiple times to define different tables.
     /// - Ensure appropriate database permissions are granted to create tables in the schema.
-
     pub fn create<F>(&mut self, table_name: impl Into<String>, f: F) -> &mut Self
     where
         F: FnOnce(&mut Table),
     {
         let name = table_name.into();
-        let mut table = Table::new(&format!("{}{}", CONFIG.database.prefix, name));
+        let mut table = Table::new(&self.fix_table_name(&name));
         table.action = TableAction::Create;
         f(&mut table);
 
@@ -1360,13 +1597,12 @@ impl Schema {
     /// # Notes
     /// - This method enables modifications to the existing table structure and can be invoked multiple times for different tables.
     /// - Ensure appropriate database permissions are granted to alter tables in the schema.
-
     pub fn table<F>(&mut self, table_name: impl Into<String>, f: F) -> &mut Self
     where
         F: FnOnce(&mut Table),
     {
         let name = table_name.into();
-        let mut table = Table::new(&format!("{}{}", CONFIG.database.prefix, name));
+        let mut table = Table::new(&self.fix_table_name(&name));
         table.action = TableAction::Alter;
 
         f(&mut table);
@@ -1431,62 +1667,122 @@ impl Schema {
     /// # Notes
     /// - Ensure that the current migration data is valid before calling this method.
     /// - Requires appropriate database permissions to execute the migration.
-
     pub async fn execute_migration(
         &self,
         final_name: &str,
         duration: &Instant,
     ) -> Result<(), DbError> {
         // extract last migration data information
-        if let Some(table) = &self.current {
-            let mut body = vec![];
-            let mut foot = vec![];
-            let mut post = vec![];
-            for column in &table.columns {
-                let (b, f, p) = self.generator.column(&column, &table.name, &table.action);
-                body.push(b);
-                if !f.is_empty() {
-                    foot.push(f);
-                }
-                if !p.is_empty() {
-                    post.push(p);
-                }
+        let sql = self.migration_sql().ok_or(DbError::InvalidTable)?;
+        // logger::info(&format!("Just4debug develop core: \n {}", sql));
+        // execute generated sql
+        let result = self.client.execute(&sql).await;
+        schema_cache::invalidate();
+        match result {
+            Ok(_) => {
+                // logger::success(&format!("Updated table  : \n {}", &table.name));
+                operation(final_name, duration.elapsed(), Status::Done);
+
+                Ok(())
             }
-            for column in &table.drop_columns {
-                body.push(self.generator.drop_column(&column));
+            Err(e) => {
+                // logger::error(&format!("{:?}", e));
+                operation(final_name, duration.elapsed(), Status::Failed);
+                Err(e)
             }
-            for key in &table.foreign_keys {
-                let str = self.generator.foreign_key(&key, &table.name, &table.action);
+        }
+    }
 
-                if !str.is_empty() {
-                    foot.push(str);
-                }
+    /// Generates the SQL `execute_migration` would run for the last `create` / `table` call.
+    ///
+    /// Returns `None` when nothing was defined since the last `clear_current()`.
+    /// The migrator hashes this SQL to detect edited migrations.
+    pub fn migration_sql(&self) -> Option<String> {
+        let table = self.current.as_ref()?;
+        let mut body = vec![];
+        let mut foot = vec![];
+        let mut post = vec![];
+        for column in &table.columns {
+            let (b, f, p) = self.generator.column(column, &table.name, &table.action);
+            body.push(b);
+            if !f.is_empty() {
+                foot.push(f);
             }
-            body.append(&mut foot);
-            let sql = Str::implode(",\n", body);
-            let sql = self.generator.table_sql(
-                &table.name,
-                &sql,
-                &Str::implode(";\n", post),
-                &table.action,
-            );
-            // logger::info(&format!("Just4debug develop core: \n {}", sql));
-            // execute generated sql
-            match self.client.execute(&sql).await {
-                Ok(_) => {
-                    // logger::success(&format!("Updated table  : \n {}", &table.name));
-                    operation(final_name, duration.elapsed(), Status::Done);
-
-                    Ok(())
-                }
-                Err(e) => {
-                    // logger::error(&format!("{:?}", e));
-                    operation(final_name, duration.elapsed(), Status::Failed);
-                    Err(e)
-                }
+            if !p.is_empty() {
+                post.push(p);
             }
-        } else {
-            Err(DbError::InvalidTable)
+        }
+        for column in &table.drop_columns {
+            body.push(self.generator.drop_column(column));
+        }
+        for key in &table.foreign_keys {
+            let str = self.generator.foreign_key(key, &table.name, &table.action);
+
+            if !str.is_empty() {
+                foot.push(str);
+            }
+        }
+        if !table.fulltext.is_empty() {
+            let key = table
+                .columns
+                .iter()
+                .find(|c| c.data_type == ColumnDataType::DTId)
+                .map_or("id", |c| c.name.as_str());
+            let (f, p) = self.generator.fulltext(&table.name, key, &table.fulltext, &table.action);
+            if !f.is_empty() {
+                foot.push(f);
+            }
+            if !p.is_empty() {
+                post.push(p);
+            }
+        }
+        body.append(&mut foot);
+        let sql = Str::implode(",\n", body);
+        Some(self.generator.table_sql(table, &sql, &Str::implode(";\n", post)))
+    }
+
+    /// The table defined by the last `create` / `table` call, before it is executed.
+    pub fn current_table(&self) -> Option<&Table> {
+        self.current.as_ref()
+    }
+
+    /// Forgets the last `create` / `table` definition, so the next migration starts clean.
+    pub fn clear_current(&mut self) {
+        self.current = None;
+    }
+
+    /// Records statements instead of running them until `end_dry_run()`, e.g. to
+    /// call a migration's `up()` without touching the database.
+    ///
+    /// Writes succeed without effect and reads come back empty.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use rustavel_core::db::schema::Schema;
+    ///
+    /// async fn run() {
+    ///     let mut s = Schema::new().await.unwrap();
+    ///     s.begin_dry_run();
+    ///     s.drop_table("users").await.unwrap();
+    ///     assert_eq!(s.end_dry_run().len(), 1); // `users` is still there
+    /// }
+    /// ```
+    pub fn begin_dry_run(&mut self) {
+        if self.dry_run.is_none() {
+            let recorder = DryRunClient::new();
+            let live = std::mem::replace(&mut self.client, Box::new(recorder.clone()));
+            self.dry_run = Some((live, recorder));
+        }
+    }
+
+    /// Ends `begin_dry_run()` and returns the statements it recorded.
+    pub fn end_dry_run(&mut self) -> Vec<String> {
+        match self.dry_run.take() {
+            Some((live, recorder)) => {
+                self.client = live;
+                recorder.statements()
+            }
+            None => vec![],
         }
     }
 
@@ -1521,7 +1817,6 @@ impl Schema {
     /// # Notes
     /// - This method relies on the `has_table` function to perform the check.
     /// - Requires appropriate database permissions to query the table existence.
-
     pub async fn repository_exists(&self) -> Result<bool, DbError> {
         self.has_table("migrations").await
     }
@@ -1556,9 +1851,8 @@ impl Schema {
     /// # Notes
     /// - This method relies on the `fetch_strings` function to perform the retrieval.
     /// - Ensure appropriate database permissions are granted to access the migrations table.
-
     pub async fn get_ran_migrations(&self) -> Result<Vec<String>, DbError> {
-        match self.client.fetch_strings(&self.generator.get_ran()).await {
+        match self.client.fetch_strings(&self.generator.get_ran(&self.migrations_table())).await {
             // note if table not found we don't have error just empty vector
             Ok(ran) => Ok(ran),
             Err(e) => {
@@ -1574,6 +1868,7 @@ impl Schema {
     ///
     /// This method:
     /// - Queries the `migrations` table to fetch the names of migrations that have been executed.
+    /// - Orders them newest first (batch, then id, descending), so they can be rolled back in order.
     ///
     /// # Behavior
     /// - Returns a vector of migration names that have been run.
@@ -1600,11 +1895,10 @@ impl Schema {
     /// # Notes
     /// - This method relies on the `fetch_strings` function to perform the retrieval.
     /// - Ensure appropriate database permissions are granted to access the migrations table.
-
     pub async fn get_ran_migrations_gt(&self, batch: i64) -> Result<Vec<String>, DbError> {
         match self
             .client
-            .fetch_strings_params(&self.generator.get_ran_gt(), &[&batch.to_string()])
+            .fetch_strings_params(&self.generator.get_ran_gt(&self.migrations_table()), &[DbValue::I64(batch)])
             .await
         {
             // note if table not found we don't have error just empty vector
@@ -1618,6 +1912,43 @@ impl Schema {
         }
     }
 
+    /// Retrieves the last `count` executed migrations, newest first.
+    ///
+    /// This method:
+    /// - Queries the `migrations` table ordered by batch and id, descending.
+    ///
+    /// # Behavior
+    /// - Returns migration names in reverse application order, ready for rolling back.
+    /// - Logs any errors encountered during the operation if debug mode is enabled.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use rustavel_core::db::schema::Schema;
+    ///
+    /// async fn run() {
+    ///     let s = Schema::new().await.unwrap();
+    ///     match s.get_last_ran_migrations(2).await {
+    ///         Ok(ran) => println!("Last two migrations: {:?}", ran),
+    ///         Err(e) => eprintln!("Error retrieving ran migrations: {:?}", e),
+    ///     }
+    /// }
+    /// ```
+    pub async fn get_last_ran_migrations(&self, count: u64) -> Result<Vec<String>, DbError> {
+        match self
+            .client
+            .fetch_strings(&self.generator.get_last_ran(&self.migrations_table(), count))
+            .await
+        {
+            Ok(ran) => Ok(ran),
+            Err(e) => {
+                if self.debug {
+                    logger::error(&format!("{:?}", e));
+                }
+                Err(e)
+            }
+        }
+    }
+
     /// Retrieves the next available batch number for migrations.
     ///
     /// This method:
@@ -1651,7 +1982,7 @@ impl Schema {
     pub async fn get_next_batch_number(&self) -> Result<i64, DbError> {
         match self
             .client
-            .fetch_numbers(&self.generator.get_next_batch_number())
+            .fetch_numbers(&self.generator.get_next_batch_number(&self.migrations_table()))
             .await
         {
             Ok(max_vec) => {
@@ -1712,12 +2043,48 @@ impl Schema {
             table.id();
             table.string("migration", 255);
             table.integer("batch");
+            table.string("checksum", 8).nullable();
         })
-        .execute_migration("Creating migration table", &start.into())
+        .execute_migration("Creating migration table", &start)
         .await?;
         Ok(())
     }
 
+    /// Acquires the cross-process migration lock, waiting up to `timeout`.
+    ///
+    /// This method:
+    /// - Uses `GET_LOCK` on MySQL, held by a connection taken out of the pool.
+    /// - Uses a row in the `migrations_lock` table on SQLite.
+    ///
+    /// # Behavior
+    /// - Returns a `DbLock` which must be released with `release()` when migrating is done.
+    /// - Returns `DbError::Locked` naming the holder if the lock isn't free before `timeout`.
+    /// - On SQLite, takes over a lock row older than `stale_after`, left by a process that died.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use rustavel_core::db::schema::Schema;
+    /// use std::time::Duration;
+    ///
+    /// async fn run() {
+    ///     let s = Schema::new().await.unwrap();
+    ///     let lock = s.lock_migrations(Duration::from_secs(60), Duration::from_secs(3600)).await.unwrap();
+    ///     // run migrations...
+    ///     lock.release().await.unwrap();
+    /// }
+    /// ```
+    ///
+    /// # Notes
+    /// - The holder is identified as `HOSTNAME:pid`, which is the pod name on Kubernetes.
+    pub async fn lock_migrations(&self, timeout: Duration, stale_after: Duration) -> Result<DbLock, DbError> {
+        let owner = format!(
+            "{}:{}",
+            std::env::var("HOSTNAME").unwrap_or_else(|_| "unknown-host".to_string()),
+            std::process::id()
+        );
+        self.client.acquire_lock(&self.migrations_table(), &owner, timeout, stale_after).await
+    }
+
     /// Adds a migrated table entry to the migrations repository.
     ///
     /// This method:
@@ -1756,10 +2123,10 @@ impl Schema {
         migration_name: &str,
         batch_number: i64,
     ) -> Result<(), DbError> {
-        let sql = &self.generator.add_migrated_table();
+        let sql = &self.generator.add_migrated_table(&self.migrations_table());
         match self
             .client
-            .execute_params(sql, &[migration_name, &batch_number.to_string()])
+            .execute_params(sql, &[migration_name.into(), batch_number.into()])
             .await
         {
             Ok(_) => Ok(()),
@@ -1772,6 +2139,484 @@ impl Schema {
         }
     }
 
+    /// Adds the `checksum` column to a `migrations` table created before checksums existed.
+    ///
+    /// # Behavior
+    /// - Does nothing when the column is already there.
+    /// - Existing rows keep a `NULL` checksum until they are repaired.
+    pub async fn ensure_migration_checksum_column(&mut self) -> Result<(), DbError> {
+        if self.has_column("migrations", "checksum").await? {
+            return Ok(());
+        }
+        let start = Instant::now();
+        self.table("migrations", |table| {
+            table.string("checksum", 8).nullable();
+        })
+        .execute_migration("Adding checksum to migration table", &start)
+        .await
+    }
+
+    /// Stores the checksum of an applied migration.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use rustavel_core::db::schema::Schema;
+    ///
+    /// async fn run() {
+    ///     let s = Schema::new().await.unwrap();
+    ///     s.set_migration_checksum("m_2025_01_15_1945_create_todos", "1c291ca3").await.unwrap();
+    /// }
+    /// ```
+    pub async fn set_migration_checksum(&self, migration_name: &str, checksum: &str) -> Result<(), DbError> {
+        let sql = &self.generator.set_migration_checksum(&self.migrations_table());
+        self.client
+            .execute_params(sql, &[checksum.into(), migration_name.into()])
+            .await
+            .map_err(|e| {
+                if self.debug {
+                    logger::error(&format!("{:?}", e));
+                }
+                e
+            })
+    }
+
+    /// Retrieves every row of the `migrations` table, in application order.
+    ///
+    /// Read-only: a table from before checksums has no `checksum` column, its
+    /// records come back with `checksum: None` and the column is not added.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use rustavel_core::db::schema::Schema;
+    ///
+    /// async fn run() {
+    ///     let s = Schema::new().await.unwrap();
+    ///     for record in s.get_migration_records().await.unwrap() {
+    ///         println!("{} [{}] {:?}", record.migration, record.batch, record.checksum);
+    ///     }
+    /// }
+    /// ```
+    pub async fn get_migration_records(&self) -> Result<Vec<MigrationRecord>, DbError> {
+        let has_checksum = self.has_column("migrations", "checksum").await?;
+        let sql = self.generator.get_migration_records(&self.migrations_table(), has_checksum);
+        self.fetch_rows(&sql, &[])
+            .await?
+            .iter()
+            .map(|row| {
+                Ok(MigrationRecord {
+                    migration: row.try_get("migration")?,
+                    batch: row.try_get("batch")?,
+                    checksum: row.try_get::<Option<String>>("checksum")?.filter(|c| !c.is_empty()),
+                })
+            })
+            .collect()
+    }
+
+    /// Builds an SQL script that recreates the current structure, for `schema:dump`.
+    ///
+    /// This method:
+    /// - Reads the `CREATE` statement of every table and view from the database itself
+    ///   (`SHOW CREATE TABLE` on MySQL, `sqlite_master` on SQLite).
+    /// - Appends the rows of the `migrations` table, so the migrations squashed into
+    ///   the dump count as ran once it's loaded.
+    ///
+    /// # Behavior
+    /// - Leaves out the migration lock table and MySQL `AUTO_INCREMENT` counters / view definers.
+    /// - Wraps the script in `disable/enable_foreign_key_constraints`, tables come out in name order.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use rustavel_core::db::schema::Schema;
+    ///
+    /// async fn run() {
+    ///     let s = Schema::new().await.unwrap();
+    ///     let dump = s.dump_schema().await.unwrap();
+    ///     std::fs::write("database/schema/sqlite-schema.sql", dump).unwrap();
+    /// }
+    /// ```
+    pub async fn dump_schema(&self) -> Result<String, DbError> {
+        let tables = self.dumpable_tables(&[]).await?;
+        let views = self.get_views().await?;
+
+        let mut statements = vec![self.generator.disable_foreign_key_constraints()];
+        statements.extend(self.structure_statements(&tables, &views).await?);
+
+        let migrations_table = self.migrations_table();
+        if tables.contains(&migrations_table) {
+            for record in self.get_migration_records().await? {
+                statements.push(self.generator.dump_migration_record(
+                    &migrations_table,
+                    &record.migration,
+                    record.batch,
+                    record.checksum.as_deref(),
+                ));
+            }
+        }
+        statements.push(self.generator.enable_foreign_key_constraints());
+
+        Ok(join_statements("generated by `artisan schema:dump`, do not edit", &statements))
+    }
+
+    /// Builds a portable SQL dump of tables and their rows, for `db:dump`.
+    ///
+    /// This method:
+    /// - Dumps the tables and views in `only`, named without the prefix like everywhere
+    ///   else, or every table and view when it is empty.
+    /// - Fails with `DbError::InvalidQuery` when a name in `only` doesn't exist.
+    /// - Writes `DROP ... IF EXISTS` and `CREATE` statements unless `mode` is `DataOnly`.
+    /// - Writes the rows as multi-row `INSERT`s of up to 100 rows unless `mode` is `SchemaOnly`.
+    ///
+    /// # Behavior
+    /// - Rows are read 1000 at a time, ordered by primary key when the table has one.
+    /// - Text is escaped for the current engine, binary columns are written as `X'..'` hex literals.
+    /// - Needs no `mysqldump` / `sqlite3` binary, the result can be run back with `load_schema()`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use rustavel_core::db::schema::Schema;
+    /// use rustavel_core::sql::script::DumpMode;
+    ///
+    /// async fn run() {
+    ///     let s = Schema::new().await.unwrap();
+    ///     let dump = s.dump_database(&["todos".to_string()], DumpMode::DataOnly).await.unwrap();
+    ///     std::fs::write("todos.sql", dump).unwrap();
+    /// }
+    /// ```
+    pub async fn dump_database(&self, only: &[String], mode: DumpMode) -> Result<String, DbError> {
+        let prefixed: Vec<String> = only.iter().map(|name| self.fix_table_name(name)).collect();
+        let tables = self.dumpable_tables(&prefixed).await?;
+        let views: Vec<String> = self
+            .get_views()
+            .await?
+            .into_iter()
+            .filter(|view| prefixed.is_empty() || prefixed.contains(view))
+            .collect();
+        // a typo would otherwise write an empty dump
+        let missing: Vec<&str> = only
+            .iter()
+            .zip(&prefixed)
+            .filter(|(_, name)| !tables.contains(name) && !views.contains(name))
+            .map(|(name, _)| name.as_str())
+            .collect();
+        if !missing.is_empty() {
+            return Err(DbError::InvalidQuery(format!("no table or view to dump named {}", missing.join(", "))));
+        }
+
+        let mut statements = vec![self.generator.disable_foreign_key_constraints()];
+        if mode != DumpMode::DataOnly {
+            for view in &views {
+                statements.push(self.generator.drop_view_if_exists(view));
+            }
+            for table in &tables {
+                statements.push(self.generator.drop_table_if_exists(table));
+            }
+            statements.extend(self.structure_statements(&tables, &views).await?);
+        }
+        if mode != DumpMode::SchemaOnly {
+            for table in &tables {
+                // a search index is refilled by the triggers of its table
+                if tables.iter().any(|t| self.generator.fulltext_table(t).as_ref() == Some(table)) {
+                    continue;
+                }
+                statements.extend(self.data_statements(table).await?);
+            }
+        }
+        statements.push(self.generator.enable_foreign_key_constraints());
+
+        Ok(join_statements("generated by `artisan db:dump`", &statements))
+    }
+
+    /// Tables a dump can contain: `only` (all when empty), never the migration lock.
+    async fn dumpable_tables(&self, only: &[String]) -> Result<Vec<String>, DbError> {
+        let mut tables: Vec<String> = self
+            .get_tables()
+            .await?
+            .into_iter()
+            .filter(|table| table != LOCK_TABLE && (only.is_empty() || only.contains(table)))
+            .collect();
+        tables.sort();
+        Ok(tables)
+    }
+
+    /// `CREATE` statements of `tables` and `views` as the database reports them.
+    async fn structure_statements(&self, tables: &[String], views: &[String]) -> Result<Vec<String>, DbError> {
+        let noise = Regex::new(r" AUTO_INCREMENT=\d+| DEFINER=\S+").unwrap();
+        let mut statements = vec![];
+        for query in self.generator.dump_structure(tables, views) {
+            let rows = self.client.fetch_rows(&query, &[]).await.map_err(|e| {
+                if self.debug {
+                    logger::error(&format!("{:?}", e));
+                }
+                e
+            })?;
+            for sql in rows.iter().filter_map(|row| row.get_index(1)?.as_text()) {
+                statements.push(noise.replace_all(&sql, "").into_owned());
+            }
+        }
+        Ok(statements)
+    }
+
+    /// Batched `INSERT` statements holding every row of `table`.
+    async fn data_statements(&self, table: &str) -> Result<Vec<String>, DbError> {
+        const PAGE: usize = 1000;
+        const BATCH: usize = 100;
+
+        let info = self.table_info(table.to_string()).await?;
+        let columns: Vec<&str> = info.columns.iter().map(|c| c.name.as_str()).collect();
+        let order: Vec<&str> = info.columns.iter().filter(|c| c.primary).map(|c| c.name.as_str()).collect();
+
+        let mut statements = vec![];
+        let mut offset = 0;
+        loop {
+            let sql = self.generator.dump_select(&info.name, &info.columns, &order, PAGE, offset);
+            let rows = self.client.fetch_rows(&sql, &[]).await.map_err(|e| {
+                if self.debug {
+                    logger::error(&format!("{:?}", e));
+                }
+                e
+            })?;
+            let fetched = rows.len();
+
+            let literals: Vec<Vec<String>> = rows
+                .into_iter()
+                .map(|row| {
+                    row.into_values()
+                        .into_iter()
+                        .zip(&info.columns)
+                        .map(|(cell, column)| self.dump_literal(cell, column))
+                        .collect()
+                })
+                .collect();
+            for chunk in literals.chunks(BATCH) {
+                statements.push(self.generator.dump_insert(&info.name, &columns, chunk));
+            }
+
+            if fetched < PAGE {
+                break;
+            }
+            offset += fetched;
+        }
+        Ok(statements)
+    }
+
+    /// SQL literal of one dumped cell; binary columns arrive hex encoded.
+    fn dump_literal(&self, cell: DbValue, column: &ColumnInfo) -> String {
+        match cell {
+            DbValue::Null => "NULL".to_string(),
+            DbValue::Bytes(bytes) => {
+                format!("X'{}'", bytes.iter().map(|b| format!("{:02X}", b)).collect::<String>())
+            }
+            hex if column.is_binary() => format!("X'{}'", hex),
+            DbValue::Bool(b) => if b { "1" } else { "0" }.to_string(),
+            DbValue::I64(number) => number.to_string(),
+            DbValue::F64(number) if number.is_finite() => number.to_string(),
+            DbValue::Text(number) if column.is_numeric() && number.parse::<f64>().is_ok_and(f64::is_finite) => number,
+            other => self.generator.quote_literal(&other.as_text().unwrap_or_default()),
+        }
+    }
+
+    /// Runs an SQL script, such as the one written by `dump_schema()`, statement by statement.
+    ///
+    /// # Behavior
+    /// - All statements run on the same connection, in file order.
+    /// - Stops at the first failing statement and returns its error.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use rustavel_core::db::schema::Schema;
+    ///
+    /// async fn run() {
+    ///     let s = Schema::new().await.unwrap();
+    ///     let dump = std::fs::read_to_string("database/schema/sqlite-schema.sql").unwrap();
+    ///     s.load_schema(&dump).await.unwrap();
+    /// }
+    /// ```
+    pub async fn load_schema(&self, script: &str) -> Result<(), DbError> {
+        let result = self.client.execute_batch(&split_statements(script)).await;
+        schema_cache::invalidate();
+        result.map_err(|e| {
+            if self.debug {
+                logger::error(&format!("{:?}", e));
+            }
+            e
+        })
+    }
+
+    /// Reads a table's columns, comment and foreign keys back from the database.
+    ///
+    /// # Behavior
+    /// - Columns come in table order, types and defaults exactly as the database reports them.
+    /// - Comments are always empty on SQLite, which doesn't store them.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use rustavel_core::db::schema::Schema;
+    ///
+    /// async fn run() {
+    ///     let s = Schema::new().await.unwrap();
+    ///     let todos = s.get_table_info("todos").await.unwrap();
+    ///     for column in &todos.columns {
+    ///         println!("{} {} {}", column.name, column.data_type, column.comment);
+    ///     }
+    /// }
+    /// ```
+    pub async fn get_table_info(&self, table_name: impl Into<String>) -> Result<TableInfo, DbError> {
+        self.table_info(self.fix_table_name(&table_name.into())).await
+    }
+
+    /// `get_table_info()` for a name that already carries the prefix.
+    async fn table_info(&self, name: String) -> Result<TableInfo, DbError> {
+        let log = |e: DbError| {
+            if self.debug {
+                logger::error(&format!("{:?}", e));
+            }
+            e
+        };
+
+        let comment = self
+            .client
+            .fetch_rows(&self.generator.introspect_table(&name), &[])
+            .await
+            .map_err(log)?
+            .first()
+            .and_then(|row| row.get_index(0)?.as_text())
+            .unwrap_or_default();
+
+        let columns = self
+            .client
+            .fetch_rows(&self.generator.introspect_columns(&name), &[])
+            .await
+            .map_err(log)?
+            .into_iter()
+            .map(|row| {
+                let mut cells = row.into_values().into_iter();
+                let mut next = || cells.next().unwrap_or(DbValue::Null);
+                ColumnInfo {
+                    name: next().as_text().unwrap_or_default(),
+                    data_type: next().as_text().unwrap_or_default(),
+                    nullable: next().as_bool().unwrap_or(false),
+                    default: next().as_text(),
+                    primary: next().as_bool().unwrap_or(false),
+                    comment: next().as_text().unwrap_or_default(),
+                }
+            })
+            .collect();
+
+        let foreign_keys = self
+            .client
+            .fetch_rows(&self.generator.introspect_foreign_keys(&name), &[])
+            .await
+            .map_err(log)?
+            .into_iter()
+            .map(|row| {
+                let mut cells = row.into_values().into_iter();
+                let mut next = || cells.next().and_then(|cell| cell.as_text()).unwrap_or_default();
+                ForeignKeyInfo {
+                    column: next(),
+                    foreign_table: next(),
+                    referenced_column: next(),
+                }
+            })
+            .collect();
+
+        let mut indexes: Vec<IndexInfo> = Vec::new();
+        for row in self
+            .client
+            .fetch_rows(&self.generator.introspect_indexes(&name), &[])
+            .await
+            .map_err(log)?
+        {
+            let index = row.get_index(0).and_then(DbValue::as_text).unwrap_or_default();
+            let column = row.get_index(1).and_then(DbValue::as_text).unwrap_or_default();
+            // rows come ordered by index, one per column
+            match indexes.last_mut() {
+                Some(last) if last.name == index => last.columns.push(column),
+                _ => indexes.push(IndexInfo {
+                    name: index,
+                    columns: vec![column],
+                    unique: row.get_index(2).and_then(DbValue::as_bool).unwrap_or(false),
+                }),
+            }
+        }
+
+        Ok(TableInfo { name, comment, columns, indexes, foreign_keys })
+    }
+
+    /// Engine, version, size, table / view counts and open connections of the database.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use rustavel_core::db::schema::Schema;
+    ///
+    /// async fn run() {
+    ///     let s = Schema::new().await.unwrap();
+    ///     let overview = s.overview().await.unwrap();
+    ///     println!("{} {}: {} tables", overview.engine, overview.version, overview.tables);
+    /// }
+    /// ```
+    pub async fn overview(&self) -> Result<DatabaseOverview, DbError> {
+        let log = |e: DbError| {
+            if self.debug {
+                logger::error(&format!("{:?}", e));
+            }
+            e
+        };
+        let first = |rows: Vec<DbRow>| rows.into_iter().next().and_then(|row| row.into_values().into_iter().next());
+
+        let version = first(self.client.fetch_rows(&self.generator.server_version(), &[]).await.map_err(log)?)
+            .and_then(|v| v.as_text())
+            .unwrap_or_default();
+        let size_bytes = first(self.client.fetch_rows(&self.generator.database_size(), &[]).await.map_err(log)?)
+            .and_then(|v| v.as_i64())
+            .unwrap_or(0);
+        let open_connections = match self.generator.open_connections() {
+            Some(sql) => first(self.client.fetch_rows(&sql, &[]).await.map_err(log)?).and_then(|v| v.as_i64()),
+            None => None,
+        };
+        let tables = self.get_tables().await?.iter().filter(|t| *t != LOCK_TABLE).count();
+        let views = self.get_views().await?.len();
+
+        Ok(DatabaseOverview {
+            engine: self.generator.engine().to_string(),
+            version,
+            database: CONFIG.database.database.clone(),
+            size_bytes,
+            tables,
+            views,
+            open_connections,
+        })
+    }
+
+    /// `get_table_info()` for every table except the migration lock, in name order.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use rustavel_core::db::schema::Schema;
+    ///
+    /// async fn run() {
+    ///     let s = Schema::new().await.unwrap();
+    ///     for table in s.introspect().await.unwrap() {
+    ///         println!("{}: {} columns", table.name, table.columns.len());
+    ///     }
+    /// }
+    /// ```
+    pub async fn introspect(&self) -> Result<Vec<TableInfo>, DbError> {
+        let mut names: Vec<String> = self
+            .get_tables()
+            .await?
+            .into_iter()
+            .filter(|table| table != LOCK_TABLE)
+            .collect();
+        names.sort();
+
+        let mut tables = Vec::with_capacity(names.len());
+        for name in names {
+            // names from the catalog already carry the prefix
+            tables.push(self.table_info(name).await?);
+        }
+        Ok(tables)
+    }
+
     /// Removes a migrated table entry from the migrations repository.
     ///
     /// This method:
@@ -1804,10 +2649,9 @@ impl Schema {
     /// # Notes
     /// - This method utilizes a prepared statement for safe execution of the deletion.
     /// - Ensure appropriate database permissions are granted to modify the migrations table.
-
     pub async fn rem_migrated_table(&self, migration_name: &str) -> Result<(), DbError> {
-        let sql = &self.generator.rem_migrated_table();
-        match self.client.execute_params(sql, &[migration_name]).await {
+        let sql = &self.generator.rem_migrated_table(&self.migrations_table());
+        match self.client.execute_params(sql, &[migration_name.into()]).await {
             Ok(_) => Ok(()),
             Err(e) => {
                 if self.debug {
@@ -1819,29 +2663,96 @@ impl Schema {
     }
 
     
-    pub async fn exists_record(&self, table: &str, column: &str, wanted: &str) -> bool {
-        // ----------------------------------------------------
-        // Perform identifier validation (table & column) using a cached schema whitelist.
-        // Schema metadata is initialized once and reused from memory (OnceCell),
-        // providing O(1)-like lookup performance under high concurrency.
-        // This ensures query safety by preventing injection through dynamic identifiers
-        // before building the final SQL statement.
-        // ----------------------------------------------------
-        let tables = get_tables().await;
-        if !tables.contains(&table.to_string()) {
-            logger::error(&format!("table not exists {}", table));
-            logger::info("May you need to run migrations or restart app to re-cache tables!?");
-            return false;
+    /// Table and column names for the `unique:` / `exists:` whitelist, cached per `Schema`.
+    ///
+    /// # Behavior
+    /// - Reads the catalog again once `DB_SCHEMA_CACHE_TTL` seconds passed, or after
+    ///   `schema_cache::invalidate()`; migrations run through `Schema` invalidate it.
+    /// - Names carry the table prefix, as they are in the database.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use rustavel_core::db::schema::Schema;
+    ///
+    /// async fn run() {
+    ///     let s = Schema::new().await.unwrap();
+    ///     let metadata = s.metadata().await.unwrap();
+    ///     println!("{}", metadata.has_column("users", "email"));
+    /// }
+    /// ```
+    pub async fn metadata(&self) -> Result<Arc<SchemaSnapshot>, DbError> {
+        if let Some(snapshot) = self.metadata.fresh() {
+            return Ok(snapshot);
         }
-        let columns = get_columns().await;
-        if !columns.contains(&format!("{}.{}", table, column)) {
-            logger::error(&format!("column `{}` not exists in {}", column, table));
-            logger::info("May you need to run migrations or restart app to re-cache tables!?");
-            return false;
+        let generation = schema_cache::generation();
+        let tables = self.get_tables().await?;
+        let mut columns = Vec::new();
+        for table in &tables {
+            // the names are prefixed already, so not through `get_column_listing()`
+            let listing = self.client.fetch_strings(&self.generator.get_column_listing(table)).await?;
+            columns.extend(listing.into_iter().map(|column| format!("{}.{}", table, column)));
+        }
+        Ok(self.metadata.store(SchemaSnapshot::new(generation, tables, columns)))
+    }
+
+    /// Checks that every `(table, column)` exists, e.g. the targets of `unique:` and `exists:` rules.
+    ///
+    /// Returns one message per missing table or column; the prefix is applied.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use rustavel_core::db::schema::Schema;
+    ///
+    /// async fn run() {
+    ///     let s = Schema::new().await.unwrap();
+    ///     if let Err(missing) = s.verify_columns(&[("users", "email")]).await {
+    ///         eprintln!("{}", missing.join("\n"));
+    ///     }
+    /// }
+    /// ```
+    pub async fn verify_columns(&self, columns: &[(&str, &str)]) -> Result<(), Vec<String>> {
+        let metadata = self.metadata().await.map_err(|e| vec![format!("{:?}", e)])?;
+        let mut missing = Vec::new();
+        for (table, column) in columns {
+            let name = self.fix_table_name(table);
+            let message = if !metadata.has_table(&name) {
+                format!("table `{}` does not exist", name)
+            } else if !metadata.has_column(&name, column) {
+                format!("column `{}` does not exist in `{}`", column, name)
+            } else {
+                continue;
+            };
+            if !missing.contains(&message) {
+                missing.push(message);
+            }
+        }
+        if missing.is_empty() { Ok(()) } else { Err(missing) }
+    }
+
+    /// The prefixed table name when it has all `columns`, logged and `None` otherwise.
+    ///
+    /// Rule arguments become identifiers in SQL, so they must be names the database reported.
+    async fn whitelisted_table(&self, table: &str, columns: &[&str]) -> Option<String> {
+        let pairs: Vec<(&str, &str)> = columns.iter().map(|column| (table, *column)).collect();
+        match self.verify_columns(&pairs).await {
+            Ok(()) => Some(self.fix_table_name(table)),
+            Err(missing) => {
+                for message in missing {
+                    logger::error(&message);
+                }
+                logger::info("Run the migrations; the names are re-read after `DB_SCHEMA_CACHE_TTL` seconds.");
+                None
+            }
         }
+    }
+
+    pub async fn exists_record(&self, table: &str, column: &str, wanted: &DbValue) -> bool {
+        let Some(table) = self.whitelisted_table(table, &[column]).await else {
+            return false;
+        };
         // check exists record
-        let sql = &self.generator.record_exists(table, column);
-        match self.client.fetch_count_params(sql, &[wanted]).await {
+        let sql = &self.generator.record_exists(&table, column);
+        match self.client.fetch_count_params(sql, std::slice::from_ref(wanted)).await {
             Ok(result) => {
                 if result == 0 { 
                     return false;
@@ -1857,34 +2768,13 @@ impl Schema {
         }
     }
     
-    pub async fn exists_record_except(&self, table: &str, column: &str, wanted: &str, except_col: &str, except_val: &str) -> bool {
-        // ----------------------------------------------------
-        // Perform identifier validation (table & column) using a cached schema whitelist.
-        // Schema metadata is initialized once and reused from memory (OnceCell),
-        // providing O(1)-like lookup performance under high concurrency.
-        // This ensures query safety by preventing injection through dynamic identifiers
-        // before building the final SQL statement.
-        // ----------------------------------------------------
-        let tables = get_tables().await;
-        if !tables.contains(&table.to_string()) {
-            logger::error(&format!("table not exists {}", table));
-            logger::info("May you need to run migrations or restart app to re-cache tables!?");
-            return false;
-        }
-        let columns = get_columns().await;
-        if !columns.contains(&format!("{}.{}", table, column)) {
-            logger::error(&format!("column `{}` not exists in {}", column, table));
-            logger::info("May you need to run migrations or restart app to re-cache tables!?");
-            return false;
-        }
-        if !columns.contains(&format!("{}.{}", table, except_col)) {
-            logger::error(&format!("column `{}` not exists in {}", column, table));
-            logger::info("May you need to run migrations or restart app to re-cache tables!?");
+    pub async fn exists_record_except(&self, table: &str, column: &str, wanted: &DbValue, except_col: &str, except_val: &DbValue) -> bool {
+        let Some(table) = self.whitelisted_table(table, &[column, except_col]).await else {
             return false;
-        }
+        };
         // check exists record
-        let sql = &self.generator.record_exists_except(table, column, except_col);
-        match self.client.fetch_count_params(sql, &[wanted, except_val]).await {
+        let sql = &self.generator.record_exists_except(&table, column, except_col);
+        match self.client.fetch_count_params(sql, &[wanted.clone(), except_val.clone()]).await {
             Ok(result) => {
                 if result == 0 { 
                     return false;
@@ -1900,3 +2790,34 @@ impl Schema {
         }
     }
 }
+
+#[async_trait::async_trait]
+impl QueryExecutor for Schema {
+    fn generator(&self) -> &(dyn SqlGenerator + Send + Sync) {
+        Schema::generator(self)
+    }
+
+    fn table_name(&self, table: &str) -> String {
+        self.fix_table_name(table)
+    }
+
+    async fn execute_params(&self, sql: &str, params: &[DbValue]) -> Result<(), DbError> {
+        Schema::execute_params(self, sql, params).await
+    }
+
+    async fn execute_insert(&self, sql: &str, params: &[DbValue]) -> Result<i64, DbError> {
+        Schema::execute_insert(self, sql, params).await
+    }
+
+    async fn fetch_rows(&self, sql: &str, params: &[DbValue]) -> Result<Vec<DbRow>, DbError> {
+        Schema::fetch_rows(self, sql, params).await
+    }
+
+    async fn fetch_count(&self, sql: &str, params: &[DbValue]) -> Result<i64, DbError> {
+        Schema::fetch_count(self, sql, params).await
+    }
+
+    fn fetch_stream(&self, sql: String, params: Vec<DbValue>) -> BoxStream<'_, Result<DbRow, DbError>> {
+        Schema::fetch_stream(self, sql, params)
+    }
+}
@@ -0,0 +1,78 @@
+//! Table and column names the `unique:` / `exists:` rules are checked against.
+//!
+//! Each `Schema` keeps a snapshot that is read again once it is older than
+//! `DB_SCHEMA_CACHE_TTL` seconds, or after [`invalidate`]. Migrations, `load_schema`
+//! and `drop_all_tables` invalidate it; a migration run by another process is
+//! picked up when the TTL runs out.
+
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+/// Bumped by `invalidate()`, snapshots of an older generation are stale.
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Drop the cached metadata of every `Schema`, the next lookup reads it again.
+pub fn invalidate() {
+    GENERATION.fetch_add(1, Ordering::SeqCst);
+}
+
+/// Read before loading, so an `invalidate()` during the load is not lost.
+pub(crate) fn generation() -> u64 {
+    GENERATION.load(Ordering::SeqCst)
+}
+
+/// Table and column names as they are in the database, prefix included.
+#[derive(Debug)]
+pub struct SchemaSnapshot {
+    tables: HashSet<String>,
+    columns: HashSet<String>,
+    loaded: Instant,
+    generation: u64,
+}
+
+impl SchemaSnapshot {
+    /// `columns` holds `table.column` names.
+    pub(crate) fn new(generation: u64, tables: Vec<String>, columns: Vec<String>) -> Self {
+        Self {
+            tables: tables.into_iter().collect(),
+            columns: columns.into_iter().collect(),
+            loaded: Instant::now(),
+            generation,
+        }
+    }
+
+    pub fn has_table(&self, table: &str) -> bool {
+        self.tables.contains(table)
+    }
+
+    pub fn has_column(&self, table: &str, column: &str) -> bool {
+        self.columns.contains(&format!("{}.{}", table, column))
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct SchemaCache {
+    ttl: Duration,
+    snapshot: RwLock<Option<Arc<SchemaSnapshot>>>,
+}
+
+impl SchemaCache {
+    pub(crate) fn new(ttl: Duration) -> Self {
+        Self { ttl, snapshot: RwLock::new(None) }
+    }
+
+    /// The stored snapshot, unless it expired or was invalidated.
+    pub(crate) fn fresh(&self) -> Option<Arc<SchemaSnapshot>> {
+        let snapshot = self.snapshot.read().unwrap().clone()?;
+        let current = snapshot.generation == generation();
+        (current && snapshot.loaded.elapsed() < self.ttl).then_some(snapshot)
+    }
+
+    pub(crate) fn store(&self, snapshot: SchemaSnapshot) -> Arc<SchemaSnapshot> {
+        let snapshot = Arc::new(snapshot);
+        *self.snapshot.write().unwrap() = Some(snapshot.clone());
+        snapshot
+    }
+}
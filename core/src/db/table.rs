@@ -1,5 +1,7 @@
 use crate::config::CONFIG;
 use crate::config::database::DatabaseEngine;
+use crate::db::lint::{self, LintIssue};
+use illuminate_string::Str;
 
 #[derive(Debug,Clone)]
 pub struct Table {
@@ -9,6 +11,17 @@ pub struct Table {
     pub comment: String,
     pub action: TableAction,
     pub drop_columns: Vec<String>,
+    /// Columns of the full-text index, empty for none
+    pub fulltext: Vec<String>,
+    /// Storage engine (MySQL), `None` for `InnoDB`
+    pub engine: Option<String>,
+    /// Character set (MySQL); with no collation either, `DB_CHARSET`
+    pub charset: Option<String>,
+    /// Collation (MySQL); with no charset either, `DB_COLLATION`
+    pub collation: Option<String>,
+    pub temporary: bool,
+    /// First value of the auto-increment key
+    pub auto_increment: Option<u64>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -94,7 +107,7 @@ pub enum DefaultValue {
     CurrenTimestamp,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct ForeignKey {
     pub column_name: String,
     pub foreign_table: String,
@@ -113,6 +126,12 @@ impl Table {
             comment: String::new(),
             action: TableAction::None,
             drop_columns: Vec::new(),
+            fulltext: Vec::new(),
+            engine: None,
+            charset: None,
+            collation: None,
+            temporary: false,
+            auto_increment: None,
         }
     }
 
@@ -121,6 +140,58 @@ impl Table {
         self
     }
 
+    /// Storage engine of the table, MySQL only.
+    ///
+    /// Like the other table options it is applied when the table is created.
+    ///
+    /// # Examples
+    /// ```rust,ignore
+    /// schema.create("logs", |table| {
+    ///     table.engine("MyISAM").charset("latin1").collation("latin1_swedish_ci");
+    ///     table.id();
+    /// });
+    /// ```
+    pub fn engine(&mut self, engine: impl Into<String>) -> &mut Self {
+        self.engine = Some(engine.into());
+        self
+    }
+
+    /// Character set of the table, MySQL only.
+    ///
+    /// A table setting neither `charset` nor `collation` gets `DB_CHARSET` and `DB_COLLATION`.
+    pub fn charset(&mut self, charset: impl Into<String>) -> &mut Self {
+        self.charset = Some(charset.into());
+        self
+    }
+
+    /// Collation of the table, MySQL only; it also implies the character set.
+    pub fn collation(&mut self, collation: impl Into<String>) -> &mut Self {
+        self.collation = Some(collation.into());
+        self
+    }
+
+    /// Create a temporary table, visible only to the connection that created it.
+    pub fn temporary(&mut self) -> &mut Self {
+        self.temporary = true;
+        self
+    }
+
+    /// Start the auto-increment key at `start` instead of 1.
+    ///
+    /// On SQLite this needs the `AUTOINCREMENT` key of `id()` and is ignored without it.
+    ///
+    /// # Examples
+    /// ```rust,ignore
+    /// schema.create("invoices", |table| {
+    ///     table.id();
+    ///     table.auto_increment_from(1000);
+    /// });
+    /// ```
+    pub fn auto_increment_from(&mut self, start: u64) -> &mut Self {
+        self.auto_increment = Some(start);
+        self
+    }
+
     fn column(
         &mut self,
         name: impl Into<String>,
@@ -145,13 +216,22 @@ impl Table {
         }
     }
 
+    /// Rust source of a struct for this table, preceded by a `DbEnum` enum
+    /// for every `enums()` / `sets()` column (`posts.status` -> `PostStatus`).
     pub fn to_struct(&self) -> String {
         let name = self.name.clone();
-        let mut result = "#[derive(Debug, sqlx::FromRow)]\n".to_string();
+        let mut result = String::new();
+        for col in &self.columns {
+            if let Some(rust_enum) = self.make_enum(col) {
+                result += &rust_enum;
+                result += "\n";
+            }
+        }
+        result += "#[derive(Debug, sqlx::FromRow)]\n";
         result += &format!("pub struct {} {{\n", name);
 
         for col in &self.columns {
-            let field = Self::make_field(col);
+            let field = self.make_field(col);
             result += &format!("    {},\n", field);
         }
 
@@ -159,7 +239,33 @@ impl Table {
         result
     }
 
-    fn make_field(column: &Column) -> String {
+    /// Name of the enum generated for an `enums()` / `sets()` column.
+    fn enum_name(&self, column: &Column) -> String {
+        format!("{}{}", studly_ident(&Str::singular(&self.name)), studly_ident(&column.name))
+    }
+
+    fn make_enum(&self, column: &Column) -> Option<String> {
+        let ColumnOption::Values(values) = &column.option else {
+            return None;
+        };
+        if !matches!(column.data_type, ColumnDataType::DTEnum | ColumnDataType::DTSet) {
+            return None;
+        }
+        let mut result = "#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, sqlx::Type, macros::DbEnum)]\n".to_string();
+        result += &format!("pub enum {} {{\n", self.enum_name(column));
+        for value in values {
+            result += &format!(
+                "    #[serde(rename = {:?})]\n    #[sqlx(rename = {:?})]\n    {},\n",
+                value,
+                value,
+                studly_ident(value)
+            );
+        }
+        result += "}\n";
+        Some(result)
+    }
+
+    fn make_field(&self, column: &Column) -> String {
         // map ColumnDataType -> Rust type
         let mut rust_type = match column.data_type {
             ColumnDataType::DTId => "i64".to_string(),
@@ -184,7 +290,8 @@ impl Table {
             | ColumnDataType::DTTimestamps => "chrono::NaiveDateTime".to_string(),
             ColumnDataType::DTTime => "chrono::NaiveTime".to_string(),
             ColumnDataType::DTSoftDelete => "Option<chrono::NaiveDateTime>".to_string(),
-            ColumnDataType::DTEnum | ColumnDataType::DTSet => "String".to_string(),
+            ColumnDataType::DTEnum => self.enum_name(column),
+            ColumnDataType::DTSet => format!("rustavel_core::sql::enums::EnumSet<{}>", self.enum_name(column)),
             ColumnDataType::DTMorph => "i64".to_string(),
             ColumnDataType::DTNone => "()".to_string(),
         };
@@ -193,7 +300,9 @@ impl Table {
             rust_type = format!("Option<{}>", rust_type);
         }
 
-        if column.unsigned && CONFIG.database.connection == DatabaseEngine::Mysql{
+        // enum names may contain an `i` too
+        let integer = !matches!(column.data_type, ColumnDataType::DTEnum | ColumnDataType::DTSet);
+        if integer && column.unsigned && CONFIG.database.connection == DatabaseEngine::Mysql{
             rust_type = rust_type.replace("i","u");
         }
 
@@ -364,81 +473,66 @@ impl Table {
 
     // --------------------------------------------------------------------------------------------
 
-    pub fn validate(&mut self) -> &mut Self {
-        for foreign_key in &mut self.foreign_keys {
-            if !foreign_key.validate() {
-                println!("invalid foreign key:");
-                dbg!(&foreign_key);
-            }
-        }
-        for column in &mut self.columns {
-            if !column.validate() {
-                println!("invalid column:");
-                dbg!(&column);
-            }
-        }
-        self
+    /// Problems of this definition on its own, see `db::lint` for the checks
+    /// `migrate` also runs across migrations.
+    pub fn validate(&self) -> Vec<LintIssue> {
+        lint::check_table(self, &CONFIG.database.connection)
     }
 
     pub fn drop_column(&mut self, name: impl Into<String>) {
         self.drop_columns.push(name.into());
     }
+
+    /// Full-text index over `columns`, searched by `Model::search()`.
+    ///
+    /// MySQL gets a `FULLTEXT` index. SQLite gets an FTS5 table `<table>_fts`
+    /// kept in sync by triggers, so the table needs the integer `id()` key.
+    /// A table has one full-text index; list the same columns in `#[searchable]`.
+    ///
+    /// # Examples
+    /// ```rust,ignore
+    /// schema.create("posts", |table| {
+    ///     table.id();
+    ///     table.string("title", 200);
+    ///     table.text("body");
+    ///     table.fulltext(["title", "body"]);
+    /// });
+    /// ```
+    pub fn fulltext<I, S>(&mut self, columns: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.fulltext = columns.into_iter().map(Into::into).collect();
+        self
+    }
     // --------------------------------------------------------------------------------------------
 }
 
-impl Column {
-    fn validate(&mut self) -> bool {
-        match self.data_type {
-            ColumnDataType::DTNone => return false,
-
-            ColumnDataType::DTString
-            | ColumnDataType::DTLongText
-            | ColumnDataType::DTMediumText
-            | ColumnDataType::DTTinyText
-            | ColumnDataType::DTJson => {
-                // String types cannot be unsigned
-                if self.unsigned {
-                    return false;
-                }
-
-                // Handle Length option
-                if let ColumnOption::Length(length) = &self.option {
-                    if *length <= 0 {
-                        return false;
-                    }
-
-                    // If length > 255, cannot be indexed or unique
-                    if *length > 255 && (self.index || self.unique) {
-                        return false;
-                    }
-                } else {
-                    // If no Length and it's indexed or unique, invalid
-                    if self.index || self.unique {
-                        return false;
-                    }
-                }
-            }
-
-            ColumnDataType::DTBoolean => {
-                if self.unique {
-                    return false;
-                }
-            }
-            _ => return true,
-        }
-
-        true
+/// `in-progress` -> `InProgress`, a valid Rust identifier for any column or value.
+fn studly_ident(value: &str) -> String {
+    let ident: String = value
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| part[..1].to_ascii_uppercase() + &part[1..])
+        .collect();
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("V{}", ident)
+    } else {
+        ident
     }
+}
 
+impl Column {
     pub fn is_string_type(&self) -> bool {
-        match self.data_type {
+        matches!(
+            self.data_type,
             ColumnDataType::DTString
-            | ColumnDataType::DTLongText
-            | ColumnDataType::DTMediumText
-            | ColumnDataType::DTTinyText
-            | ColumnDataType::DTJson => true,
-            _ => false,
-        }
+                | ColumnDataType::DTLongText
+                | ColumnDataType::DTMediumText
+                | ColumnDataType::DTTinyText
+                | ColumnDataType::DTJson
+        )
     }
 }
 
@@ -459,30 +553,6 @@ impl Default for Column {
         }
     }
 }
-impl Default for ForeignKey {
-    fn default() -> Self {
-        ForeignKey {
-            referenced_column: String::new(),
-            column_name: String::new(),
-            foreign_table: String::new(),
-            on_delete: false,
-            on_update: false,
-        }
-    }
-}
-
-impl ForeignKey {
-    fn validate(&mut self) -> bool {
-        if self.column_name.is_empty()
-            || self.referenced_column.is_empty()
-            || self.foreign_table.is_empty()
-        {
-            return false;
-        }
-
-        true
-    }
-}
 
 impl<'a> ColumnBuilder<'a> {
     pub fn nullable(mut self) -> Self {
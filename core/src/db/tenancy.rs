@@ -0,0 +1,77 @@
+//! Tenants: which prefix or database the current request works on.
+//!
+//! `http::tenancy::identify_tenant` runs the rest of the request inside
+//! [`scope`], so [`current`] and `get_static_schema()` see the tenant of the
+//! request. Work spawned onto another task does not inherit it, wrap it in
+//! [`scope`] again.
+
+use crate::config::CONFIG;
+use crate::db::schema::Schema;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::future::Future;
+use tokio::sync::Mutex;
+
+tokio::task_local! {
+    static CURRENT: Tenant;
+}
+
+/// One connection pool per tenant, kept for the life of the process.
+static SCHEMAS: Lazy<Mutex<HashMap<String, &'static Schema>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tenant {
+    id: String,
+}
+
+impl Tenant {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self { id: id.into() }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Table prefix of this tenant, from `TENANCY_PREFIX`.
+    pub fn prefix(&self) -> String {
+        CONFIG.tenancy.prefix.replace("{tenant}", &self.id)
+    }
+
+    /// Database (SQLite: file) of this tenant, from `TENANCY_DATABASE`.
+    pub fn database(&self) -> String {
+        CONFIG.tenancy.database.replace("{tenant}", &self.id)
+    }
+}
+
+/// Every tenant listed in `TENANTS`.
+pub fn registered() -> Vec<Tenant> {
+    CONFIG.tenancy.tenants.iter().map(Tenant::new).collect()
+}
+
+/// The registered tenant called `id`; ids are never used unchecked in a prefix or database name.
+pub fn find(id: &str) -> Option<Tenant> {
+    CONFIG.tenancy.tenants.iter().find(|tenant| tenant.as_str() == id).map(Tenant::new)
+}
+
+/// The tenant of the running request, if any.
+pub fn current() -> Option<Tenant> {
+    CURRENT.try_with(|tenant| tenant.clone()).ok()
+}
+
+/// Runs `work` with `tenant` as the current tenant.
+pub async fn scope<F: Future>(tenant: Tenant, work: F) -> F::Output {
+    CURRENT.scope(tenant, work).await
+}
+
+/// The shared `Schema` of `tenant`, connected on first use.
+pub async fn schema(tenant: &Tenant) -> Result<&'static Schema, sqlx::Error> {
+    let mut schemas = SCHEMAS.lock().await;
+    if let Some(schema) = schemas.get(tenant.id()) {
+        return Ok(schema);
+    }
+    // tenants come from `TENANTS`, so this leaks a bounded number of pools
+    let schema: &'static Schema = Box::leak(Box::new(Schema::for_tenant(tenant).await?));
+    schemas.insert(tenant.id().to_string(), schema);
+    Ok(schema)
+}
@@ -0,0 +1,160 @@
+//! Transactions: `Schema::transaction` / `DB::transaction` run a closure on one
+//! connection, committing when it returns `Ok` and rolling back on `Err`.
+//!
+//! `Transaction::transaction` nests with a savepoint. After a deadlock or
+//! serialization failure (`DbError::is_transient`) the whole closure runs again,
+//! up to `DB_TRANSACTION_RETRIES` times, so it should not have side effects
+//! outside the database.
+
+use crate::config::CONFIG;
+use crate::db::schema::Schema;
+use crate::logger;
+use crate::sql::database_client::{DatabaseClient, DbError};
+use crate::sql::executor::QueryExecutor;
+use crate::sql::generator::SqlGenerator;
+use crate::sql::value::{DbRow, DbValue};
+use futures::stream::BoxStream;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// An open transaction, handed to the closure of `transaction()`.
+///
+/// Pass `&tx` to the builders instead of the schema to run them inside it.
+/// Clones share the transaction; once it ends, using one is an error.
+#[derive(Debug, Clone)]
+pub struct Transaction<'s> {
+    schema: &'s Schema,
+    client: Arc<dyn DatabaseClient + Send + Sync>,
+    /// `0` for the transaction itself, `n` inside the `n`th nested savepoint
+    depth: u32,
+}
+
+impl Schema {
+    /// Runs `work` in a transaction, retrying deadlocks `DB_TRANSACTION_RETRIES` times.
+    ///
+    /// # Examples
+    /// ```rust,ignore
+    /// let id = schema
+    ///     .transaction(|tx| async move {
+    ///         Account::update(from).set(col!(Account::balance), from_balance - amount).execute(&tx).await?;
+    ///         Account::update(to).set(col!(Account::balance), to_balance + amount).execute(&tx).await?;
+    ///         Ok(to)
+    ///     })
+    ///     .await?;
+    /// ```
+    pub async fn transaction<'s, T, F, Fut>(&'s self, work: F) -> Result<T, DbError>
+    where
+        F: FnMut(Transaction<'s>) -> Fut,
+        Fut: Future<Output = Result<T, DbError>>,
+    {
+        self.transaction_with_retries(CONFIG.database.transaction_retries, work).await
+    }
+
+    /// `transaction()` with its own number of retries, `0` runs `work` once.
+    pub async fn transaction_with_retries<'s, T, F, Fut>(&'s self, retries: u32, mut work: F) -> Result<T, DbError>
+    where
+        F: FnMut(Transaction<'s>) -> Fut,
+        Fut: Future<Output = Result<T, DbError>>,
+    {
+        let mut attempt = 0;
+        loop {
+            let tx = Transaction {
+                schema: self,
+                client: Arc::from(self.client().begin().await?),
+                depth: 0,
+            };
+            let result = match work(tx.clone()).await {
+                Ok(value) => tx.client.commit().await.map(|_| value),
+                Err(e) => {
+                    if let Err(rollback) = tx.client.rollback().await {
+                        logger::error(&format!("rollback failed: {:?}", rollback));
+                    }
+                    Err(e)
+                }
+            };
+            match result {
+                Err(e) if e.is_transient() && attempt < retries => {
+                    attempt += 1;
+                    logger::warn(&format!("transaction retried ({}/{}): {:?}", attempt, retries, e));
+                    tokio::time::sleep(Duration::from_millis(10 * u64::from(attempt))).await;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl<'s> Transaction<'s> {
+    /// Runs `work` in a savepoint: its `Err` undoes only what `work` did, and
+    /// is returned for the caller to handle or pass on.
+    ///
+    /// # Examples
+    /// ```rust,ignore
+    /// DB::transaction(|tx| async move {
+    ///     Order::insert().set(col!(Order::total), 10).execute(&tx).await?;
+    ///     // a failed coupon keeps the order
+    ///     let _ = tx.transaction(|tx| async move { apply_coupon(&tx).await }).await;
+    ///     Ok(())
+    /// })
+    /// .await?;
+    /// ```
+    pub async fn transaction<T, F, Fut>(&self, work: F) -> Result<T, DbError>
+    where
+        F: FnOnce(Transaction<'s>) -> Fut,
+        Fut: Future<Output = Result<T, DbError>>,
+    {
+        let depth = self.depth + 1;
+        let savepoint = format!("rustavel_savepoint_{}", depth);
+        self.client.execute(&format!("SAVEPOINT {}", savepoint)).await?;
+        match work(Transaction { depth, ..self.clone() }).await {
+            Ok(value) => {
+                self.client.execute(&format!("RELEASE SAVEPOINT {}", savepoint)).await?;
+                Ok(value)
+            }
+            // a deadlock already rolled back the whole transaction, the outermost level retries it
+            Err(e) if e.is_transient() => Err(e),
+            Err(e) => {
+                self.client.execute(&format!("ROLLBACK TO SAVEPOINT {}", savepoint)).await?;
+                self.client.execute(&format!("RELEASE SAVEPOINT {}", savepoint)).await?;
+                Err(e)
+            }
+        }
+    }
+
+    /// How many savepoints deep this handle is, `0` outside of any.
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+}
+
+#[async_trait::async_trait]
+impl QueryExecutor for Transaction<'_> {
+    fn generator(&self) -> &(dyn SqlGenerator + Send + Sync) {
+        self.schema.generator()
+    }
+
+    fn table_name(&self, table: &str) -> String {
+        self.schema.fix_table_name(table)
+    }
+
+    async fn execute_params(&self, sql: &str, params: &[DbValue]) -> Result<(), DbError> {
+        self.client.execute_params(sql, params).await
+    }
+
+    async fn execute_insert(&self, sql: &str, params: &[DbValue]) -> Result<i64, DbError> {
+        self.client.execute_insert(sql, params).await
+    }
+
+    async fn fetch_rows(&self, sql: &str, params: &[DbValue]) -> Result<Vec<DbRow>, DbError> {
+        self.client.fetch_rows(sql, params).await
+    }
+
+    async fn fetch_count(&self, sql: &str, params: &[DbValue]) -> Result<i64, DbError> {
+        self.client.fetch_count_params(sql, params).await
+    }
+
+    fn fetch_stream(&self, sql: String, params: Vec<DbValue>) -> BoxStream<'_, Result<DbRow, DbError>> {
+        self.client.fetch_stream(sql, params)
+    }
+}
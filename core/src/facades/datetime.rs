@@ -13,6 +13,7 @@ use time::{
     OffsetDateTime,
     PrimitiveDateTime,
     Time,
+    UtcOffset,
     error::Parse,
     format_description::FormatItem,
     format_description::well_known::Rfc2822,
@@ -22,9 +23,10 @@ use time::{
 
 use serde::{Deserializer, Deserialize};
 use serde::de::{Error};
-/// =============================
-/// Precompiled Format Definitions
-/// =============================
+use crate::config::CONFIG;
+// =============================
+// Precompiled Format Definitions
+// =============================
 
 /// Format: `YYYY-MM-DD`
 /// Example: `2026-02-14`
@@ -48,9 +50,14 @@ const HMS: &[FormatItem<'static>] =
 const COMPACT: &[FormatItem<'static>] =
     format_description!("[year]_[month]_[day]_[hour][minute]");
 
-/// =============================
-/// NOW HELPERS (UTC)
-/// =============================
+/// Format: `+HH:MM`
+/// Example: `+03:30`
+const OFFSET: &[FormatItem<'static>] =
+    format_description!("[offset_hour sign:mandatory]:[offset_minute]");
+
+// =============================
+// NOW HELPERS (UTC)
+// =============================
 
 /// Returns the current UTC time as `OffsetDateTime`.
 ///
@@ -95,9 +102,9 @@ pub fn now_rfc2822() -> String {
     now().format(&Rfc2822).expect("valid RFC2822 format")
 }
 
-/// =============================
-/// PARSING HELPERS
-/// =============================
+// =============================
+// PARSING HELPERS
+// =============================
 
 /// Parses a string formatted as `YYYY-MM-DD` into `Date`.
 ///
@@ -169,6 +176,64 @@ pub fn now_primitive() -> PrimitiveDateTime {
 }
 
 
+// =============================
+// APP TIMEZONE HELPERS
+// =============================
+
+/// Resolves `AppConfig::timezone` (`APP_TIMEZONE`) into a fixed offset.
+///
+/// Supported values:
+/// - `UTC` (default)
+/// - `local` (offset of the host machine)
+/// - fixed offsets like `+03:30` or `-05:00`
+///
+/// Named zones such as `Asia/Tehran` need a tz database which `time` doesn't ship,
+/// so they fall back to UTC.
+pub fn app_offset() -> UtcOffset {
+    let timezone = CONFIG.app.timezone.trim();
+
+    if timezone.is_empty() || timezone.eq_ignore_ascii_case("utc") {
+        return UtcOffset::UTC;
+    }
+    if timezone.eq_ignore_ascii_case("local") {
+        return UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC);
+    }
+
+    UtcOffset::parse(timezone, OFFSET).unwrap_or(UtcOffset::UTC)
+}
+
+/// Returns the current date and time in the application timezone.
+///
+/// This is what the write builders use to fill `created_at` / `updated_at`.
+pub fn now_app() -> PrimitiveDateTime {
+    let current_time = OffsetDateTime::now_utc().to_offset(app_offset());
+    PrimitiveDateTime::new(current_time.date(), current_time.time())
+}
+
+/// Returns the current application-timezone date-time formatted as `YYYY-MM-DD HH:MM:SS`.
+#[inline]
+pub fn now_app_ymd_hms() -> String {
+    now_app().format(YMD_HMS).expect("valid YMD_HMS format")
+}
+
+/// Formats a `PrimitiveDateTime` as `YYYY-MM-DD HH:MM:SS`.
+#[inline]
+pub fn format_ymd_hms(datetime: &PrimitiveDateTime) -> String {
+    datetime.format(YMD_HMS).expect("valid YMD_HMS format")
+}
+
+/// Formats a `Date` as `YYYY-MM-DD`.
+#[inline]
+pub fn format_ymd(date: &Date) -> String {
+    date.format(YMD).expect("valid YMD format")
+}
+
+/// Formats a `Time` as `HH:MM:SS`.
+#[inline]
+pub fn format_hms(time: &Time) -> String {
+    time.format(HMS).expect("valid HMS format")
+}
+
 /// convert string to PrimitiveDateTime
 pub fn parse_to_primitive_datetime(datetime_str: &str) -> Result<PrimitiveDateTime, time::Error> {
     // Parse the string into a PrimitiveDateTime.
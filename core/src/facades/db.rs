@@ -0,0 +1,58 @@
+use crate::db::try_get_static_schema;
+use crate::db::transaction::Transaction;
+use crate::sql::database_client::DbError;
+use crate::sql::query_log::{self, QueryEvent};
+use std::future::Future;
+
+#[derive(Debug)]
+pub struct DB;
+
+impl DB {
+    /// Call `callback` after every statement any `Schema` runs, with its SQL,
+    /// bindings, duration and connection.
+    ///
+    /// Returns an id for `DB::forget`. Listeners are process wide, so a test
+    /// should filter the events it collects, other tests may run at the same time.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use rustavel_core::facades::db::DB;
+    ///
+    /// let id = DB::listen(|query| println!("{}ms {}", query.duration.as_millis(), query.sql));
+    /// // ...
+    /// DB::forget(id);
+    /// ```
+    pub fn listen(callback: impl Fn(&QueryEvent) + Send + Sync + 'static) -> usize {
+        query_log::listen(callback)
+    }
+
+    /// Remove a listener registered with `DB::listen`.
+    pub fn forget(id: usize) {
+        query_log::forget(id)
+    }
+
+    /// Run `work` in a transaction on the shared `Schema` (or the current tenant's),
+    /// see `Schema::transaction`.
+    ///
+    /// Commits when `work` returns `Ok`, rolls back on `Err`, and runs `work` again
+    /// after a deadlock, up to `DB_TRANSACTION_RETRIES` times.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// DB::transaction(|tx| async move {
+    ///     Order::insert().set(col!(Order::total), 10).execute(&tx).await?;
+    ///     Stock::update(item).set(col!(Stock::count), count - 1).execute(&tx).await
+    /// })
+    /// .await?;
+    /// ```
+    pub async fn transaction<T, F, Fut>(work: F) -> Result<T, DbError>
+    where
+        F: FnMut(Transaction<'static>) -> Fut,
+        Fut: Future<Output = Result<T, DbError>>,
+    {
+        let schema = try_get_static_schema().await.map_err(DbError::from)?;
+        schema.transaction(work).await
+    }
+}
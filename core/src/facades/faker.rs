@@ -0,0 +1,212 @@
+//! Offline fake-data generator for factories, seeders and tests.
+//!
+//! Everything comes from small built-in word lists and a SplitMix64 PRNG, so
+//! there is no network access and the same seed always produces the same data.
+//!
+//! # Examples
+//! ```rust
+//! use rustavel_core::facades::faker::Faker;
+//!
+//! let mut a = Faker::seeded(42);
+//! let mut b = Faker::seeded(42);
+//! assert_eq!(a.name(), b.name());
+//! assert!(a.persian_phone_number().starts_with("09"));
+//! ```
+
+use time::{Date, Duration, OffsetDateTime, PrimitiveDateTime};
+
+const FIRST_NAMES: &[&str] = &[
+    "James", "Mary", "John", "Patricia", "Robert", "Jennifer", "Michael", "Linda",
+    "William", "Elizabeth", "David", "Barbara", "Richard", "Susan", "Joseph", "Jessica",
+    "Thomas", "Sarah", "Charles", "Karen", "Daniel", "Emma", "Matthew", "Olivia",
+];
+
+const LAST_NAMES: &[&str] = &[
+    "Smith", "Johnson", "Williams", "Brown", "Jones", "Garcia", "Miller", "Davis",
+    "Rodriguez", "Martinez", "Wilson", "Anderson", "Taylor", "Thomas", "Moore", "Jackson",
+    "Martin", "Lee", "Thompson", "White", "Harris", "Clark", "Lewis", "Walker",
+];
+
+const PERSIAN_FIRST_NAMES: &[&str] = &[
+    "علی", "محمد", "حسین", "رضا", "مهدی", "امیر", "سعید", "حمید",
+    "مریم", "فاطمه", "زهرا", "سارا", "نرگس", "لیلا", "مینا", "شیرین",
+    "آرش", "کاوه", "بهرام", "پرستو", "نازنین", "یاسمن", "کوروش", "داریوش",
+];
+
+const PERSIAN_LAST_NAMES: &[&str] = &[
+    "محمدی", "حسینی", "احمدی", "رضایی", "کریمی", "موسوی", "جعفری", "صادقی",
+    "رحیمی", "هاشمی", "نوری", "طاهری", "قاسمی", "کاظمی", "اکبری", "عباسی",
+    "سلیمانی", "فرهادی", "نجفی", "شریفی", "مرادی", "یزدانی", "تهرانی", "شیرازی",
+];
+
+/// Operator prefixes of Iranian mobile numbers.
+const PERSIAN_MOBILE_PREFIXES: &[&str] = &[
+    "0910", "0911", "0912", "0913", "0914", "0915", "0916", "0917", "0918", "0919",
+    "0990", "0991", "0901", "0902", "0903", "0930", "0933", "0935", "0936", "0937",
+    "0938", "0939", "0920", "0921", "0922",
+];
+
+const EMAIL_DOMAINS: &[&str] = &["example.com", "example.org", "example.net"];
+
+const WORDS: &[&str] = &[
+    "lorem", "ipsum", "dolor", "sit", "amet", "consectetur", "adipiscing", "elit",
+    "sed", "do", "eiusmod", "tempor", "incididunt", "ut", "labore", "et",
+    "dolore", "magna", "aliqua", "enim", "ad", "minim", "veniam", "quis",
+    "nostrud", "exercitation", "ullamco", "laboris", "nisi", "aliquip", "ex", "ea",
+];
+
+/// Deterministic fake-data generator.
+#[derive(Debug, Clone)]
+pub struct Faker {
+    state: u64,
+}
+
+impl Faker {
+    /// Seeded from `FAKER_SEED` when set, otherwise from the clock.
+    pub fn new() -> Self {
+        let seed = std::env::var("FAKER_SEED")
+            .ok()
+            .and_then(|s| s.trim().parse().ok())
+            .unwrap_or_else(|| OffsetDateTime::now_utc().unix_timestamp_nanos() as u64);
+        Self::seeded(seed)
+    }
+
+    /// Same seed, same sequence of values.
+    pub fn seeded(seed: u64) -> Self {
+        Faker { state: seed }
+    }
+
+    /// Next raw 64-bit value (SplitMix64).
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Random integer in `min..=max`.
+    pub fn number_between(&mut self, min: i64, max: i64) -> i64 {
+        if min >= max {
+            return min;
+        }
+        let span = (max as i128 - min as i128 + 1) as u128;
+        (min as i128 + (self.next_u64() as u128 % span) as i128) as i64
+    }
+
+    /// Random decimal number in `min..max`.
+    pub fn float_between(&mut self, min: f64, max: f64) -> f64 {
+        let unit = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        min + (max - min) * unit
+    }
+
+    pub fn boolean(&mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+
+    /// Random digit string of the given length (leading zeros allowed).
+    pub fn digits(&mut self, len: usize) -> String {
+        (0..len)
+            .map(|_| char::from(b'0' + self.number_between(0, 9) as u8))
+            .collect()
+    }
+
+    /// Random element of a non-empty slice.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        let index = self.number_between(0, items.len() as i64 - 1) as usize;
+        &items[index]
+    }
+
+    pub fn first_name(&mut self) -> String {
+        self.pick(FIRST_NAMES).to_string()
+    }
+
+    pub fn last_name(&mut self) -> String {
+        self.pick(LAST_NAMES).to_string()
+    }
+
+    pub fn name(&mut self) -> String {
+        format!("{} {}", self.first_name(), self.last_name())
+    }
+
+    /// Email on a reserved `example.*` domain, so it can never reach a real inbox.
+    pub fn email(&mut self) -> String {
+        let first = self.first_name().to_lowercase();
+        let last = self.last_name().to_lowercase();
+        let number = self.number_between(1, 999);
+        let domain = self.pick(EMAIL_DOMAINS);
+        format!("{}.{}{}@{}", first, last, number, domain)
+    }
+
+    /// Same as `email()`; kept for Laravel muscle memory.
+    pub fn safe_email(&mut self) -> String {
+        self.email()
+    }
+
+    pub fn word(&mut self) -> String {
+        self.pick(WORDS).to_string()
+    }
+
+    pub fn words(&mut self, count: usize) -> Vec<String> {
+        (0..count).map(|_| self.word()).collect()
+    }
+
+    /// Capitalized sentence of `words` words, ending with a dot.
+    pub fn sentence(&mut self, words: usize) -> String {
+        let mut sentence = self.words(words.max(1)).join(" ");
+        if let Some(first) = sentence.get(0..1) {
+            sentence.replace_range(0..1, &first.to_uppercase());
+        }
+        sentence.push('.');
+        sentence
+    }
+
+    /// `sentences` sentences of 4 to 12 words each.
+    pub fn paragraph(&mut self, sentences: usize) -> String {
+        (0..sentences.max(1))
+            .map(|_| {
+                let words = self.number_between(4, 12) as usize;
+                self.sentence(words)
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Random date-time in `start..=end` (second precision).
+    pub fn date_time_between(&mut self, start: PrimitiveDateTime, end: PrimitiveDateTime) -> PrimitiveDateTime {
+        let (start, end) = if start <= end { (start, end) } else { (end, start) };
+        let seconds = (end - start).whole_seconds();
+        start + Duration::seconds(self.number_between(0, seconds))
+    }
+
+    /// Random date in `start..=end`.
+    pub fn date_between(&mut self, start: Date, end: Date) -> Date {
+        let (start, end) = if start <= end { (start, end) } else { (end, start) };
+        let days = (end - start).whole_days();
+        start + Duration::days(self.number_between(0, days))
+    }
+
+    pub fn persian_first_name(&mut self) -> String {
+        self.pick(PERSIAN_FIRST_NAMES).to_string()
+    }
+
+    pub fn persian_last_name(&mut self) -> String {
+        self.pick(PERSIAN_LAST_NAMES).to_string()
+    }
+
+    pub fn persian_name(&mut self) -> String {
+        format!("{} {}", self.persian_first_name(), self.persian_last_name())
+    }
+
+    /// Iranian mobile number, e.g. `09121234567`.
+    pub fn persian_phone_number(&mut self) -> String {
+        let prefix = self.pick(PERSIAN_MOBILE_PREFIXES);
+        format!("{}{}", prefix, self.digits(7))
+    }
+}
+
+impl Default for Faker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -3,4 +3,6 @@ pub mod file;
 pub mod carbon;
 pub mod terminal_ui;
 pub mod datetime;
-pub mod file_content;
\ No newline at end of file
+pub mod file_content;
+pub mod faker;
+pub mod db;
@@ -59,6 +59,20 @@ pub fn operation(name: &str, duration: Duration, status: Status) {
     );
 }
 
+/// Print a `name ........ value` line, e.g. for status listings
+pub fn line(name: &str, value: &str) {
+    let mut term_width = terminal_width().unwrap_or(80) - 7;
+    if term_width > 147 {
+        term_width =  147;
+    }
+
+    let fixed_len = name.len() + 1 + strip_ansi(value).len();
+    let dots_len = term_width.saturating_sub(fixed_len).max(3);
+    let dots = ".".repeat(dots_len);
+
+    println!(" {} {} {}", name, dots.dimmed(), value);
+}
+
 /// Different title types
 pub enum TitleKind {
     Info,
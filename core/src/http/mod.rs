@@ -1 +1,2 @@
-pub mod request;
\ No newline at end of file
+pub mod request;
+pub mod tenancy;
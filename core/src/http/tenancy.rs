@@ -0,0 +1,47 @@
+use crate::config::tenancy::TenantResolver;
+use crate::config::CONFIG;
+use crate::db::tenancy::{self, Tenant};
+use axum::body::Body;
+use axum::extract::Request;
+use axum::http::{header, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+/// Middleware that scopes the rest of the request to its tenant.
+///
+/// The tenant id is read as `TENANCY_RESOLVER` says: the first label of the
+/// host, the `TENANCY_HEADER` header, or the first path segment (routes are
+/// then declared under `/{tenant}/...`). Ids not listed in `TENANTS` get a 404.
+/// With `TENANCY_MODE=off` every request passes through untouched.
+///
+/// # Examples
+/// ```rust,ignore
+/// let app = built.router.layer(axum::middleware::from_fn(identify_tenant));
+/// ```
+pub async fn identify_tenant(req: Request<Body>, next: Next) -> Response {
+    if !CONFIG.tenancy.enabled() {
+        return next.run(req).await;
+    }
+    match resolve(&req) {
+        Some(tenant) => tenancy::scope(tenant, next.run(req)).await,
+        None => (StatusCode::NOT_FOUND, "Unknown tenant").into_response(),
+    }
+}
+
+/// The registered tenant `req` is addressed to.
+pub fn resolve(req: &Request<Body>) -> Option<Tenant> {
+    let id = match CONFIG.tenancy.resolver {
+        TenantResolver::Subdomain => {
+            let host = req.headers().get(header::HOST)?.to_str().ok()?;
+            let host = host.split(':').next()?;
+            // `example.com` alone names no tenant
+            if host.split('.').count() < 3 && !host.ends_with(".localhost") {
+                return None;
+            }
+            host.split('.').next()?
+        }
+        TenantResolver::Header => req.headers().get(CONFIG.tenancy.header.as_str())?.to_str().ok()?,
+        TenantResolver::Path => req.uri().path().trim_start_matches('/').split('/').next()?,
+    };
+    tenancy::find(id)
+}
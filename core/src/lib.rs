@@ -10,6 +10,11 @@ pub mod db;
 pub mod http;
 pub mod localization;
 pub mod macros;
+pub mod testing;
+
+// `#[derive(CheckMate)]` registers its rules through it, see `db::rules`
+#[doc(hidden)]
+pub use inventory;
 
 
 
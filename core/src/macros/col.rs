@@ -0,0 +1,16 @@
+/// Typed column of a model, `col!(Todo::title)` is `Todo::COLUMNS.title`.
+///
+/// The model may be a path, `col!(models::Todo::title)`. Fails to compile when
+/// `Todo` has no `title` field.
+#[macro_export]
+macro_rules! col {
+    ($model:ident :: $($rest:tt)+) => {
+        $crate::col!(@path [] $model :: $($rest)+)
+    };
+    (@path [$($path:tt)*] $model:ident :: $field:ident) => {
+        $($path)* $model::COLUMNS.$field
+    };
+    (@path [$($path:tt)*] $module:ident :: $($rest:tt)+) => {
+        $crate::col!(@path [$($path)* $module ::] $($rest)+)
+    };
+}
@@ -1 +1,2 @@
-pub mod register_model;
\ No newline at end of file
+pub mod register_model;
+pub mod col;
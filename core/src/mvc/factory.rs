@@ -0,0 +1,159 @@
+//! Model factories for tests, seeders and demo data.
+//!
+//! A model opts in by implementing [`HasFactory`]; the factory then builds any
+//! number of instances from `definition()` and applies overrides on top.
+//!
+//! # Examples
+//! ```rust,ignore
+//! impl HasFactory for Todo {
+//!     fn definition(faker: &mut Faker) -> Self {
+//!         Todo {
+//!             id: 0,
+//!             title: faker.sentence(4),
+//!             done: faker.boolean(),
+//!             created_at: now_app(),
+//!             updated_at: now_app(),
+//!         }
+//!     }
+//! }
+//!
+//! let todos = Todo::factory()
+//!     .count(10)
+//!     .seed(42)
+//!     .state(|todo, _| todo.done = false)
+//!     .sequence(|todo, i| todo.title = format!("todo #{}", i))
+//!     .create(&schema)
+//!     .await?;
+//! ```
+
+use crate::facades::faker::Faker;
+use crate::mvc::model::{Col, Model};
+use crate::sql::database_client::DbError;
+use crate::sql::executor::QueryExecutor;
+use crate::sql::query::InsertQuery;
+use crate::sql::value::DbValue;
+
+type Modifier<M> = Box<dyn Fn(&mut M, &mut Faker, usize) + Send + Sync>;
+
+pub trait HasFactory: Model {
+    /// Default attributes of one fake instance.
+    fn definition(faker: &mut Faker) -> Self;
+
+    fn factory() -> Factory<Self> {
+        Factory::new()
+    }
+}
+
+pub struct Factory<M: HasFactory> {
+    count: usize,
+    seed: Option<u64>,
+    modifiers: Vec<Modifier<M>>,
+}
+
+impl<M: HasFactory> Factory<M> {
+    pub fn new() -> Self {
+        Factory {
+            count: 1,
+            seed: None,
+            modifiers: Vec::new(),
+        }
+    }
+
+    /// Number of instances `make()` / `create()` produce.
+    pub fn count(mut self, count: usize) -> Self {
+        self.count = count;
+        self
+    }
+
+    /// Fix the faker seed so the generated data is reproducible.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Override attributes of every instance, applied in call order after `definition()`.
+    pub fn state<F>(mut self, state: F) -> Self
+    where
+        F: Fn(&mut M, &mut Faker) + Send + Sync + 'static,
+    {
+        self.modifiers.push(Box::new(move |model, faker, _| state(model, faker)));
+        self
+    }
+
+    /// Like `state()`, but also gets the zero-based index of the instance.
+    pub fn sequence<F>(mut self, sequence: F) -> Self
+    where
+        F: Fn(&mut M, usize) + Send + Sync + 'static,
+    {
+        self.modifiers.push(Box::new(move |model, _, index| sequence(model, index)));
+        self
+    }
+
+    /// Build the instances without touching the database.
+    pub fn make(&self) -> Vec<M> {
+        let mut faker = match self.seed {
+            Some(seed) => Faker::seeded(seed),
+            None => Faker::new(),
+        };
+
+        (0..self.count)
+            .map(|index| {
+                let mut model = M::definition(&mut faker);
+                for modifier in &self.modifiers {
+                    modifier(&mut model, &mut faker, index);
+                }
+                model
+            })
+            .collect()
+    }
+
+    /// Build a single instance, ignoring `count()`.
+    pub fn make_one(self) -> M {
+        self.count(1).make().remove(0)
+    }
+
+    /// Build the instances and insert them through `Model::insert()`.
+    ///
+    /// `NULL` columns are left to the database default, and so is a primary key
+    /// that is still `0` or empty (auto increment). Such rows are read back after
+    /// the insert, so the returned models carry the generated key and defaults.
+    pub async fn create(&self, db: &(impl QueryExecutor + ?Sized)) -> Result<Vec<M>, DbError> {
+        let mut models = self.make();
+        for model in &mut models {
+            let mut query = InsertQuery::<M>::new();
+            let mut generated_key = false;
+            for (column, value) in model.to_row() {
+                if value.is_null() {
+                    continue;
+                }
+                if column == M::primary_key() && matches!(value.as_text().as_deref(), Some("" | "0")) {
+                    generated_key = true;
+                    continue;
+                }
+                query = query.set(Col::raw(column), value);
+            }
+            if !generated_key {
+                query.execute(db).await?;
+                continue;
+            }
+            let id = query.execute_get_id(db).await?;
+            let sql = db
+                .generator()
+                .select_by_key(&db.table_name(M::table()), M::columns(), M::primary_key());
+            let row = db.fetch_rows(&sql, &[DbValue::from(id)]).await?.into_iter().next();
+            *model = M::from_row(&row.ok_or(DbError::NotFound)?)?;
+        }
+        Ok(models)
+    }
+
+    /// Create a single row, ignoring `count()`.
+    pub async fn create_one(self, db: &(impl QueryExecutor + ?Sized)) -> Result<M, DbError> {
+        Ok(self.count(1).create(db).await?.remove(0))
+    }
+}
+
+impl<M: HasFactory> Default for Factory<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
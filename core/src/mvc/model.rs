@@ -1,4 +1,7 @@
-
+use crate::sql::database_client::DbError;
+use crate::sql::value::{DbRow, DbValue};
+use std::fmt;
+use std::marker::PhantomData;
 
 pub trait Model: Sized + Send + Sync + 'static {
     type PrimaryKey;
@@ -7,4 +10,91 @@ pub trait Model: Sized + Send + Sync + 'static {
     fn primary_key() -> &'static str;
     fn columns() -> &'static [&'static str];
 
-}
\ No newline at end of file
+    /// Opt-in: when `true`, the write builders fill `created_at` / `updated_at` automatically.
+    fn timestamps() -> bool {
+        false
+    }
+
+    /// Column/value pairs of this instance, in `columns()` order (`None` is `DbValue::Null`).
+    fn to_row(&self) -> Vec<(&'static str, DbValue)>;
+
+    /// Builds an instance from a fetched row; skipped fields get their `Default`.
+    fn from_row(row: &DbRow) -> Result<Self, DbError>;
+
+    /// Columns of the full-text index searched by `search()`, from `#[searchable(fields = "...")]`.
+    fn searchable() -> &'static [&'static str] {
+        &[]
+    }
+}
+
+/// A column of model `M`, checked when the code compiles.
+///
+/// `#[derive(Model)]` writes one per field into `M::COLUMNS`; `col!(Todo::title)`
+/// is `Todo::COLUMNS.title`, and a misspelled field is a compile error.
+///
+/// # Examples
+/// ```rust,ignore
+/// use rustavel_core::col;
+///
+/// Todo::update(1).set(col!(Todo::done), true).execute(&schema).await?;
+/// assert_eq!(col!(Todo::title).name(), "title");
+/// ```
+pub struct Col<M> {
+    name: &'static str,
+    _model: PhantomData<fn() -> M>,
+}
+
+impl<M> Col<M> {
+    /// The columns `#[derive(Model)]` writes into `M::COLUMNS`, not meant to be called by hand.
+    #[doc(hidden)]
+    pub const fn __derived(name: &'static str) -> Self {
+        Self { name, _model: PhantomData }
+    }
+
+    /// A column `M` has no field for, e.g. `created_at`; the name is not checked.
+    ///
+    /// # Examples
+    /// ```rust,ignore
+    /// Todo::update(1).set(Col::raw("created_at"), yesterday).execute(&schema).await?;
+    /// ```
+    pub const fn raw(name: &'static str) -> Self {
+        Self { name, _model: PhantomData }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+// derives would require `M: Clone`
+impl<M> Clone for Col<M> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M> Copy for Col<M> {}
+
+impl<M> fmt::Debug for Col<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Col({})", self.name)
+    }
+}
+
+impl<M> fmt::Display for Col<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name)
+    }
+}
+
+/// What the query builders of `M` take as a column: a `Col<M>`, so a name that is
+/// not checked has to be spelled out as `Col::raw("…")`.
+pub trait IntoColumn<M> {
+    fn into_column(self) -> String;
+}
+
+impl<M> IntoColumn<M> for Col<M> {
+    fn into_column(self) -> String {
+        self.name.to_string()
+    }
+}
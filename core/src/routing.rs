@@ -141,6 +141,12 @@ macro_rules! define_method {
     };
 }
 
+impl<S: Clone + Send + Sync + 'static> Default for Route<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[allow(dead_code)]
 impl<S: Clone + Send + Sync + 'static> Route<S> {
     pub fn new() -> Self {
@@ -464,7 +470,6 @@ impl<S: Clone + Send + Sync + 'static> Route<S> {
     /// This function does not perform validation or deduplication.
     /// It is intended to be used during the build phase after all
     /// route contexts have been resolved.
-
     fn build_full_path(item: &RouteItem<S>) -> String {
         let mut path = String::new();
 
@@ -1,5 +1,22 @@
-use sqlx::Row;
+use crate::sql::value::{DbRow, DbValue};
+use futures::channel::mpsc;
+use futures::stream::BoxStream;
+use futures::{SinkExt, StreamExt};
+use sqlx::mysql::{MySqlQueryResult, MySqlRow};
+use sqlx::sqlite::{SqliteError, SqliteQueryResult, SqliteRow};
+use sqlx::{Column, Connection, MySql, MySqlConnection, Row, Sqlite, SqlitePool, TypeInfo, ValueRef};
+use time::{Date, PrimitiveDateTime, Time};
 use std::fmt::Debug;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// SQLite table backing `DbLock::Sqlite`; `drop_all_tables` leaves it alone.
+pub const LOCK_TABLE: &str = "migrations_lock";
+
+/// Rows `fetch_stream` reads ahead of the consumer.
+const STREAM_BUFFER: usize = 64;
 
 #[derive(Debug)]
 pub enum DbError {
@@ -7,16 +24,192 @@ pub enum DbError {
     InvalidTable,
     NotFound,
     InvalidQuery(String),
+    /// Lock held by someone else until the timeout, the message names the holder.
+    Locked(String),
+    /// Applied migrations whose SQL changed since they ran.
+    ChecksumMismatch(Vec<String>),
+    /// Pending migrations with lint errors, one message per problem.
+    MigrationLint(Vec<String>),
+    /// Reading or writing a file, like a schema dump, failed.
+    Io(std::io::Error),
+}
+
+impl DbError {
+    /// A deadlock or serialization failure: the transaction was rolled back and
+    /// running it again may succeed.
+    pub fn is_transient(&self) -> bool {
+        let DbError::Sqlx(sqlx::Error::Database(e)) = self else {
+            return false;
+        };
+        // SQLSTATE `40001`, MySQL reports deadlocks (1213) with it too
+        if e.code().as_deref() == Some("40001") {
+            return true;
+        }
+        // SQLITE_BUSY / SQLITE_LOCKED, the code is the extended one
+        e.try_downcast_ref::<SqliteError>().is_some()
+            && e.code().and_then(|code| code.parse::<i32>().ok()).is_some_and(|code| matches!(code & 0xff, 5 | 6))
+    }
+}
+
+/// Cross-process lock returned by `DatabaseClient::acquire_lock`.
+///
+/// Call `release()` when done. If the process dies instead, MySQL frees the lock
+/// with the session; on SQLite the row in `migrations_lock` is taken over once it
+/// is older than the `stale_after` given to `acquire_lock`.
+#[derive(Debug)]
+pub enum DbLock {
+    /// `GET_LOCK` belongs to the session, so the connection is kept out of the pool.
+    MySql { name: String, conn: MySqlConnection },
+    Sqlite { name: String, owner: String, pool: SqlitePool },
+}
+
+impl DbLock {
+    pub async fn release(self) -> Result<(), DbError> {
+        match self {
+            DbLock::MySql { name, mut conn } => {
+                sqlx::query("SELECT RELEASE_LOCK(CONCAT(DATABASE(), '.', ?))")
+                    .bind(&name)
+                    .execute(&mut conn)
+                    .await?;
+                conn.close().await?;
+            }
+            DbLock::Sqlite { name, owner, pool } => {
+                sqlx::query(&format!("DELETE FROM {} WHERE name = ? AND owner = ?", LOCK_TABLE))
+                    .bind(&name)
+                    .bind(&owner)
+                    .execute(&pool)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
 pub trait DatabaseClient: Send + Sync + Debug {
     async fn execute(&self, sql: &str) -> Result<(), DbError>;
-    async fn execute_params(&self, sql: &str, params: &[&str]) -> Result<(), DbError>;
+    async fn execute_params(&self, sql: &str, params: &[DbValue]) -> Result<(), DbError>;
+    /// Executes an `INSERT` and returns the auto-increment key it generated.
+    async fn execute_insert(&self, sql: &str, params: &[DbValue]) -> Result<i64, DbError>;
     async fn fetch_strings(&self, sql: &str) -> Result<Vec<String>, DbError>;
-    async fn fetch_strings_params(&self, sql: &str, params: &[&str]) -> Result<Vec<String>, DbError>;
-    async fn fetch_count_params(&self, sql: &str, params: &[&str]) -> Result<i64, DbError>;
+    async fn fetch_strings_params(&self, sql: &str, params: &[DbValue]) -> Result<Vec<String>, DbError>;
+    async fn fetch_count_params(&self, sql: &str, params: &[DbValue]) -> Result<i64, DbError>;
     async fn fetch_numbers(&self, sql: &str) -> Result<Vec<i64>, DbError>;
+    /// Run `statements` in order on one connection, so session settings like
+    /// `FOREIGN_KEY_CHECKS` apply to all of them.
+    async fn execute_batch(&self, statements: &[String]) -> Result<(), DbError>;
+    /// Every column of every row, typed after the column's SQL type.
+    async fn fetch_rows(&self, sql: &str, params: &[DbValue]) -> Result<Vec<DbRow>, DbError>;
+    /// `fetch_rows()` as a stream, the rows are read as the consumer takes them.
+    ///
+    /// The query runs on its own task holding one pooled connection until the
+    /// stream ends or is dropped.
+    fn fetch_stream<'a>(&'a self, sql: String, params: Vec<DbValue>) -> BoxStream<'a, Result<DbRow, DbError>>;
+    /// Wait up to `timeout` for the named lock; `owner` is shown to whoever waits next.
+    /// A lock held longer than `stale_after` is taken over where it can outlive its process.
+    async fn acquire_lock(&self, name: &str, owner: &str, timeout: Duration, stale_after: Duration) -> Result<DbLock, DbError>;
+    /// Starts a transaction on a pooled connection of its own. The returned client
+    /// runs every statement inside it until its `commit()` or `rollback()`; dropped
+    /// before either, the transaction is rolled back.
+    async fn begin(&self) -> Result<Box<dyn DatabaseClient + Send + Sync>, DbError>;
+    /// Commits the transaction of a client returned by `begin()`.
+    async fn commit(&self) -> Result<(), DbError>;
+    /// Rolls back the transaction of a client returned by `begin()`.
+    async fn rollback(&self) -> Result<(), DbError>;
+}
+
+/// Binds one `DbValue`; dates and times go in as text (see `DbValue`).
+macro_rules! bind_value {
+    ($query:expr, $value:expr) => {
+        match $value {
+            DbValue::Null => $query.bind(None::<String>),
+            DbValue::Bool(b) => $query.bind(*b),
+            DbValue::I64(v) => $query.bind(*v),
+            DbValue::F64(v) => $query.bind(*v),
+            DbValue::Text(s) => $query.bind(s.as_str()),
+            DbValue::Bytes(b) => $query.bind(b.as_slice()),
+            value @ (DbValue::Date(_) | DbValue::Time(_) | DbValue::DateTime(_)) => $query.bind(value.as_text()),
+        }
+    };
+}
+
+/// Decodes one column after its reported SQL type, falling back to whatever decodes.
+macro_rules! decode_value {
+    ($row:expr, $index:expr) => {{
+        let row = $row;
+        let index = $index;
+        let type_name = row.column(index).type_info().name().to_uppercase();
+
+        let typed = if row.try_get_raw(index).map_or(true, |raw| raw.is_null()) {
+            Some(DbValue::Null)
+        } else if type_name.contains("BOOL") {
+            row.try_get::<bool, _>(index).ok().map(DbValue::Bool)
+        } else if type_name.contains("DATETIME") || type_name.contains("TIMESTAMP") {
+            row.try_get::<PrimitiveDateTime, _>(index).ok().map(DbValue::DateTime)
+        } else if type_name.contains("DATE") {
+            row.try_get::<Date, _>(index).ok().map(DbValue::Date)
+        } else if type_name == "TIME" {
+            row.try_get::<Time, _>(index).ok().map(DbValue::Time)
+        } else {
+            None
+        };
+
+        typed.unwrap_or_else(|| {
+            if let Ok(value) = row.try_get::<i64, _>(index) {
+                DbValue::I64(value)
+            } else if let Ok(value) = row.try_get::<u64, _>(index) {
+                DbValue::from(value)
+            } else if let Ok(value) = row.try_get::<f64, _>(index) {
+                DbValue::F64(value)
+            } else if let Ok(value) = row.try_get::<String, _>(index) {
+                DbValue::Text(value)
+            } else if let Ok(value) = row.try_get::<Vec<u8>, _>(index) {
+                DbValue::Bytes(value)
+            } else {
+                // e.g. DECIMAL without a decimal crate: the wire format is its text
+                row.try_get_unchecked::<String, _>(index).map_or(DbValue::Null, DbValue::Text)
+            }
+        })
+    }};
+}
+
+/// Stream of the rows `feed` sends from a spawned task, at most `STREAM_BUFFER` ahead.
+fn spawn_stream<F, Fut>(feed: F) -> BoxStream<'static, Result<DbRow, DbError>>
+where
+    F: FnOnce(mpsc::Sender<Result<DbRow, DbError>>) -> Fut,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+    tokio::spawn(feed(tx));
+    rx.boxed()
+}
+
+/// Reads `$query` into `$tx` until the receiver is dropped.
+macro_rules! feed_rows {
+    ($query:expr, $pool:expr, $tx:expr, $to_row:expr) => {{
+        let mut tx = $tx;
+        let mut rows = $query.fetch($pool);
+        while let Some(row) = rows.next().await {
+            let row = row.map(|row| $to_row(&row)).map_err(DbError::from);
+            if tx.send(row).await.is_err() {
+                break;
+            }
+        }
+    }};
+}
+
+fn mysql_row(row: &MySqlRow) -> DbRow {
+    DbRow::new(
+        row.columns().iter().map(|c| c.name().to_string()).collect(),
+        (0..row.len()).map(|i| decode_value!(row, i)).collect(),
+    )
+}
+
+fn sqlite_row(row: &SqliteRow) -> DbRow {
+    DbRow::new(
+        row.columns().iter().map(|c| c.name().to_string()).collect(),
+        (0..row.len()).map(|i| decode_value!(row, i)).collect(),
+    )
 }
 
 #[derive(Debug)]
@@ -30,24 +223,50 @@ impl From<sqlx::Error> for DbError {
     }
 }
 
+impl From<std::io::Error> for DbError {
+    fn from(err: std::io::Error) -> Self {
+        DbError::Io(err)
+    }
+}
+
 #[async_trait::async_trait]
 impl DatabaseClient for MySqlClient {
 
+    async fn begin(&self) -> Result<Box<dyn DatabaseClient + Send + Sync>, DbError> {
+        Ok(Box::new(MySqlTransactionClient::new(self.pool.begin().await?)))
+    }
+
+    async fn commit(&self) -> Result<(), DbError> {
+        Err(not_in_transaction())
+    }
+
+    async fn rollback(&self) -> Result<(), DbError> {
+        Err(not_in_transaction())
+    }
+
     async fn execute_params(
         &self,
         sql: &str,
-        params: &[&str],  // ← str reference
+        params: &[DbValue],
     ) -> Result<(), DbError> {
         let mut query = sqlx::query(sql);
 
         for param in params {
-            query = query.bind(*param);
+            query = bind_value!(query, param);
         }
 
         query.execute(&self.pool).await?;
         Ok(())
     }
 
+    async fn execute_insert(&self, sql: &str, params: &[DbValue]) -> Result<i64, DbError> {
+        let mut query = sqlx::query(sql);
+        for param in params {
+            query = bind_value!(query, param);
+        }
+        Ok(mysql_insert_id(query.execute(&self.pool).await?))
+    }
+
 
     async fn execute(&self, sql: &str) -> Result<(), DbError> {
         sqlx::query(sql).execute(&self.pool).await?;
@@ -64,31 +283,32 @@ impl DatabaseClient for MySqlClient {
             .into_iter()
             .map(|row| {
                 // dbg!(&row);
-                row.get::<String, _>(0)
+                row.try_get::<String, _>(0)
             })
-            .collect())
+            .collect::<Result<_, _>>()?)
     }
 
     async fn fetch_numbers(&self, sql: &str) -> Result<Vec<i64>, DbError> {
         let rows = sqlx::query(sql).fetch_all(&self.pool).await?;
 
-        if !rows.is_empty() {
+        if rows.is_empty() {
             return Ok(vec![]);
         }
+        // aggregates like MAX() give a single NULL row on empty tables
         Ok(rows
             .into_iter()
-            .map(|row| {
+            .filter_map(|row| {
                 // dbg!(&row);
-                row.get::<i64, _>(0)
+                row.try_get::<Option<i64>, _>(0).transpose()
             })
-            .collect())
+            .collect::<Result<_, _>>()?)
     }
 
-    async fn fetch_strings_params(&self, sql: &str, params: &[&str]) -> Result<Vec<String>, DbError> {
+    async fn fetch_strings_params(&self, sql: &str, params: &[DbValue]) -> Result<Vec<String>, DbError> {
         let mut query = sqlx::query(sql);
 
             for param in params {
-                query = query.bind(*param);
+                query = bind_value!(query, param);
             }
 
             let rows = query.fetch_all(&self.pool).await?;
@@ -98,20 +318,20 @@ impl DatabaseClient for MySqlClient {
             .into_iter()
             .map(|row| {
                 // dbg!(&row);
-                row.get::<String, _>(0)
+                row.try_get::<String, _>(0)
             })
-            .collect())
+            .collect::<Result<_, _>>()?)
     }
     
     async fn fetch_count_params(
         &self,
         sql: &str,
-        params: &[&str],
+        params: &[DbValue],
     ) -> Result<i64, DbError> {
         let mut query = sqlx::query(sql);
 
         for param in params {
-            query = query.bind(*param);
+            query = bind_value!(query, param);
         }
 
         let row = query.fetch_one(&self.pool).await?;
@@ -120,6 +340,75 @@ impl DatabaseClient for MySqlClient {
 
         Ok(count)
     }
+
+    async fn fetch_rows(&self, sql: &str, params: &[DbValue]) -> Result<Vec<DbRow>, DbError> {
+        let mut query = sqlx::query(sql);
+        for param in params {
+            query = bind_value!(query, param);
+        }
+        let rows = query.fetch_all(&self.pool).await?;
+        Ok(rows.iter().map(mysql_row).collect())
+    }
+
+    fn fetch_stream<'a>(&'a self, sql: String, params: Vec<DbValue>) -> BoxStream<'a, Result<DbRow, DbError>> {
+        let pool = self.pool.clone();
+        spawn_stream(move |tx| async move {
+            let mut query = sqlx::query(&sql);
+            for param in &params {
+                query = bind_value!(query, param);
+            }
+            feed_rows!(query, &pool, tx, mysql_row);
+        })
+    }
+
+    async fn execute_batch(&self, statements: &[String]) -> Result<(), DbError> {
+        let mut conn = self.pool.acquire().await?;
+        for statement in statements {
+            sqlx::query(statement).execute(&mut *conn).await?;
+        }
+        Ok(())
+    }
+
+    async fn acquire_lock(&self, name: &str, _owner: &str, timeout: Duration, _stale_after: Duration) -> Result<DbLock, DbError> {
+        // lock names are server wide, so they get the database name as namespace
+        let mut conn = self.pool.acquire().await?.detach();
+
+        let row = sqlx::query("SELECT GET_LOCK(CONCAT(DATABASE(), '.', ?), ?)")
+            .bind(name)
+            .bind(timeout.as_secs() as i64)
+            .fetch_one(&mut conn)
+            .await?;
+
+        if row.try_get::<Option<i64>, _>(0)? == Some(1) {
+            return Ok(DbLock::MySql { name: name.to_string(), conn });
+        }
+
+        let holder = sqlx::query(
+            "SELECT p.ID, p.HOST FROM information_schema.PROCESSLIST p \
+             WHERE p.ID = IS_USED_LOCK(CONCAT(DATABASE(), '.', ?))",
+        )
+        .bind(name)
+        .fetch_optional(&mut conn)
+        .await
+        .ok()
+        .flatten()
+        .map(|row| {
+            format!(
+                "connection {} from {}",
+                row.try_get::<u64, _>(0).unwrap_or_default(),
+                row.try_get::<String, _>(1).unwrap_or_default()
+            )
+        })
+        .unwrap_or_else(|| "another connection".to_string());
+
+        let _ = conn.close().await;
+        Err(DbError::Locked(format!(
+            "lock `{}` is held by {} (waited {}s)",
+            name,
+            holder,
+            timeout.as_secs()
+        )))
+    }
 }
 
 #[derive(Debug)]
@@ -130,21 +419,41 @@ pub struct SqliteClient {
 #[async_trait::async_trait]
 impl DatabaseClient for SqliteClient {
 
+    async fn begin(&self) -> Result<Box<dyn DatabaseClient + Send + Sync>, DbError> {
+        Ok(Box::new(SqliteTransactionClient::new(self.pool.begin().await?)))
+    }
+
+    async fn commit(&self) -> Result<(), DbError> {
+        Err(not_in_transaction())
+    }
+
+    async fn rollback(&self) -> Result<(), DbError> {
+        Err(not_in_transaction())
+    }
+
     async fn execute_params(
         &self,
         sql: &str,
-        params: &[&str],  // ← str reference
+        params: &[DbValue],
     ) -> Result<(), DbError> {
         let mut query = sqlx::query(sql);
 
         for param in params {
-            query = query.bind(*param);
+            query = bind_value!(query, param);
         }
 
         query.execute(&self.pool).await?;
         Ok(())
     }
 
+    async fn execute_insert(&self, sql: &str, params: &[DbValue]) -> Result<i64, DbError> {
+        let mut query = sqlx::query(sql);
+        for param in params {
+            query = bind_value!(query, param);
+        }
+        Ok(sqlite_insert_id(query.execute(&self.pool).await?))
+    }
+
     async fn execute(&self, sql: &str) -> Result<(), DbError> {
         sqlx::query(sql).execute(&self.pool).await?;
         Ok(())
@@ -157,8 +466,8 @@ impl DatabaseClient for SqliteClient {
         }
         Ok(rows
             .into_iter()
-            .map(|row| row.get::<String, _>(0))
-            .collect())
+            .map(|row| row.try_get::<String, _>(0))
+            .collect::<Result<_, _>>()?)
     }
 
     async fn fetch_numbers(&self, sql: &str) -> Result<Vec<i64>, DbError> {
@@ -166,17 +475,18 @@ impl DatabaseClient for SqliteClient {
         if rows.is_empty() {
             return Ok(vec![]);
         }
+        // aggregates like MAX() give a single NULL row on empty tables
         Ok(rows
             .into_iter()
-            .map(|row| row.get::<i64, _>(0))
-            .collect())
+            .filter_map(|row| row.try_get::<Option<i64>, _>(0).transpose())
+            .collect::<Result<_, _>>()?)
     }
 
-    async fn fetch_strings_params(&self, sql: &str, params: &[&str]) -> Result<Vec<String>, DbError> {
+    async fn fetch_strings_params(&self, sql: &str, params: &[DbValue]) -> Result<Vec<String>, DbError> {
         let mut query = sqlx::query(sql);
 
         for param in params {
-            query = query.bind(*param);
+            query = bind_value!(query, param);
         }
 
         let rows = query.fetch_all(&self.pool).await?;
@@ -186,20 +496,20 @@ impl DatabaseClient for SqliteClient {
             .into_iter()
             .map(|row| {
                 // dbg!(&row);
-                row.get::<String, _>(0)
+                row.try_get::<String, _>(0)
             })
-            .collect())
+            .collect::<Result<_, _>>()?)
     }
 
     async fn fetch_count_params(
         &self,
         sql: &str,
-        params: &[&str],
+        params: &[DbValue],
     ) -> Result<i64, DbError> {
         let mut query = sqlx::query(sql);
 
         for param in params {
-            query = query.bind(*param);
+            query = bind_value!(query, param);
         }
 
         let row = query.fetch_one(&self.pool).await?;
@@ -209,5 +519,268 @@ impl DatabaseClient for SqliteClient {
         Ok(count)
     }
 
+    async fn fetch_rows(&self, sql: &str, params: &[DbValue]) -> Result<Vec<DbRow>, DbError> {
+        let mut query = sqlx::query(sql);
+        for param in params {
+            query = bind_value!(query, param);
+        }
+        let rows = query.fetch_all(&self.pool).await?;
+        Ok(rows.iter().map(sqlite_row).collect())
+    }
+
+    fn fetch_stream<'a>(&'a self, sql: String, params: Vec<DbValue>) -> BoxStream<'a, Result<DbRow, DbError>> {
+        let pool = self.pool.clone();
+        spawn_stream(move |tx| async move {
+            let mut query = sqlx::query(&sql);
+            for param in &params {
+                query = bind_value!(query, param);
+            }
+            feed_rows!(query, &pool, tx, sqlite_row);
+        })
+    }
+
+    async fn execute_batch(&self, statements: &[String]) -> Result<(), DbError> {
+        let mut conn = self.pool.acquire().await?;
+        for statement in statements {
+            sqlx::query(statement).execute(&mut *conn).await?;
+        }
+        Ok(())
+    }
+
+    async fn acquire_lock(&self, name: &str, owner: &str, timeout: Duration, stale_after: Duration) -> Result<DbLock, DbError> {
+        sqlx::query(&format!(
+            "CREATE TABLE IF NOT EXISTS {} (name TEXT PRIMARY KEY, owner TEXT NOT NULL, acquired_at TEXT NOT NULL)",
+            LOCK_TABLE
+        ))
+        .execute(&self.pool)
+        .await?;
+
+        let insert = format!("INSERT INTO {} (name, owner, acquired_at) VALUES (?, ?, ?)", LOCK_TABLE);
+        // `acquired_at` is `YYYY-MM-DD HH:MM:SS` in UTC, so text comparison orders it
+        let take_over = format!("DELETE FROM {} WHERE name = ? AND acquired_at < ?", LOCK_TABLE);
+        let deadline = Instant::now() + timeout;
+        loop {
+            let result = sqlx::query(&insert)
+                .bind(name)
+                .bind(owner)
+                .bind(crate::facades::datetime::now_ymd_hms())
+                .execute(&self.pool)
+                .await;
+
+            match result {
+                Ok(_) => {
+                    return Ok(DbLock::Sqlite {
+                        name: name.to_string(),
+                        owner: owner.to_string(),
+                        pool: self.pool.clone(),
+                    });
+                }
+                Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {}
+                Err(e) => return Err(e.into()),
+            }
+
+            let stale_since = crate::facades::datetime::now_primitive() - stale_after;
+            let taken_over = sqlx::query(&take_over)
+                .bind(name)
+                .bind(crate::facades::datetime::format_ymd_hms(&stale_since))
+                .execute(&self.pool)
+                .await?;
+            if taken_over.rows_affected() > 0 {
+                continue;
+            }
+
+            if Instant::now() >= deadline {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(250)).await;
+        }
+
+        let holder = match sqlx::query(&format!(
+            "SELECT owner, acquired_at FROM {} WHERE name = ?",
+            LOCK_TABLE
+        ))
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await?
+        {
+            Some(row) => format!(
+                "{} since {} UTC",
+                row.try_get::<String, _>(0)?,
+                row.try_get::<String, _>(1)?
+            ),
+            None => "another process".to_string(),
+        };
+
+        Err(DbError::Locked(format!(
+            "lock `{}` is held by {} (waited {}s), it is taken over after {}s; if that process is gone, \
+             delete its row with `DELETE FROM {} WHERE name = '{}'`",
+            name,
+            holder,
+            timeout.as_secs(),
+            stale_after.as_secs(),
+            LOCK_TABLE,
+            name
+        )))
+    }
+
+
+}
+
+fn not_in_transaction() -> DbError {
+    DbError::InvalidQuery("no transaction to end, start one with `begin()`".to_string())
+}
+
+fn transaction_finished() -> DbError {
+    DbError::InvalidQuery("the transaction was already committed or rolled back".to_string())
+}
+
+/// A `DatabaseClient` that runs every statement in one open `sqlx::Transaction`.
+macro_rules! transaction_client {
+    ($name:ident, $db:ty, $to_row:ident, $insert_id:ident) => {
+        pub struct $name {
+            /// `None` once committed or rolled back
+            tx: Arc<Mutex<Option<sqlx::Transaction<'static, $db>>>>,
+        }
+
+        impl $name {
+            fn new(tx: sqlx::Transaction<'static, $db>) -> Self {
+                Self { tx: Arc::new(Mutex::new(Some(tx))) }
+            }
+        }
+
+        impl Debug for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_struct(stringify!($name)).finish_non_exhaustive()
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl DatabaseClient for $name {
+            async fn execute(&self, sql: &str) -> Result<(), DbError> {
+                self.execute_params(sql, &[]).await
+            }
+
+            async fn execute_params(&self, sql: &str, params: &[DbValue]) -> Result<(), DbError> {
+                let mut guard = self.tx.lock().await;
+                let tx = guard.as_mut().ok_or_else(transaction_finished)?;
+                let mut query = sqlx::query(sql);
+                for param in params {
+                    query = bind_value!(query, param);
+                }
+                query.execute(&mut **tx).await?;
+                Ok(())
+            }
+
+            async fn execute_insert(&self, sql: &str, params: &[DbValue]) -> Result<i64, DbError> {
+                let mut guard = self.tx.lock().await;
+                let tx = guard.as_mut().ok_or_else(transaction_finished)?;
+                let mut query = sqlx::query(sql);
+                for param in params {
+                    query = bind_value!(query, param);
+                }
+                Ok($insert_id(query.execute(&mut **tx).await?))
+            }
+
+            async fn fetch_strings(&self, sql: &str) -> Result<Vec<String>, DbError> {
+                self.fetch_strings_params(sql, &[]).await
+            }
+
+            async fn fetch_strings_params(&self, sql: &str, params: &[DbValue]) -> Result<Vec<String>, DbError> {
+                let mut guard = self.tx.lock().await;
+                let tx = guard.as_mut().ok_or_else(transaction_finished)?;
+                let mut query = sqlx::query(sql);
+                for param in params {
+                    query = bind_value!(query, param);
+                }
+                let rows = query.fetch_all(&mut **tx).await?;
+                Ok(rows.into_iter().map(|row| row.try_get::<String, _>(0)).collect::<Result<_, _>>()?)
+            }
+
+            async fn fetch_count_params(&self, sql: &str, params: &[DbValue]) -> Result<i64, DbError> {
+                let mut guard = self.tx.lock().await;
+                let tx = guard.as_mut().ok_or_else(transaction_finished)?;
+                let mut query = sqlx::query(sql);
+                for param in params {
+                    query = bind_value!(query, param);
+                }
+                let row = query.fetch_one(&mut **tx).await?;
+                Ok(row.try_get(0)?)
+            }
+
+            async fn fetch_numbers(&self, sql: &str) -> Result<Vec<i64>, DbError> {
+                let mut guard = self.tx.lock().await;
+                let tx = guard.as_mut().ok_or_else(transaction_finished)?;
+                let rows = sqlx::query(sql).fetch_all(&mut **tx).await?;
+                Ok(rows.into_iter().filter_map(|row| row.try_get::<Option<i64>, _>(0).transpose()).collect::<Result<_, _>>()?)
+            }
+
+            async fn execute_batch(&self, statements: &[String]) -> Result<(), DbError> {
+                let mut guard = self.tx.lock().await;
+                let tx = guard.as_mut().ok_or_else(transaction_finished)?;
+                for statement in statements {
+                    sqlx::query(statement).execute(&mut **tx).await?;
+                }
+                Ok(())
+            }
+
+            async fn fetch_rows(&self, sql: &str, params: &[DbValue]) -> Result<Vec<DbRow>, DbError> {
+                let mut guard = self.tx.lock().await;
+                let tx = guard.as_mut().ok_or_else(transaction_finished)?;
+                let mut query = sqlx::query(sql);
+                for param in params {
+                    query = bind_value!(query, param);
+                }
+                let rows = query.fetch_all(&mut **tx).await?;
+                Ok(rows.iter().map($to_row).collect())
+            }
+
+            // other statements of the transaction wait until the stream ends or is dropped
+            fn fetch_stream<'a>(&'a self, sql: String, params: Vec<DbValue>) -> BoxStream<'a, Result<DbRow, DbError>> {
+                let tx = self.tx.clone();
+                spawn_stream(move |mut sender| async move {
+                    let mut guard = tx.lock_owned().await;
+                    let Some(tx) = guard.as_mut() else {
+                        let _ = sender.send(Err(transaction_finished())).await;
+                        return;
+                    };
+                    let mut query = sqlx::query(&sql);
+                    for param in &params {
+                        query = bind_value!(query, param);
+                    }
+                    feed_rows!(query, &mut **tx, sender, $to_row);
+                })
+            }
+
+            async fn acquire_lock(&self, _name: &str, _owner: &str, _timeout: Duration, _stale_after: Duration) -> Result<DbLock, DbError> {
+                Err(DbError::InvalidQuery("locks are taken outside of a transaction".to_string()))
+            }
+
+            async fn begin(&self) -> Result<Box<dyn DatabaseClient + Send + Sync>, DbError> {
+                Err(DbError::InvalidQuery("already in a transaction, nest it with a savepoint".to_string()))
+            }
+
+            async fn commit(&self) -> Result<(), DbError> {
+                let tx = self.tx.lock().await.take().ok_or_else(transaction_finished)?;
+                tx.commit().await?;
+                Ok(())
+            }
+
+            async fn rollback(&self) -> Result<(), DbError> {
+                let tx = self.tx.lock().await.take().ok_or_else(transaction_finished)?;
+                tx.rollback().await?;
+                Ok(())
+            }
+        }
+    };
+}
+
+transaction_client!(MySqlTransactionClient, MySql, mysql_row, mysql_insert_id);
+transaction_client!(SqliteTransactionClient, Sqlite, sqlite_row, sqlite_insert_id);
+
+fn mysql_insert_id(result: MySqlQueryResult) -> i64 {
+    result.last_insert_id() as i64
+}
 
+fn sqlite_insert_id(result: SqliteQueryResult) -> i64 {
+    result.last_insert_rowid()
 }
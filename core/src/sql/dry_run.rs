@@ -0,0 +1,104 @@
+//! Dry runs: a `DatabaseClient` that records statements instead of running them.
+//!
+//! `Schema::begin_dry_run` swaps it in, e.g. so `migrate --lint` can call a
+//! migration's `up()` without touching the database. Writes succeed without
+//! effect and reads come back empty.
+
+use crate::sql::database_client::{DatabaseClient, DbError, DbLock};
+use crate::sql::value::{DbRow, DbValue};
+use futures::stream::{self, BoxStream};
+use futures::StreamExt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Records the SQL it gets; clones, and the transactions it begins, share the record.
+#[derive(Debug, Clone, Default)]
+pub struct DryRunClient {
+    statements: Arc<Mutex<Vec<String>>>,
+}
+
+impl DryRunClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The statements received so far, in order.
+    pub fn statements(&self) -> Vec<String> {
+        self.statements.lock().unwrap().clone()
+    }
+
+    fn record(&self, sql: &str) {
+        self.statements.lock().unwrap().push(sql.trim().to_string());
+    }
+}
+
+#[async_trait::async_trait]
+impl DatabaseClient for DryRunClient {
+    async fn execute(&self, sql: &str) -> Result<(), DbError> {
+        self.record(sql);
+        Ok(())
+    }
+
+    async fn execute_params(&self, sql: &str, _params: &[DbValue]) -> Result<(), DbError> {
+        self.record(sql);
+        Ok(())
+    }
+
+    async fn execute_insert(&self, sql: &str, _params: &[DbValue]) -> Result<i64, DbError> {
+        self.record(sql);
+        Ok(0)
+    }
+
+    async fn fetch_strings(&self, sql: &str) -> Result<Vec<String>, DbError> {
+        self.record(sql);
+        Ok(vec![])
+    }
+
+    async fn fetch_strings_params(&self, sql: &str, _params: &[DbValue]) -> Result<Vec<String>, DbError> {
+        self.record(sql);
+        Ok(vec![])
+    }
+
+    async fn fetch_count_params(&self, sql: &str, _params: &[DbValue]) -> Result<i64, DbError> {
+        self.record(sql);
+        Ok(0)
+    }
+
+    async fn fetch_numbers(&self, sql: &str) -> Result<Vec<i64>, DbError> {
+        self.record(sql);
+        Ok(vec![])
+    }
+
+    async fn execute_batch(&self, statements: &[String]) -> Result<(), DbError> {
+        for sql in statements {
+            self.record(sql);
+        }
+        Ok(())
+    }
+
+    async fn fetch_rows(&self, sql: &str, _params: &[DbValue]) -> Result<Vec<DbRow>, DbError> {
+        self.record(sql);
+        Ok(vec![])
+    }
+
+    fn fetch_stream<'a>(&'a self, sql: String, _params: Vec<DbValue>) -> BoxStream<'a, Result<DbRow, DbError>> {
+        self.record(&sql);
+        stream::empty().boxed()
+    }
+
+    async fn acquire_lock(&self, _name: &str, _owner: &str, _timeout: Duration, _stale_after: Duration) -> Result<DbLock, DbError> {
+        Err(DbError::InvalidQuery("a dry run takes no locks".to_string()))
+    }
+
+    async fn begin(&self) -> Result<Box<dyn DatabaseClient + Send + Sync>, DbError> {
+        Ok(Box::new(self.clone()))
+    }
+
+    async fn commit(&self) -> Result<(), DbError> {
+        Ok(())
+    }
+
+    async fn rollback(&self) -> Result<(), DbError> {
+        Ok(())
+    }
+}
@@ -0,0 +1,135 @@
+//! Rust enums for `enums()` / `sets()` columns.
+//!
+//! `#[derive(DbEnum)]` (from `macros`) implements [`DbEnum`] for a unit-only
+//! enum, along with the `DbValue` conversions `#[derive(Model)]` needs. The
+//! stored value of a variant is its `#[serde(rename = "..")]` or
+//! `#[sqlx(rename = "..")]`, else its name. A `sets()` column is an
+//! [`EnumSet`] of such an enum. `Table::to_struct()` writes both for you.
+//!
+//! # Examples
+//! ```rust,ignore
+//! #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, sqlx::Type, DbEnum)]
+//! pub enum PostStatus {
+//!     #[serde(rename = "draft")]
+//!     #[sqlx(rename = "draft")]
+//!     Draft,
+//!     #[serde(rename = "published")]
+//!     #[sqlx(rename = "published")]
+//!     Published,
+//! }
+//!
+//! assert_eq!(PostStatus::VARIANTS, &["draft", "published"]);
+//! assert_eq!(PostStatus::parse("draft"), Some(PostStatus::Draft));
+//! ```
+
+use crate::sql::value::{DbValue, FromSqlValue};
+use serde::{Deserialize, Serialize};
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::{Database, Decode, Encode, Type};
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+/// An enum stored as one of a fixed list of strings.
+pub trait DbEnum: Sized + Copy + 'static {
+    /// Stored value of every variant, in declaration order.
+    const VARIANTS: &'static [&'static str];
+
+    /// Stored value of this variant.
+    fn as_str(&self) -> &'static str;
+
+    /// The variant stored as `value`, `None` for anything else.
+    fn parse(value: &str) -> Option<Self>;
+}
+
+/// Value of a `sets()` column: the chosen variants, stored comma separated.
+///
+/// Serialized as a list, e.g. `["news","tech"]`.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EnumSet<E>(pub Vec<E>);
+
+impl<E: DbEnum> EnumSet<E> {
+    /// `None` when an item isn't a variant of `E`.
+    pub fn parse(value: &str) -> Option<Self> {
+        value
+            .split(',')
+            .filter(|item| !item.is_empty())
+            .map(E::parse)
+            .collect::<Option<Vec<_>>>()
+            .map(Self)
+    }
+}
+
+impl<E> Deref for EnumSet<E> {
+    type Target = Vec<E>;
+
+    fn deref(&self) -> &Vec<E> {
+        &self.0
+    }
+}
+
+impl<E> DerefMut for EnumSet<E> {
+    fn deref_mut(&mut self) -> &mut Vec<E> {
+        &mut self.0
+    }
+}
+
+impl<E> From<Vec<E>> for EnumSet<E> {
+    fn from(items: Vec<E>) -> Self {
+        Self(items)
+    }
+}
+
+impl<E: DbEnum> fmt::Display for EnumSet<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let items: Vec<&str> = self.0.iter().map(DbEnum::as_str).collect();
+        f.write_str(&items.join(","))
+    }
+}
+
+impl<E: DbEnum> From<EnumSet<E>> for DbValue {
+    fn from(set: EnumSet<E>) -> Self {
+        DbValue::Text(set.to_string())
+    }
+}
+
+impl<E: DbEnum> FromSqlValue for EnumSet<E> {
+    fn from_sql_value(value: &DbValue) -> Option<Self> {
+        Self::parse(&value.as_text()?)
+    }
+}
+
+// sqlx sees the set as its text, so `sqlx::FromRow` structs can hold one too
+
+impl<DB: Database, E> Type<DB> for EnumSet<E>
+where
+    String: Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <String as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <String as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'q, DB: Database, E: DbEnum> Encode<'q, DB> for EnumSet<E>
+where
+    String: Encode<'q, DB>,
+{
+    fn encode_by_ref(&self, buf: &mut DB::ArgumentBuffer<'q>) -> Result<IsNull, BoxDynError> {
+        self.to_string().encode_by_ref(buf)
+    }
+}
+
+impl<'r, DB: Database, E: DbEnum> Decode<'r, DB> for EnumSet<E>
+where
+    String: Decode<'r, DB>,
+{
+    fn decode(value: DB::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        let text = String::decode(value)?;
+        Self::parse(&text).ok_or_else(|| format!("`{}` is not a set of {:?}", text, E::VARIANTS).into())
+    }
+}
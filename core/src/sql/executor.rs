@@ -0,0 +1,30 @@
+//! Where the query and write builders run their SQL: a `Schema` (the pool) or an
+//! open `Transaction`.
+
+use crate::sql::database_client::DbError;
+use crate::sql::generator::SqlGenerator;
+use crate::sql::value::{DbRow, DbValue};
+use futures::stream::BoxStream;
+
+/// The statements the builders need, on a `Schema` or a `Transaction`.
+///
+/// # Examples
+/// ```rust,ignore
+/// Todo::insert().set(col!(Todo::title), "a").execute(&schema).await?;
+/// DB::transaction(|tx| async move {
+///     Todo::insert().set(col!(Todo::title), "b").execute(&tx).await
+/// })
+/// .await?;
+/// ```
+#[async_trait::async_trait]
+pub trait QueryExecutor: Send + Sync {
+    fn generator(&self) -> &(dyn SqlGenerator + Send + Sync);
+    /// `table` with the table prefix.
+    fn table_name(&self, table: &str) -> String;
+    async fn execute_params(&self, sql: &str, params: &[DbValue]) -> Result<(), DbError>;
+    /// Runs an `INSERT`, returning the auto-increment key it generated.
+    async fn execute_insert(&self, sql: &str, params: &[DbValue]) -> Result<i64, DbError>;
+    async fn fetch_rows(&self, sql: &str, params: &[DbValue]) -> Result<Vec<DbRow>, DbError>;
+    async fn fetch_count(&self, sql: &str, params: &[DbValue]) -> Result<i64, DbError>;
+    fn fetch_stream(&self, sql: String, params: Vec<DbValue>) -> BoxStream<'_, Result<DbRow, DbError>>;
+}
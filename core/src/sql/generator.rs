@@ -1,4 +1,6 @@
-use crate::db::table::{Column, ForeignKey, TableAction};
+use crate::db::introspection::ColumnInfo;
+use crate::db::table::{Column, ForeignKey, Table, TableAction};
+use crate::sql::value::DbValue;
 use std::fmt::Debug;
 
 pub trait SqlGenerator: Debug + Sync + Send {
@@ -29,24 +31,142 @@ pub trait SqlGenerator: Debug + Sync + Send {
     fn foreign_key(&self, key: &ForeignKey, table_name: &str, action: &TableAction) -> String;
     fn drop_column(&self, column_name: &str) -> String;
 
-    fn table_sql(
-        &self,
-        table_name: &str,
-        body_sql: &str,
-        post_sql: &str,
-        action: &TableAction,
-    ) -> String;
+    /// The `CREATE` / `ALTER` statement of `table`, table options included.
+    fn table_sql(&self, table: &Table, body_sql: &str, post_sql: &str) -> String;
 
-    fn get_ran(&self) -> String;
-    fn get_ran_gt(&self) -> String;
+    /// Every ran migration; `table` is the (prefixed) migrations table.
+    fn get_ran(&self, table: &str) -> String;
+    /// Ran migrations above a batch, newest first (reverse application order).
+    fn get_ran_gt(&self, table: &str) -> String;
+    /// Last `count` ran migrations, newest first.
+    fn get_last_ran(&self, table: &str, count: u64) -> String;
 
-    fn get_next_batch_number(&self) -> String;
+    fn get_next_batch_number(&self, table: &str) -> String;
 
-    fn add_migrated_table(&self) -> String;
-    fn rem_migrated_table(&self) -> String;
+    fn add_migrated_table(&self, table: &str) -> String;
+    fn rem_migrated_table(&self, table: &str) -> String;
+    fn set_migration_checksum(&self, table: &str) -> String;
+    /// `migration`, `batch` and `checksum` of every row, in application order;
+    /// without a `checksum` column (`checksum: false`) it is read as NULL.
+    fn get_migration_records(&self, table: &str, checksum: bool) -> String;
     
     fn record_exists(&self,table: &str,column: &str) -> String;
     
     fn record_exists_except(&self,table: &str,column: &str, except: &str) -> String;
 
+    fn insert(&self, table: &str, columns: &[&str]) -> String;
+
+    fn update(&self, table: &str, columns: &[&str], key_column: &str) -> String;
+
+    fn count_where(&self, table: &str, columns: &[&str]) -> String;
+
+    fn truncate(&self, table: &str) -> String;
+
+    /// Full-text index over `columns`, as `(footer, post)` statements like `column()`.
+    fn fulltext(&self, table_name: &str, key_column: &str, columns: &[String], action: &TableAction) -> (String, String);
+
+    /// Table holding the full-text index of `table`, when the engine keeps it in one.
+    fn fulltext_table(&self, _table: &str) -> Option<String> {
+        None
+    }
+
+    /// Statements removing the full-text index of `table_name`.
+    fn drop_fulltext(&self, table_name: &str) -> String;
+
+    /// A page of `table` rows matching `terms`, best first, with a `score` column after `columns`.
+    ///
+    /// `searchable` are the columns of the full-text index.
+    #[allow(clippy::too_many_arguments)]
+    fn search(
+        &self,
+        table: &str,
+        key_column: &str,
+        columns: &[&str],
+        searchable: &[&str],
+        terms: &str,
+        limit: u64,
+        offset: u64,
+    ) -> (String, Vec<DbValue>);
+
+    /// Number of rows `search()` matches.
+    fn search_count(&self, table: &str, searchable: &[&str], terms: &str) -> (String, Vec<DbValue>);
+
+    /// `SELECT` of `columns` ordered by `key`, used by `chunk` / `cursor`.
+    ///
+    /// `after_key` adds `WHERE key > ?`; `limit` and `offset` are written as literals.
+    fn select_ordered(
+        &self,
+        table: &str,
+        columns: &[&str],
+        key: &str,
+        after_key: bool,
+        limit: Option<u64>,
+        offset: u64,
+    ) -> String {
+        let columns: Vec<String> = columns.iter().map(|c| self.quote_ident(c)).collect();
+        let mut sql = format!("SELECT {} FROM {}", columns.join(", "), self.quote_ident(table));
+        if after_key {
+            sql += &format!(" WHERE {} > ?", self.quote_ident(key));
+        }
+        sql += &format!(" ORDER BY {}", self.quote_ident(key));
+        if let Some(limit) = limit {
+            sql += &format!(" LIMIT {} OFFSET {}", limit, offset);
+        }
+        sql
+    }
+
+    /// `SELECT columns FROM table WHERE key = ?`, one row by its primary key.
+    fn select_by_key(&self, table: &str, columns: &[&str], key: &str) -> String {
+        let columns: Vec<String> = columns.iter().map(|c| self.quote_ident(c)).collect();
+        format!(
+            "SELECT {} FROM {} WHERE {} = ?",
+            columns.join(", "),
+            self.quote_ident(table),
+            self.quote_ident(key)
+        )
+    }
+
+    /// Queries for `schema:dump`, each row has a `CREATE` statement in its second column.
+    fn dump_structure(&self, tables: &[String], views: &[String]) -> Vec<String>;
+
+    /// Literal `INSERT` of one row of the migrations `table`, for `schema:dump`.
+    fn dump_migration_record(&self, table: &str, migration: &str, batch: i64, checksum: Option<&str>) -> String;
+
+    fn drop_view_if_exists(&self, view_name: &str) -> String;
+
+    /// Identifier (table, column, index) quoted and escaped for this engine.
+    fn quote_ident(&self, name: &str) -> String;
+
+    /// Text as a string literal, escaped for this engine.
+    fn quote_literal(&self, value: &str) -> String;
+
+    /// Page of rows for `db:dump`, binary columns hex encoded and everything else readable as text.
+    fn dump_select(&self, table: &str, columns: &[ColumnInfo], order_by: &[&str], limit: usize, offset: usize) -> String;
+
+    /// Multi-row `INSERT` of already quoted literals.
+    fn dump_insert(&self, table: &str, columns: &[&str], rows: &[Vec<String>]) -> String;
+
+    /// One row holding the table comment.
+    fn introspect_table(&self, table: &str) -> String;
+
+    /// `name, type, nullable, default, primary, comment` per column, in table order.
+    fn introspect_columns(&self, table: &str) -> String;
+
+    /// `column, foreign_table, referenced_column` per foreign key column.
+    fn introspect_foreign_keys(&self, table: &str) -> String;
+
+    /// `index, column, unique` per indexed column, in key order.
+    fn introspect_indexes(&self, table: &str) -> String;
+
+    /// Display name of the engine, for `db:show`.
+    fn engine(&self) -> &'static str;
+
+    fn server_version(&self) -> String;
+
+    /// One row with the size of the current database in bytes.
+    fn database_size(&self) -> String;
+
+    /// Count of client connections, `None` when the engine has no server.
+    fn open_connections(&self) -> Option<String>;
+
 }
@@ -3,3 +3,9 @@ pub mod generator;
 pub mod mysql;
 pub mod sqlite;
 pub mod query;
+pub mod value;
+pub mod enums;
+pub mod script;
+pub mod query_log;
+pub mod dry_run;
+pub mod executor;
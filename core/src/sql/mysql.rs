@@ -1,9 +1,11 @@
 use super::generator::SqlGenerator;
+use crate::db::introspection::ColumnInfo;
 use crate::config::CONFIG;
 use crate::db::table::{
-    Column, ColumnDataType, ColumnOption, DefaultValue, ForeignKey, TableAction,
+    Column, ColumnDataType, ColumnOption, DefaultValue, ForeignKey, Table, TableAction,
 };
 use crate::logger;
+use crate::sql::value::DbValue;
 use std::string::String;
 // use axum::Form;
 
@@ -17,11 +19,61 @@ impl MySqlGenerator {
     fn db(&self) -> &'static str {
         "DATABASE()"
     }
+
+    /// `MATCH ... AGAINST (?)`, the columns must be exactly those of the `FULLTEXT` index.
+    fn match_against(&self, searchable: &[&str]) -> String {
+        format!(
+            "MATCH({}) AGAINST(? IN NATURAL LANGUAGE MODE)",
+            searchable.iter().map(|c| self.quote_ident(c)).collect::<Vec<_>>().join(", ")
+        )
+    }
+
+    /// Options after `CREATE TABLE (...)`, `DB_CHARSET` / `DB_COLLATION` unless the table sets its own.
+    fn table_options(&self, table: &Table) -> String {
+        // each one implies the other, so the config pair is only used as a whole
+        let (charset, collation) = match (&table.charset, &table.collation) {
+            (None, None) => (Some(CONFIG.database.charset.as_str()), Some(CONFIG.database.collection.as_str())),
+            (charset, collation) => (charset.as_deref(), collation.as_deref()),
+        };
+        let mut options = String::new();
+        if let Some(charset) = charset {
+            // a collation names its charset (`utf8mb4_unicode_ci`), repeating it changes nothing
+            if !collation.is_some_and(|collation| collation.starts_with(&format!("{}_", charset))) {
+                options.push_str(&format!(" DEFAULT CHARSET={}", self.option_name(charset)));
+            }
+        }
+        if let Some(collation) = collation {
+            options.push_str(&format!(" COLLATE={}", self.quote_literal(collation)));
+        }
+        options.push_str(&format!(" ENGINE={}", self.option_name(table.engine.as_deref().unwrap_or("InnoDB"))));
+        if let Some(start) = table.auto_increment {
+            options.push_str(&format!(" AUTO_INCREMENT={}", start));
+        }
+        if !table.comment.is_empty() {
+            options.push_str(&format!(" COMMENT={}", self.quote_literal(&table.comment)));
+        }
+        options
+    }
+
+    /// An engine or charset name: `InnoDB` or `latin1` as written, anything else
+    /// quoted so it can't reach past the option.
+    fn option_name(&self, name: &str) -> String {
+        if !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            name.to_string()
+        } else {
+            self.quote_ident(name)
+        }
+    }
+}
+
+/// Name of the `FULLTEXT` index of `table`.
+fn fulltext_index(table: &str) -> String {
+    format!("{}_fulltext", table)
 }
 
 impl SqlGenerator for MySqlGenerator {
     fn drop_table_if_exists(&self, table_name: &str) -> String {
-        format!("DROP TABLE IF EXISTS `{}`;", table_name)
+        format!("DROP TABLE IF EXISTS {};", self.quote_ident(table_name))
     }
 
     fn get_tables(&self) -> String {
@@ -48,9 +100,9 @@ impl SqlGenerator for MySqlGenerator {
             "
         SELECT column_name
         FROM information_schema.columns
-        WHERE table_schema = DATABASE() AND TABLE_NAME = '{}';
+        WHERE table_schema = DATABASE() AND TABLE_NAME = {};
         ",
-            table_name
+            self.quote_literal(table_name)
         )
     }
 
@@ -61,19 +113,19 @@ impl SqlGenerator for MySqlGenerator {
             SELECT constraint_name
             FROM information_schema.table_constraints
             WHERE table_schema = DATABASE()
-              AND table_name = '{}'
+              AND table_name = {}
               AND constraint_type = 'FOREIGN KEY'
             ",
-            table_name
+            self.quote_literal(table_name)
         )
     }
 
     fn drop_table(&self, table_name: &str) -> String {
-        format!("DROP TABLE `{}`;", table_name)
+        format!("DROP TABLE {};", self.quote_ident(table_name))
     }
 
     fn drop_view(&self, view_name: &str) -> String {
-        format!("DROP VIEW `{}`;", view_name)
+        format!("DROP VIEW {};", self.quote_ident(view_name))
     }
 
     // fn drop_all_tables(&self) -> String {
@@ -102,11 +154,12 @@ impl SqlGenerator for MySqlGenerator {
             SELECT '1'
             FROM information_schema.columns
             WHERE table_schema = DATABASE()
-              AND table_name = '{}'
-              AND column_name = '{}'
+              AND table_name = {}
+              AND column_name = {}
             LIMIT 1
             ",
-            table_name, column_name
+            self.quote_literal(table_name),
+            self.quote_literal(column_name)
         )
     }
 
@@ -116,10 +169,10 @@ impl SqlGenerator for MySqlGenerator {
             SELECT '1'
             FROM information_schema.tables
             WHERE table_schema = DATABASE()
-              AND table_name = '{}'
+              AND table_name = {}
             LIMIT 1
             ",
-            table_name
+            self.quote_literal(table_name)
         )
     }
 
@@ -129,17 +182,17 @@ impl SqlGenerator for MySqlGenerator {
             SELECT '1'
             FROM information_schema.views
             WHERE table_schema = DATABASE()
-              AND table_name = '{}'
+              AND table_name = {}
             LIMIT 1
             ",
-            table_name
+            self.quote_literal(table_name)
         )
     }
 
     fn has_index(&self, table_name: &str, columns_name: Vec<&str>) -> String {
         let cols = columns_name
             .iter()
-            .map(|c| format!("'{}'", c))
+            .map(|c| self.quote_literal(c))
             .collect::<Vec<_>>()
             .join(",");
 
@@ -150,22 +203,24 @@ impl SqlGenerator for MySqlGenerator {
             SELECT '1' AS index_count
             FROM information_schema.statistics
             WHERE table_schema = DATABASE()
-              AND table_name = '{}'
+              AND table_name = {}
               AND column_name IN ({})
             GROUP BY index_count
             HAVING COUNT(*) = {}
             LIMIT 1
             ",
-            table_name, cols, count
+            self.quote_literal(table_name),
+            cols,
+            count
         )
     }
 
     fn create_database(&self, db_name: &str) -> String {
-        format!("CREATE DATABASE `{}`;", db_name)
+        format!("CREATE DATABASE {};", self.quote_ident(db_name))
     }
 
     fn drop_database_if_exists(&self, db_name: &str) -> String {
-        format!("DROP DATABASE IF EXISTS `{}`;", db_name)
+        format!("DROP DATABASE IF EXISTS {};", self.quote_ident(db_name))
     }
 
     fn disable_foreign_key_constraints(&self) -> String {
@@ -177,7 +232,11 @@ impl SqlGenerator for MySqlGenerator {
     }
 
     fn rename(&self, old_table_name: &str, new_table_name: &str) -> String {
-        format!("RENAME TABLE `{}` TO `{}`;", old_table_name, new_table_name)
+        format!(
+            "RENAME TABLE {} TO {};",
+            self.quote_ident(old_table_name),
+            self.quote_ident(new_table_name)
+        )
     }
 
     fn column(
@@ -201,9 +260,10 @@ impl SqlGenerator for MySqlGenerator {
 
         let unsigned = if column.unsigned { "UNSIGNED" } else { "" };
 
+        let name = self.quote_ident(&column.name);
         let def = match &column.default {
             DefaultValue::Null => "DEFAULT NULL",
-            DefaultValue::String(str) => &format!(" DEFAULT '{}' ", str),
+            DefaultValue::String(str) => &format!(" DEFAULT {} ", self.quote_literal(str)),
             DefaultValue::JsonArray => "DEFAULT json_array()",
             DefaultValue::CurrenTimestamp => "DEFAULT current_timestamp()",
             DefaultValue::Bool(bool_val) => {
@@ -223,41 +283,41 @@ impl SqlGenerator for MySqlGenerator {
                 footer_sql = "PRIMARY KEY (`id`)".to_string();
             }
             ColumnDataType::DTBoolean => {
-                column_sql = format!("`{}` BIT(1) {} {}", column.name, nullable, def);
+                column_sql = format!("{} BIT(1) {} {}", name, nullable, def);
             }
             ColumnDataType::DTTinyInteger => {
                 column_sql = format!(
-                    "`{}` TINYINT {} {} {}",
-                    column.name, unsigned, nullable, def
+                    "{} TINYINT {} {} {}",
+                    name, unsigned, nullable, def
                 );
             }
             ColumnDataType::DTInteger => {
-                column_sql = format!("`{}` INT {} {} {}", column.name, unsigned, nullable, def);
+                column_sql = format!("{} INT {} {} {}", name, unsigned, nullable, def);
             }
             ColumnDataType::DTSmallInteger => {
                 column_sql = format!(
-                    "`{}` SMALLINT {} {} {}",
-                    column.name, unsigned, nullable, def
+                    "{} SMALLINT {} {} {}",
+                    name, unsigned, nullable, def
                 );
             }
             ColumnDataType::DTMediumInteger => {
                 column_sql = format!(
-                    "`{}` MEDIUMINT {} {} {}",
-                    column.name, unsigned, nullable, def
+                    "{} MEDIUMINT {} {} {}",
+                    name, unsigned, nullable, def
                 );
             }
             ColumnDataType::DTBigInteger => {
                 // BIGINT(20) why `20` ? cuz compatible with id foreign key
                 column_sql = format!(
-                    "`{}` BIGINT(20) {} {} {}",
-                    column.name, unsigned, nullable, def
+                    "{} BIGINT(20) {} {} {}",
+                    name, unsigned, nullable, def
                 );
             }
             ColumnDataType::DTFloat => {
-                column_sql = format!("`{}` FLOAT {} {} {}", column.name, unsigned, nullable, def);
+                column_sql = format!("{} FLOAT {} {} {}", name, unsigned, nullable, def);
             }
             ColumnDataType::DTDouble => {
-                column_sql = format!("`{}` DOUBLE {} {} {}", column.name, unsigned, nullable, def);
+                column_sql = format!("{} DOUBLE {} {} {}", name, unsigned, nullable, def);
             }
             ColumnDataType::DTDecimal => {
                 let (precision, scale) = match &column.option {
@@ -265,8 +325,8 @@ impl SqlGenerator for MySqlGenerator {
                     _ => (20, 6),
                 };
                 column_sql = format!(
-                    "`{}` DECIMAL({},{}) {} {} {}",
-                    column.name, precision, scale, unsigned, nullable, def
+                    "{} DECIMAL({},{}) {} {} {}",
+                    name, precision, scale, unsigned, nullable, def
                 );
             }
             ColumnDataType::DTString => {
@@ -274,38 +334,38 @@ impl SqlGenerator for MySqlGenerator {
                     ColumnOption::Length(l) => l,
                     _ => 127,
                 };
-                column_sql = format!("`{}` VARCHAR({}) {} {}", column.name, len, nullable, def);
+                column_sql = format!("{} VARCHAR({}) {} {}", name, len, nullable, def);
             }
             ColumnDataType::DTText => {
-                column_sql = format!("`{}` TEXT {} {}", column.name, nullable, def);
+                column_sql = format!("{} TEXT {} {}", name, nullable, def);
             }
             ColumnDataType::DTTinyText => {
-                column_sql = format!("`{}` TINYTEXT {} {}", column.name, nullable, def);
+                column_sql = format!("{} TINYTEXT {} {}", name, nullable, def);
             }
             ColumnDataType::DTMediumText => {
-                column_sql = format!("`{}` MEDIUMTEXT {} {}", column.name, nullable, def);
+                column_sql = format!("{} MEDIUMTEXT {} {}", name, nullable, def);
             }
             ColumnDataType::DTLongText => {
-                column_sql = format!("`{}` LONGTEXT {} {}", column.name, nullable, def);
+                column_sql = format!("{} LONGTEXT {} {}", name, nullable, def);
             }
             ColumnDataType::DTJson => {
-                column_sql = format!("`{}` LONGTEXT {} {}", column.name, nullable, def);
-                footer_sql = format!("CONSTRAINT `{}` CHECK (json_valid(`data`))", column.name);
+                column_sql = format!("{} LONGTEXT {} {}", name, nullable, def);
+                footer_sql = format!("CONSTRAINT {} CHECK (json_valid(`data`))", name);
                 if collation.is_empty() {
                     collation = "utf8mb4_bin";
                 }
             }
             ColumnDataType::DTDate => {
-                column_sql = format!("`{}` DATE {} {}", column.name, nullable, def);
+                column_sql = format!("{} DATE {} {}", name, nullable, def);
             }
             ColumnDataType::DTDateTime => {
-                column_sql = format!("`{}` DATETIME {} {}", column.name, nullable, def);
+                column_sql = format!("{} DATETIME {} {}", name, nullable, def);
             }
             ColumnDataType::DTTime => {
-                column_sql = format!("`{}` TIME {} {}", column.name, nullable, def);
+                column_sql = format!("{} TIME {} {}", name, nullable, def);
             }
             ColumnDataType::DTTimestamp => {
-                column_sql = format!("`{}` TIMESTAMP {} {}", column.name, nullable, def);
+                column_sql = format!("{} TIMESTAMP {} {}", name, nullable, def);
             }
             ColumnDataType::DTTimestamps => {
                 column_sql = "`created_at` TIMESTAMP NULL DEFAULT NULL, `updated_at` TIMESTAMP NULL DEFAULT NULL".to_string();
@@ -317,56 +377,61 @@ impl SqlGenerator for MySqlGenerator {
                 let enums = match &column.option {
                     ColumnOption::Values(items) => items
                         .iter()
-                        .map(|item| format!("'{}'", item))
+                        .map(|item| self.quote_literal(item))
                         .collect::<Vec<_>>()
                         .join(", "),
                     _ => "''".to_string(),
                 };
-                column_sql = format!("`{}` ENUM({}) {} {}", column.name, enums, nullable, def);
+                column_sql = format!("{} ENUM({}) {} {}", name, enums, nullable, def);
             }
             ColumnDataType::DTSet => {
                 let sets = match &column.option {
                     ColumnOption::Values(items) => items
                         .iter()
-                        .map(|item| format!("'{}'", item))
+                        .map(|item| self.quote_literal(item))
                         .collect::<Vec<_>>()
                         .join(", "),
                     _ => "''".to_string(),
                 };
-                column_sql = format!("`{}` SET({}) {} {}", column.name, sets, nullable, def);
+                column_sql = format!("{} SET({}) {} {}", name, sets, nullable, def);
             }
             ColumnDataType::DTMorph => {
+                let morph_type = self.quote_ident(&format!("{}_type", column.name));
+                let morph_id = self.quote_ident(&format!("{}_id", column.name));
                 column_sql = format!(
-                    "`{}_type` VARCHAR(255) {} {} ,\
-                `{}_id` BIGINT(20) UNSIGNED {} {}",
-                    column.name, nullable, def, column.name, nullable, def
+                    "{} VARCHAR(255) {} {} ,\
+                {} BIGINT(20) UNSIGNED {} {}",
+                    morph_type, nullable, def, morph_id, nullable, def
                 );
                 footer_sql = format!(
-                    "INDEX `morph_{}_type_{}_id_index` (`{}_type`, `{}_id`)",
-                    column.name, column.name, column.name, column.name
+                    "INDEX {} ({}, {})",
+                    self.quote_ident(&format!("morph_{}_type_{}_id_index", column.name, column.name)),
+                    morph_type,
+                    morph_id
                 )
             }
             _ => {}
         }
 
         if !column.comment.is_empty() {
-            column_sql = format!("{} COMMENT '{}' ", column_sql, column.comment);
+            column_sql = format!("{} COMMENT {} ", column_sql, self.quote_literal(&column.comment));
         }
         if !collation.is_empty() && column.is_string_type() {
-            column_sql = format!("{} COLLATE '{}' ", column_sql, collation);
+            column_sql = format!("{} COLLATE {} ", column_sql, self.quote_literal(collation));
         }
         if column.index {
-            footer_sql = format!("INDEX `{}` (`{}`)", column.name, column.name);
+            footer_sql = format!("INDEX {} ({})", name, name);
         }
         if column.unique {
             footer_sql = format!(
-                "UNIQUE INDEX `{}_{}_unique` (`{}`)",
-                table_name, column.name, column.name
+                "UNIQUE INDEX {} ({})",
+                self.quote_ident(&format!("{}_{}_unique", table_name, column.name)),
+                name
             );
         }
         if *action == TableAction::Alter {
             if column.change {
-                column_sql = format!("CHANGE COLUMN `{}` {}", column.name, column_sql);
+                column_sql = format!("CHANGE COLUMN {} {}", name, column_sql);
             } else {
                 column_sql = format!("ADD COLUMN {}", column_sql);
             }
@@ -383,11 +448,11 @@ impl SqlGenerator for MySqlGenerator {
 
     fn foreign_key(&self, key: &ForeignKey, table_name: &str, action: &TableAction) -> String {
         let update = match key.on_update {
-            true => "ON DELETE CASCADE",
+            true => "ON UPDATE CASCADE",
             false => "",
         };
         let delete = match key.on_delete {
-            true => "ON UPDATE CASCADE",
+            true => "ON DELETE CASCADE",
             false => "",
         };
         let prefix = match *action {
@@ -395,72 +460,320 @@ impl SqlGenerator for MySqlGenerator {
             _ => "",
         };
         format!(
-            "{} CONSTRAINT `{}_{}_foreign` FOREIGN KEY (`{}`) \
-        REFERENCES `{}` (`{}`) \
+            "{} CONSTRAINT {} FOREIGN KEY ({}) \
+        REFERENCES {} ({}) \
          {} {}",
             prefix,
-            table_name,
-            key.column_name,
-            key.column_name,
-            key.foreign_table,
-            key.referenced_column,
+            self.quote_ident(&format!("{}_{}_foreign", table_name, key.column_name)),
+            self.quote_ident(&key.column_name),
+            self.quote_ident(&key.foreign_table),
+            self.quote_ident(&key.referenced_column),
             update,
             delete
         )
     }
 
     fn drop_column(&self, column_name: &str) -> String {
-        format!("DROP COLUMN `{}`", column_name)
+        format!("DROP COLUMN {}", self.quote_ident(column_name))
     }
 
-    fn table_sql(
-        &self,
-        table_name: &str,
-        body_sql: &str,
-        post_sql: &str,
-        action: &TableAction,
-    ) -> String {
-        match action {
+    fn table_sql(&self, table: &Table, body_sql: &str, post_sql: &str) -> String {
+        match table.action {
             TableAction::Create => {
-                let collection = CONFIG.database.collection.clone();
                 format!(
-                    "CREATE TABLE `{}` ( \n {} \n ) COLLATE='{}' ENGINE=InnoDB; \n {}",
-                    table_name, body_sql, collection, post_sql
+                    "CREATE {}TABLE {} ( \n {} \n ){}; \n {}",
+                    if table.temporary { "TEMPORARY " } else { "" },
+                    self.quote_ident(&table.name),
+                    body_sql,
+                    self.table_options(table),
+                    post_sql
                 )
             }
+            TableAction::Alter if body_sql.is_empty() => post_sql.to_string(),
             TableAction::Alter => {
                 format!(
-                    "ALTER TABLE `{}` \n {} ; \n {}",
-                    table_name, body_sql, post_sql
+                    "ALTER TABLE {} \n {} ; \n {}",
+                    self.quote_ident(&table.name),
+                    body_sql,
+                    post_sql
                 )
             }
             _ => "".to_string(),
         }
     }
 
-    fn get_ran(&self) -> String {
-        "SELECT `migration` FROM `migrations`".to_string()
+    fn get_ran(&self, table: &str) -> String {
+        format!("SELECT `migration` FROM {}", self.quote_ident(table))
     }
-    fn get_ran_gt(&self) -> String {
-        "SELECT `migration` FROM `migrations` WHERE `batch` > ?".to_string()
+    fn get_ran_gt(&self, table: &str) -> String {
+        format!(
+            "SELECT `migration` FROM {} WHERE `batch` > ? ORDER BY `batch` DESC, `id` DESC",
+            self.quote_ident(table)
+        )
     }
 
-    fn get_next_batch_number(&self) -> String {
-        "SELECT MAX(batch) AS 'batch' FROM `migrations`".to_string()
+    fn get_last_ran(&self, table: &str, count: u64) -> String {
+        format!(
+            "SELECT `migration` FROM {} ORDER BY `batch` DESC, `id` DESC LIMIT {}",
+            self.quote_ident(table),
+            count
+        )
     }
 
-    fn add_migrated_table(&self) -> String {
-        "INSERT INTO `migrations` (`migration`, `batch`) VALUES (?, ?)".to_string()
+    fn get_next_batch_number(&self, table: &str) -> String {
+        format!("SELECT MAX(batch) AS 'batch' FROM {}", self.quote_ident(table))
     }
 
-    fn rem_migrated_table(&self) -> String {
-        "DELETE FROM `migrations` WHERE  `migration` = ?".to_string()
+    fn add_migrated_table(&self, table: &str) -> String {
+        format!("INSERT INTO {} (`migration`, `batch`) VALUES (?, ?)", self.quote_ident(table))
+    }
+
+    fn rem_migrated_table(&self, table: &str) -> String {
+        format!("DELETE FROM {} WHERE  `migration` = ?", self.quote_ident(table))
+    }
+
+    fn set_migration_checksum(&self, table: &str) -> String {
+        format!("UPDATE {} SET `checksum` = ? WHERE `migration` = ?", self.quote_ident(table))
+    }
+
+    fn get_migration_records(&self, table: &str, checksum: bool) -> String {
+        format!(
+            "SELECT `migration`, `batch`, {} FROM {} ORDER BY `batch`, `id`",
+            if checksum { "`checksum`" } else { "NULL AS `checksum`" },
+            self.quote_ident(table)
+        )
     }
 
     fn record_exists(&self,table: &str,column: &str) -> String{
-        format!("SELECT COUNT(*) AS 'count' FROM `{}` WHERE `{}` = ?", table, column)
+        format!(
+            "SELECT COUNT(*) AS `count` FROM {} WHERE {} = ?",
+            self.quote_ident(table),
+            self.quote_ident(column)
+        )
     }
     fn record_exists_except(&self,table: &str,column: &str, except: &str) -> String{
-        format!("SELECT COUNT(*) AS 'count' FROM `{}` WHERE `{}` = ? AND `{}` <> ?", table, column, except)
+        format!(
+            "SELECT COUNT(*) AS `count` FROM {} WHERE {} = ? AND {} <> ?",
+            self.quote_ident(table),
+            self.quote_ident(column),
+            self.quote_ident(except)
+        )
+    }
+
+    fn insert(&self, table: &str, columns: &[&str]) -> String {
+        let cols = columns
+            .iter()
+            .map(|c| self.quote_ident(c))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let placeholders = vec!["?"; columns.len()].join(", ");
+        format!("INSERT INTO {} ({}) VALUES ({})", self.quote_ident(table), cols, placeholders)
+    }
+
+    fn update(&self, table: &str, columns: &[&str], key_column: &str) -> String {
+        let sets = columns
+            .iter()
+            .map(|c| format!("{} = ?", self.quote_ident(c)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "UPDATE {} SET {} WHERE {} = ?",
+            self.quote_ident(table),
+            sets,
+            self.quote_ident(key_column)
+        )
+    }
+
+    fn count_where(&self, table: &str, columns: &[&str]) -> String {
+        let mut sql = format!("SELECT COUNT(*) AS `count` FROM {}", self.quote_ident(table));
+        if !columns.is_empty() {
+            let wheres = columns
+                .iter()
+                .map(|c| format!("{} = ?", self.quote_ident(c)))
+                .collect::<Vec<_>>()
+                .join(" AND ");
+            sql.push_str(&format!(" WHERE {}", wheres));
+        }
+        sql
+    }
+
+    fn truncate(&self, table: &str) -> String {
+        format!("TRUNCATE TABLE {};", self.quote_ident(table))
+    }
+
+    fn fulltext(&self, table_name: &str, _key_column: &str, columns: &[String], action: &TableAction) -> (String, String) {
+        let prefix = match *action {
+            TableAction::Alter => "ADD ",
+            _ => "",
+        };
+        let footer = format!(
+            "{}FULLTEXT INDEX {} ({})",
+            prefix,
+            self.quote_ident(&fulltext_index(table_name)),
+            columns.iter().map(|c| self.quote_ident(c)).collect::<Vec<_>>().join(", ")
+        );
+        (footer, String::new())
+    }
+
+    fn drop_fulltext(&self, table_name: &str) -> String {
+        format!(
+            "ALTER TABLE {} DROP INDEX {};",
+            self.quote_ident(table_name),
+            self.quote_ident(&fulltext_index(table_name))
+        )
+    }
+
+    fn search(
+        &self,
+        table: &str,
+        _key_column: &str,
+        columns: &[&str],
+        searchable: &[&str],
+        terms: &str,
+        limit: u64,
+        offset: u64,
+    ) -> (String, Vec<DbValue>) {
+        let source = self.quote_ident(table);
+        let select = columns
+            .iter()
+            .map(|c| format!("{}.{}", source, self.quote_ident(c)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let matches = self.match_against(searchable);
+        let sql = format!(
+            "SELECT {select}, {matches} AS `score` FROM {source} WHERE {matches} \
+             ORDER BY `score` DESC LIMIT {limit} OFFSET {offset}"
+        );
+        (sql, vec![DbValue::from(terms), DbValue::from(terms)])
+    }
+
+    fn search_count(&self, table: &str, searchable: &[&str], terms: &str) -> (String, Vec<DbValue>) {
+        (
+            format!("SELECT COUNT(*) FROM {} WHERE {}", self.quote_ident(table), self.match_against(searchable)),
+            vec![DbValue::from(terms)],
+        )
+    }
+
+    fn dump_structure(&self, tables: &[String], views: &[String]) -> Vec<String> {
+        tables
+            .iter()
+            .map(|t| format!("SHOW CREATE TABLE {}", self.quote_ident(t)))
+            .chain(views.iter().map(|v| format!("SHOW CREATE VIEW {}", self.quote_ident(v))))
+            .collect()
+    }
+
+    fn drop_view_if_exists(&self, view_name: &str) -> String {
+        format!("DROP VIEW IF EXISTS {};", self.quote_ident(view_name))
+    }
+
+    fn quote_ident(&self, name: &str) -> String {
+        format!("`{}`", name.replace('`', "``"))
+    }
+
+    fn quote_literal(&self, value: &str) -> String {
+        // backslash is an escape character unless NO_BACKSLASH_ESCAPES is set
+        let escaped = value
+            .replace('\\', "\\\\")
+            .replace('\'', "''")
+            .replace('\0', "\\0");
+        format!("'{}'", escaped)
+    }
+
+    fn dump_select(&self, table: &str, columns: &[ColumnInfo], order_by: &[&str], limit: usize, offset: usize) -> String {
+        let select = columns
+            .iter()
+            .map(|c| {
+                if c.is_binary() {
+                    format!("HEX({})", self.quote_ident(&c.name))
+                } else {
+                    // dates, decimals and the like only decode as text
+                    format!("CAST({} AS CHAR)", self.quote_ident(&c.name))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut sql = format!("SELECT {} FROM {}", select, self.quote_ident(table));
+        if !order_by.is_empty() {
+            let order = order_by.iter().map(|c| self.quote_ident(c)).collect::<Vec<_>>().join(", ");
+            sql.push_str(&format!(" ORDER BY {}", order));
+        }
+        sql.push_str(&format!(" LIMIT {} OFFSET {}", limit, offset));
+        sql
+    }
+
+    fn dump_insert(&self, table: &str, columns: &[&str], rows: &[Vec<String>]) -> String {
+        let cols = columns.iter().map(|c| self.quote_ident(c)).collect::<Vec<_>>().join(", ");
+        let values = rows
+            .iter()
+            .map(|row| format!("({})", row.join(", ")))
+            .collect::<Vec<_>>()
+            .join(",\n");
+        format!("INSERT INTO {} ({}) VALUES\n{}", self.quote_ident(table), cols, values)
+    }
+
+    fn introspect_table(&self, table: &str) -> String {
+        format!(
+            "SELECT TABLE_COMMENT FROM information_schema.tables \
+             WHERE table_schema = DATABASE() AND table_name = {}",
+            self.quote_literal(table)
+        )
+    }
+
+    fn introspect_columns(&self, table: &str) -> String {
+        format!(
+            "SELECT COLUMN_NAME, COLUMN_TYPE, IS_NULLABLE = 'YES', COLUMN_DEFAULT, COLUMN_KEY = 'PRI', COLUMN_COMMENT \
+             FROM information_schema.columns \
+             WHERE table_schema = DATABASE() AND table_name = {} \
+             ORDER BY ORDINAL_POSITION",
+            self.quote_literal(table)
+        )
+    }
+
+    fn introspect_foreign_keys(&self, table: &str) -> String {
+        format!(
+            "SELECT COLUMN_NAME, REFERENCED_TABLE_NAME, REFERENCED_COLUMN_NAME \
+             FROM information_schema.key_column_usage \
+             WHERE table_schema = DATABASE() AND table_name = {} AND REFERENCED_TABLE_NAME IS NOT NULL \
+             ORDER BY CONSTRAINT_NAME, ORDINAL_POSITION",
+            self.quote_literal(table)
+        )
+    }
+
+    fn engine(&self) -> &'static str {
+        "MySQL"
+    }
+
+    fn server_version(&self) -> String {
+        "SELECT VERSION()".to_string()
+    }
+
+    fn database_size(&self) -> String {
+        "SELECT COALESCE(SUM(data_length + index_length), 0) FROM information_schema.tables \
+         WHERE table_schema = DATABASE()"
+            .to_string()
+    }
+
+    fn open_connections(&self) -> Option<String> {
+        Some("SELECT COUNT(*) FROM information_schema.processlist".to_string())
+    }
+
+    fn introspect_indexes(&self, table: &str) -> String {
+        format!(
+            "SELECT INDEX_NAME, COLUMN_NAME, NON_UNIQUE = 0 \
+             FROM information_schema.statistics \
+             WHERE table_schema = DATABASE() AND table_name = {} \
+             ORDER BY INDEX_NAME = 'PRIMARY' DESC, INDEX_NAME, SEQ_IN_INDEX",
+            self.quote_literal(table)
+        )
+    }
+
+    fn dump_migration_record(&self, table: &str, migration: &str, batch: i64, checksum: Option<&str>) -> String {
+        format!(
+            "INSERT INTO {} (`migration`, `batch`, `checksum`) VALUES ({}, {}, {})",
+            self.quote_ident(table),
+            self.quote_literal(migration),
+            batch,
+            checksum.map_or("NULL".to_string(), |c| self.quote_literal(c))
+        )
     }
 }
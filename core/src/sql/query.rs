@@ -1,5 +1,20 @@
+use std::future::Future;
 use std::marker::PhantomData;
-use crate::mvc::model::Model;
+use crate::sql::executor::QueryExecutor;
+use crate::facades::datetime::now_app;
+use crate::mvc::model::{Col, IntoColumn, Model};
+use crate::sql::database_client::DbError;
+use crate::sql::generator::SqlGenerator;
+use crate::sql::value::DbValue;
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use time::PrimitiveDateTime;
+
+/// Column filled on insert when the model uses timestamps.
+pub const CREATED_AT: &str = "created_at";
+/// Column filled on insert and update when the model uses timestamps.
+pub const UPDATED_AT: &str = "updated_at";
+
 pub struct QueryBuilder<M: Model> {
     select: SelectQuery,
     _marker: PhantomData<M>,
@@ -7,7 +22,6 @@ pub struct QueryBuilder<M: Model> {
 
 impl<M: Model> QueryBuilder<M> {
     pub fn all() -> Self {
-        println!("qb all called 2");
         QueryBuilder {
             select: SelectQuery {
                 table: M::table(),
@@ -21,6 +35,109 @@ impl<M: Model> QueryBuilder<M> {
     pub fn debug(self) -> SelectQuery {
         self.select
     }
+
+    /// Calls `callback` with `size` models at a time, in primary key order.
+    ///
+    /// Pages are read with `LIMIT` / `OFFSET`, so rows inserted or deleted in
+    /// between shift the pages; use `chunk_by_id` when the callback changes the table.
+    ///
+    /// # Examples
+    /// ```rust,ignore
+    /// Todo::all()
+    ///     .chunk(&schema, 500, |todos| async move {
+    ///         export(&todos).await?;
+    ///         Ok(())
+    ///     })
+    ///     .await?;
+    /// ```
+    pub async fn chunk<F, Fut>(self, db: &(impl QueryExecutor + ?Sized), size: u64, mut callback: F) -> Result<(), DbError>
+    where
+        F: FnMut(Vec<M>) -> Fut,
+        Fut: Future<Output = Result<(), DbError>>,
+    {
+        let size = size.max(1);
+        let table = db.table_name(self.select.table);
+        let mut offset = 0;
+        loop {
+            let sql = db.generator().select_ordered(
+                &table,
+                self.select.columns,
+                M::primary_key(),
+                false,
+                Some(size),
+                offset,
+            );
+            let rows = db.fetch_rows(&sql, &[]).await?;
+            let full = rows.len() as u64 == size;
+            if !rows.is_empty() {
+                callback(rows.iter().map(M::from_row).collect::<Result<_, _>>()?).await?;
+            }
+            if !full {
+                return Ok(());
+            }
+            offset += size;
+        }
+    }
+
+    /// Like `chunk`, but each page starts after the last primary key seen
+    /// (`WHERE key > ?`), so updating or deleting the chunked rows skips none.
+    pub async fn chunk_by_id<F, Fut>(self, db: &(impl QueryExecutor + ?Sized), size: u64, mut callback: F) -> Result<(), DbError>
+    where
+        F: FnMut(Vec<M>) -> Fut,
+        Fut: Future<Output = Result<(), DbError>>,
+    {
+        let size = size.max(1);
+        let table = db.table_name(self.select.table);
+        let mut after: Option<DbValue> = None;
+        loop {
+            let sql = db.generator().select_ordered(
+                &table,
+                self.select.columns,
+                M::primary_key(),
+                after.is_some(),
+                Some(size),
+                0,
+            );
+            let params: Vec<DbValue> = after.iter().cloned().collect();
+            let rows = db.fetch_rows(&sql, &params).await?;
+            let full = rows.len() as u64 == size;
+            after = rows.last().and_then(|row| row.get(M::primary_key())).cloned();
+            if !rows.is_empty() {
+                callback(rows.iter().map(M::from_row).collect::<Result<_, _>>()?).await?;
+            }
+            if !full || after.is_none() {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Every model, in primary key order, read one row at a time from a single query.
+    ///
+    /// Only a few rows are held at once however large the table is. The query keeps
+    /// a pooled connection until the stream is read to the end or dropped.
+    ///
+    /// # Examples
+    /// ```rust,ignore
+    /// let mut todos = Todo::all().cursor(&schema);
+    /// while let Some(todo) = todos.next().await {
+    ///     println!("{}", todo?.title);
+    /// }
+    /// ```
+    pub fn cursor(self, db: &(impl QueryExecutor + ?Sized)) -> BoxStream<'_, Result<M, DbError>> {
+        let table = db.table_name(self.select.table);
+        let sql = db.generator().select_ordered(
+            &table,
+            self.select.columns,
+            M::primary_key(),
+            false,
+            None,
+            0,
+        );
+        db
+            .fetch_stream(sql, Vec::new())
+            .map(|row| M::from_row(&row?))
+            .boxed()
+    }
 }
 
 
@@ -30,13 +147,281 @@ pub struct SelectQuery {
     pub columns: &'static [&'static str],
 }
 
+/// Builds an `INSERT` for a model table.
+///
+/// When `M::timestamps()` is true, `created_at` and `updated_at` are filled with
+/// the current time in `APP_TIMEZONE`, unless they were set explicitly or
+/// `without_timestamps()` was called.
+///
+/// # Examples
+/// ```rust,ignore
+/// Todo::insert()
+///     .set(col!(Todo::title), "write docs")
+///     .set(col!(Todo::done), false)
+///     .execute(&schema)
+///     .await?;
+/// ```
+pub struct InsertQuery<M: Model> {
+    values: Vec<(String, DbValue)>,
+    timestamps: bool,
+    _marker: PhantomData<M>,
+}
+
+impl<M: Model> InsertQuery<M> {
+    pub fn new() -> Self {
+        InsertQuery {
+            values: Vec::new(),
+            timestamps: M::timestamps(),
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn set(mut self, column: impl IntoColumn<M>, value: impl Into<DbValue>) -> Self {
+        self.values.push((column.into_column(), value.into()));
+        self
+    }
+
+    /// Skip automatic `created_at` / `updated_at` for this query only.
+    pub fn without_timestamps(mut self) -> Self {
+        self.timestamps = false;
+        self
+    }
+
+    /// Final column/value list, including automatic timestamps.
+    pub fn values(&self) -> Vec<(String, DbValue)> {
+        let mut values = self.values.clone();
+        if self.timestamps {
+            let now = now_app();
+            fill_if_missing(&mut values, CREATED_AT, now);
+            fill_if_missing(&mut values, UPDATED_AT, now);
+        }
+        values
+    }
+
+    pub fn to_sql(&self, generator: &dyn SqlGenerator, table: &str) -> (String, Vec<DbValue>) {
+        let values = self.values();
+        let columns: Vec<&str> = values.iter().map(|(c, _)| c.as_str()).collect();
+        let sql = generator.insert(table, &columns);
+        (sql, values.iter().map(|(_, v)| v.clone()).collect())
+    }
+
+    pub async fn execute(self, db: &(impl QueryExecutor + ?Sized)) -> Result<(), DbError> {
+        if self.values.is_empty() && !self.timestamps {
+            return Err(DbError::InvalidQuery("nothing to insert".to_string()));
+        }
+        let table = db.table_name(M::table());
+        let (sql, params) = self.to_sql(db.generator(), &table);
+        db.execute_params(&sql, &params).await
+    }
+
+    /// `execute()` returning the auto-increment key of the new row.
+    ///
+    /// # Examples
+    /// ```rust,ignore
+    /// let id = Todo::insert().set(col!(Todo::title), "write docs").execute_get_id(&schema).await?;
+    /// ```
+    pub async fn execute_get_id(self, db: &(impl QueryExecutor + ?Sized)) -> Result<i64, DbError> {
+        if self.values.is_empty() && !self.timestamps {
+            return Err(DbError::InvalidQuery("nothing to insert".to_string()));
+        }
+        let table = db.table_name(M::table());
+        let (sql, params) = self.to_sql(db.generator(), &table);
+        db.execute_insert(&sql, &params).await
+    }
+}
+
+impl<M: Model> Default for InsertQuery<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Builds an `UPDATE ... WHERE <primary key> = ?` for a single model row.
+///
+/// When `M::timestamps()` is true, `updated_at` is refreshed unless it was set
+/// explicitly or `without_timestamps()` was called.
+pub struct UpdateQuery<M: Model> {
+    key: DbValue,
+    values: Vec<(String, DbValue)>,
+    timestamps: bool,
+    _marker: PhantomData<M>,
+}
+
+impl<M: Model> UpdateQuery<M> {
+    pub fn new(key: impl Into<DbValue>) -> Self {
+        UpdateQuery {
+            key: key.into(),
+            values: Vec::new(),
+            timestamps: M::timestamps(),
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn set(mut self, column: impl IntoColumn<M>, value: impl Into<DbValue>) -> Self {
+        self.values.push((column.into_column(), value.into()));
+        self
+    }
+
+    /// Skip automatic `updated_at` for this query only.
+    pub fn without_timestamps(mut self) -> Self {
+        self.timestamps = false;
+        self
+    }
+
+    /// Final column/value list, including the automatic `updated_at`.
+    pub fn values(&self) -> Vec<(String, DbValue)> {
+        let mut values = self.values.clone();
+        if self.timestamps {
+            fill_if_missing(&mut values, UPDATED_AT, now_app());
+        }
+        values
+    }
+
+    pub fn to_sql(&self, generator: &dyn SqlGenerator, table: &str) -> (String, Vec<DbValue>) {
+        let values = self.values();
+        let columns: Vec<&str> = values.iter().map(|(c, _)| c.as_str()).collect();
+        let sql = generator.update(table, &columns, M::primary_key());
+        let mut params: Vec<DbValue> = values.iter().map(|(_, v)| v.clone()).collect();
+        params.push(self.key.clone());
+        (sql, params)
+    }
+
+    pub async fn execute(self, db: &(impl QueryExecutor + ?Sized)) -> Result<(), DbError> {
+        if self.values().is_empty() {
+            return Err(DbError::InvalidQuery("nothing to update".to_string()));
+        }
+        let table = db.table_name(M::table());
+        let (sql, params) = self.to_sql(db.generator(), &table);
+        db.execute_params(&sql, &params).await
+    }
+}
+
+/// Full-text search over the `#[searchable]` columns of a model, best matches first.
+///
+/// MySQL ranks with `MATCH ... AGAINST` in natural language mode, SQLite with the
+/// FTS5 `bm25()` score; the words of the query must all match on SQLite.
+/// The table needs a `table.fulltext(...)` index over the same columns.
+///
+/// # Examples
+/// ```rust,ignore
+/// let page = Todo::search("write docs").paginate(&schema, 1, 20).await?;
+/// for todo in &page.items {
+///     println!("{}", todo.title);
+/// }
+/// ```
+pub struct SearchQuery<M: Model> {
+    terms: String,
+    _marker: PhantomData<M>,
+}
+
+impl<M: Model> SearchQuery<M> {
+    pub fn new(terms: impl Into<String>) -> Self {
+        SearchQuery {
+            terms: terms.into(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// `(rows query, count query)` for one page, pages start at `1`.
+    pub fn to_sql(
+        &self,
+        generator: &dyn SqlGenerator,
+        table: &str,
+        page: u64,
+        per_page: u64,
+    ) -> ((String, Vec<DbValue>), (String, Vec<DbValue>)) {
+        let offset = page.saturating_sub(1) * per_page;
+        (
+            generator.search(table, M::primary_key(), M::columns(), M::searchable(), &self.terms, per_page, offset),
+            generator.search_count(table, M::searchable(), &self.terms),
+        )
+    }
+
+    /// One page of matches and the total number of matches.
+    pub async fn paginate(self, db: &(impl QueryExecutor + ?Sized), page: u64, per_page: u64) -> Result<Page<M>, DbError> {
+        if M::searchable().is_empty() {
+            return Err(DbError::InvalidQuery(format!(
+                "`{}` has no `#[searchable(fields = \"...\")]` columns",
+                M::table()
+            )));
+        }
+        let page = page.max(1);
+        let per_page = per_page.max(1);
+        let mut result = Page { items: Vec::new(), total: 0, page, per_page };
+        if self.terms.trim().is_empty() {
+            return Ok(result);
+        }
+
+        let table = db.table_name(M::table());
+        let ((sql, params), (count_sql, count_params)) = self.to_sql(db.generator(), &table, page, per_page);
+        result.total = db.fetch_count(&count_sql, &count_params).await?.max(0) as u64;
+        result.items = db
+            .fetch_rows(&sql, &params)
+            .await?
+            .iter()
+            .map(M::from_row)
+            .collect::<Result<_, _>>()?;
+        Ok(result)
+    }
+}
+
+/// One page of results and where it sits in the whole result.
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: u64,
+    /// Starts at `1`.
+    pub page: u64,
+    pub per_page: u64,
+}
+
+impl<T> Page<T> {
+    /// `1` for an empty result.
+    pub fn last_page(&self) -> u64 {
+        self.total.div_ceil(self.per_page).max(1)
+    }
+
+    pub fn has_more(&self) -> bool {
+        self.page < self.last_page()
+    }
+}
+
+fn fill_if_missing(values: &mut Vec<(String, DbValue)>, column: &str, now: PrimitiveDateTime) {
+    if !values.iter().any(|(c, _)| c == column) {
+        values.push((column.to_string(), DbValue::DateTime(now)));
+    }
+}
+
 pub trait QueryDsl: Model {
     fn all() -> QueryBuilder<Self>;
+    fn insert() -> InsertQuery<Self>;
+    fn update(key: impl Into<DbValue>) -> UpdateQuery<Self>;
+    /// Refresh only `updated_at` of the given row, even if the model doesn't opt in to timestamps.
+    fn touch(key: impl Into<DbValue>) -> UpdateQuery<Self>;
+    /// Full-text search over the `#[searchable]` columns, see `SearchQuery`.
+    fn search(terms: impl Into<String>) -> SearchQuery<Self>;
 }
 
 impl<T: Model> QueryDsl for T {
     fn all() -> QueryBuilder<Self> {
-        println!("qb all called 1");
         QueryBuilder::all()
     }
-}
\ No newline at end of file
+
+    fn insert() -> InsertQuery<Self> {
+        InsertQuery::new()
+    }
+
+    fn update(key: impl Into<DbValue>) -> UpdateQuery<Self> {
+        UpdateQuery::new(key)
+    }
+
+    fn touch(key: impl Into<DbValue>) -> UpdateQuery<Self> {
+        UpdateQuery::new(key)
+            .without_timestamps()
+            .set(Col::raw(UPDATED_AT), now_app())
+    }
+
+    fn search(terms: impl Into<String>) -> SearchQuery<Self> {
+        SearchQuery::new(terms)
+    }
+}
@@ -0,0 +1,230 @@
+//! Query logging: a `DatabaseClient` decorator that times every statement.
+//!
+//! `Schema` wraps its client in a [`LoggingClient`]. Each statement is handed to
+//! the listeners registered with `DB::listen`, written to the logger when
+//! `DB_LOG_QUERIES` is on (defaults to `APP_DEBUG`), and reported as a warning
+//! when it takes `DB_SLOW_QUERY_MS` or longer.
+
+use crate::logger;
+use crate::sql::database_client::{DatabaseClient, DbError, DbLock};
+use crate::sql::value::{DbRow, DbValue};
+use futures::stream::{self, BoxStream};
+use futures::StreamExt;
+use once_cell::sync::Lazy;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+/// One executed statement, as seen by `DB::listen` callbacks.
+#[derive(Debug, Clone)]
+pub struct QueryEvent {
+    pub sql: String,
+    pub bindings: Vec<DbValue>,
+    pub duration: Duration,
+    /// `mysql` or `sqlite`.
+    pub connection: &'static str,
+    /// Whether the database returned an error.
+    pub failed: bool,
+}
+
+type Listener = Arc<dyn Fn(&QueryEvent) + Send + Sync>;
+
+static LISTENERS: Lazy<RwLock<Vec<(usize, Listener)>>> = Lazy::new(|| RwLock::new(Vec::new()));
+static NEXT_LISTENER: AtomicUsize = AtomicUsize::new(1);
+
+/// Register `callback` for every statement of every `Schema`; returns the id `forget` takes.
+pub(crate) fn listen(callback: impl Fn(&QueryEvent) + Send + Sync + 'static) -> usize {
+    let id = NEXT_LISTENER.fetch_add(1, Ordering::Relaxed);
+    LISTENERS.write().unwrap().push((id, Arc::new(callback)));
+    id
+}
+
+pub(crate) fn forget(id: usize) {
+    LISTENERS.write().unwrap().retain(|(listener, _)| *listener != id);
+}
+
+fn has_listeners() -> bool {
+    !LISTENERS.read().unwrap().is_empty()
+}
+
+fn dispatch(event: &QueryEvent) {
+    // called outside the lock, a callback may listen or forget
+    let listeners: Vec<Listener> = LISTENERS.read().unwrap().iter().map(|(_, l)| l.clone()).collect();
+    for listener in listeners {
+        listener(event);
+    }
+}
+
+/// Times the statements of the wrapped client and reports them.
+#[derive(Debug)]
+pub struct LoggingClient {
+    inner: Box<dyn DatabaseClient + Send + Sync>,
+    connection: &'static str,
+    log_queries: bool,
+    slow_threshold: Option<Duration>,
+}
+
+impl LoggingClient {
+    pub fn new(
+        inner: Box<dyn DatabaseClient + Send + Sync>,
+        connection: &'static str,
+        log_queries: bool,
+        slow_threshold: Option<Duration>,
+    ) -> Self {
+        Self { inner, connection, log_queries, slow_threshold }
+    }
+
+    fn record<T>(&self, sql: &str, bindings: &[DbValue], started: Instant, result: &Result<T, DbError>) {
+        self.record_event(sql, bindings, started, result.is_err());
+    }
+
+    fn record_event(&self, sql: &str, bindings: &[DbValue], started: Instant, failed: bool) {
+        let duration = started.elapsed();
+        let slow = self.slow_threshold.is_some_and(|threshold| duration >= threshold);
+        if !self.log_queries && !slow && !has_listeners() {
+            return;
+        }
+
+        let event = QueryEvent {
+            sql: sql.trim().to_string(),
+            bindings: bindings.to_vec(),
+            duration,
+            connection: self.connection,
+            failed,
+        };
+        if slow || self.log_queries {
+            // one line per statement, the generators indent their SQL
+            let line = event.sql.split_whitespace().collect::<Vec<_>>().join(" ");
+            let ms = duration.as_secs_f64() * 1000.0;
+            if slow {
+                logger::warn(&format!(
+                    "Slow query on {} ({:.2}ms): {} {:?}",
+                    event.connection, ms, line, event.bindings
+                ));
+            } else {
+                logger::info(&format!("[{}] {:.2}ms {} {:?}", event.connection, ms, line, event.bindings));
+            }
+        }
+        dispatch(&event);
+    }
+}
+
+#[async_trait::async_trait]
+impl DatabaseClient for LoggingClient {
+    async fn execute(&self, sql: &str) -> Result<(), DbError> {
+        let started = Instant::now();
+        let result = self.inner.execute(sql).await;
+        self.record(sql, &[], started, &result);
+        result
+    }
+
+    async fn execute_params(&self, sql: &str, params: &[DbValue]) -> Result<(), DbError> {
+        let started = Instant::now();
+        let result = self.inner.execute_params(sql, params).await;
+        self.record(sql, params, started, &result);
+        result
+    }
+
+    async fn execute_insert(&self, sql: &str, params: &[DbValue]) -> Result<i64, DbError> {
+        let started = Instant::now();
+        let result = self.inner.execute_insert(sql, params).await;
+        self.record(sql, params, started, &result);
+        result
+    }
+
+    async fn fetch_strings(&self, sql: &str) -> Result<Vec<String>, DbError> {
+        let started = Instant::now();
+        let result = self.inner.fetch_strings(sql).await;
+        self.record(sql, &[], started, &result);
+        result
+    }
+
+    async fn fetch_strings_params(&self, sql: &str, params: &[DbValue]) -> Result<Vec<String>, DbError> {
+        let started = Instant::now();
+        let result = self.inner.fetch_strings_params(sql, params).await;
+        self.record(sql, params, started, &result);
+        result
+    }
+
+    async fn fetch_count_params(&self, sql: &str, params: &[DbValue]) -> Result<i64, DbError> {
+        let started = Instant::now();
+        let result = self.inner.fetch_count_params(sql, params).await;
+        self.record(sql, params, started, &result);
+        result
+    }
+
+    async fn fetch_numbers(&self, sql: &str) -> Result<Vec<i64>, DbError> {
+        let started = Instant::now();
+        let result = self.inner.fetch_numbers(sql).await;
+        self.record(sql, &[], started, &result);
+        result
+    }
+
+    async fn execute_batch(&self, statements: &[String]) -> Result<(), DbError> {
+        // one event for the batch, it runs as a unit on one connection
+        let started = Instant::now();
+        let result = self.inner.execute_batch(statements).await;
+        self.record(&statements.join(";\n"), &[], started, &result);
+        result
+    }
+
+    async fn fetch_rows(&self, sql: &str, params: &[DbValue]) -> Result<Vec<DbRow>, DbError> {
+        let started = Instant::now();
+        let result = self.inner.fetch_rows(sql, params).await;
+        self.record(sql, params, started, &result);
+        result
+    }
+
+    fn fetch_stream<'a>(&'a self, sql: String, params: Vec<DbValue>) -> BoxStream<'a, Result<DbRow, DbError>> {
+        // one event per statement, recorded once the stream is read to the end
+        let started = Instant::now();
+        let rows = self.inner.fetch_stream(sql.clone(), params.clone());
+        stream::unfold(Some((rows, sql, params)), move |state| async move {
+            let (mut rows, sql, params) = state?;
+            match rows.next().await {
+                Some(Ok(row)) => Some((Ok(row), Some((rows, sql, params)))),
+                Some(Err(e)) => {
+                    self.record_event(&sql, &params, started, true);
+                    Some((Err(e), None))
+                }
+                None => {
+                    self.record_event(&sql, &params, started, false);
+                    None
+                }
+            }
+        })
+        .boxed()
+    }
+
+    async fn acquire_lock(&self, name: &str, owner: &str, timeout: Duration, stale_after: Duration) -> Result<DbLock, DbError> {
+        // waiting for a lock is not a slow query
+        self.inner.acquire_lock(name, owner, timeout, stale_after).await
+    }
+
+    async fn begin(&self) -> Result<Box<dyn DatabaseClient + Send + Sync>, DbError> {
+        let started = Instant::now();
+        let result = self.inner.begin().await;
+        self.record("BEGIN", &[], started, &result);
+        // the statements of the transaction are reported like any other
+        Ok(Box::new(LoggingClient {
+            inner: result?,
+            connection: self.connection,
+            log_queries: self.log_queries,
+            slow_threshold: self.slow_threshold,
+        }))
+    }
+
+    async fn commit(&self) -> Result<(), DbError> {
+        let started = Instant::now();
+        let result = self.inner.commit().await;
+        self.record("COMMIT", &[], started, &result);
+        result
+    }
+
+    async fn rollback(&self) -> Result<(), DbError> {
+        let started = Instant::now();
+        let result = self.inner.rollback().await;
+        self.record("ROLLBACK", &[], started, &result);
+        result
+    }
+}
@@ -0,0 +1,100 @@
+//! SQL scripts, like `database/schema/*-schema.sql` and `db:dump` output.
+//!
+//! Neither driver runs several statements in one prepared query, so a script is
+//! split and executed statement by statement.
+
+/// What `Schema::dump_database()` writes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DumpMode {
+    /// Structure and rows
+    Full,
+    /// `DROP` / `CREATE` statements only
+    SchemaOnly,
+    /// `INSERT` statements only
+    DataOnly,
+}
+
+/// One script from `statements`, each ending in `;` and a blank line, under a `-- header` comment.
+pub fn join_statements(header: &str, statements: &[String]) -> String {
+    let mut script = format!("-- {}\n\n", header);
+    for statement in statements {
+        script.push_str(statement.trim().trim_end_matches(';'));
+        script.push_str(";\n\n");
+    }
+    script
+}
+
+/// Split `script` on `;`, ignoring those inside quotes, comments and
+/// `BEGIN ... END` / `CASE ... END` blocks (trigger bodies).
+///
+/// # Examples
+/// ```rust
+/// use rustavel_core::sql::script::split_statements;
+///
+/// let sql = "CREATE TABLE a (x TEXT DEFAULT ';');\n-- done;\nINSERT INTO a VALUES ('b');";
+/// assert_eq!(
+///     split_statements(sql),
+///     vec!["CREATE TABLE a (x TEXT DEFAULT ';')", "INSERT INTO a VALUES ('b')"]
+/// );
+/// ```
+pub fn split_statements(script: &str) -> Vec<String> {
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut word = String::new();
+    let mut depth = 0usize;
+    let mut chars = script.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c.is_alphanumeric() || c == '_' {
+            word.push(c);
+            current.push(c);
+            continue;
+        }
+        block_keyword(&mut word, &current, &mut depth);
+
+        match c {
+            '\'' | '"' | '`' => {
+                current.push(c);
+                // a doubled quote is an escaped quote, it just closes and reopens
+                for q in chars.by_ref() {
+                    current.push(q);
+                    if q == c {
+                        break;
+                    }
+                }
+            }
+            '-' if chars.peek() == Some(&'-') => {
+                for q in chars.by_ref() {
+                    if q == '\n' {
+                        current.push('\n');
+                        break;
+                    }
+                }
+            }
+            ';' if depth == 0 => push_statement(&mut statements, &mut current),
+            _ => current.push(c),
+        }
+    }
+    block_keyword(&mut word, &current, &mut depth);
+    push_statement(&mut statements, &mut current);
+    statements
+}
+
+fn block_keyword(word: &mut String, current: &str, depth: &mut usize) {
+    // `BEGIN;` opening a transaction is a statement of its own, not a block
+    let starts_statement = current.trim() == word.as_str();
+    if (word.eq_ignore_ascii_case("BEGIN") && !starts_statement) || word.eq_ignore_ascii_case("CASE") {
+        *depth += 1;
+    } else if word.eq_ignore_ascii_case("END") {
+        *depth = depth.saturating_sub(1);
+    }
+    word.clear();
+}
+
+fn push_statement(statements: &mut Vec<String>, current: &mut String) {
+    let statement = current.trim();
+    if !statement.is_empty() {
+        statements.push(statement.to_string());
+    }
+    current.clear();
+}
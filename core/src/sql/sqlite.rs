@@ -1,6 +1,9 @@
 use super::generator::SqlGenerator;
+use crate::db::introspection::ColumnInfo;
 use crate::logger;
-use crate::db::table::{Column, ColumnDataType, ColumnOption, DefaultValue, ForeignKey, TableAction};
+use crate::sql::database_client::LOCK_TABLE;
+use crate::sql::value::DbValue;
+use crate::db::table::{Column, ColumnDataType, ColumnOption, DefaultValue, ForeignKey, Table, TableAction};
 
 #[derive(Debug)]
 pub struct SqliteGenerator;
@@ -15,7 +18,7 @@ impl SqliteGenerator {
 
 impl SqlGenerator for SqliteGenerator {
     fn drop_table_if_exists(&self, table_name: &str) -> String {
-        format!("DROP TABLE IF EXISTS \"{}\";", table_name)
+        format!("DROP TABLE IF EXISTS {};", self.quote_ident(table_name))
     }
 
     fn get_tables(&self) -> String {
@@ -24,6 +27,7 @@ impl SqlGenerator for SqliteGenerator {
         FROM sqlite_master
         WHERE type = 'table'
           AND name NOT LIKE 'sqlite_%'
+          AND name NOT IN (SELECT name FROM pragma_table_list WHERE type = 'shadow')
         "
         .to_string()
     }
@@ -45,22 +49,22 @@ impl SqlGenerator for SqliteGenerator {
         JOIN pragma_table_info(m.name) p
         WHERE m.type = 'table'
           AND m.name NOT LIKE 'sqlite_%'
-          AND m.name = '{}'
+          AND m.name = {}
         ",
-            table_name
+            self.quote_literal(table_name)
         )
     }
 
 
     fn get_foreign_keys(&self, table_name: &str) -> String {
-        format!("PRAGMA foreign_key_list(\"{}\");", table_name)
+        format!("PRAGMA foreign_key_list({});", self.quote_ident(table_name))
     }
 
     fn drop_table(&self, table_name: &str) -> String {
-        format!("DROP TABLE \"{}\";", table_name)
+        format!("DROP TABLE {};", self.quote_ident(table_name))
     }
     fn drop_view(&self, view_name: &str) -> String {
-        format!("DROP VIEW \"{}\";", view_name)
+        format!("DROP VIEW {};", self.quote_ident(view_name))
     }
 
     // fn drop_all_tables(&self) -> String {
@@ -86,11 +90,12 @@ impl SqlGenerator for SqliteGenerator {
         format!(
             "
             SELECT \"1\"
-            FROM pragma_table_info(\"{}\")
-            WHERE name = '{}'
+            FROM pragma_table_info({})
+            WHERE name = {}
             LIMIT 1
             ",
-            table_name, column_name
+            self.quote_literal(table_name),
+            self.quote_literal(column_name)
         )
     }
 
@@ -100,10 +105,10 @@ impl SqlGenerator for SqliteGenerator {
             SELECT \"1\"
             FROM sqlite_master
             WHERE type = 'table'
-              AND name = '{}'
+              AND name = {}
             LIMIT 1
             ",
-            table_name
+            self.quote_literal(table_name)
         )
     }
 
@@ -113,17 +118,17 @@ impl SqlGenerator for SqliteGenerator {
             SELECT \"1\"
             FROM sqlite_master
             WHERE type = 'view'
-              AND name = '{}'
+              AND name = {}
             LIMIT 1
             ",
-            table_name
+            self.quote_literal(table_name)
         )
     }
 
     fn has_index(&self, table_name: &str, columns_name: Vec<&str>) -> String {
         let cols = columns_name
             .iter()
-            .map(|c| format!("'{}'", c))
+            .map(|c| self.quote_literal(c))
             .collect::<Vec<_>>()
             .join(",");
 
@@ -132,14 +137,16 @@ impl SqlGenerator for SqliteGenerator {
         format!(
             "
             SELECT \"1\"
-            FROM pragma_index_list('{}') il
+            FROM pragma_index_list({}) il
             JOIN pragma_index_info(il.name) ii
             WHERE ii.name IN ({})
             GROUP BY il.name
             HAVING COUNT(*) = {}
             LIMIT 1
             ",
-            table_name, cols, count
+            self.quote_literal(table_name),
+            cols,
+            count
         )
     }
 
@@ -161,8 +168,9 @@ impl SqlGenerator for SqliteGenerator {
 
     fn rename(&self, old_table_name: &str, new_table_name: &str) -> String {
         format!(
-            "ALTER TABLE \"{}\" RENAME TO \"{}\";",
-            old_table_name, new_table_name
+            "ALTER TABLE {} RENAME TO {};",
+            self.quote_ident(old_table_name),
+            self.quote_ident(new_table_name)
         )
     }
 
@@ -178,9 +186,10 @@ impl SqlGenerator for SqliteGenerator {
 
         let nullable = if column.nullable { "" } else { "NOT NULL" };
 
+        let name = self.quote_ident(&column.name);
         let def = match &column.default {
             DefaultValue::Null => "",
-            DefaultValue::String(val) => &format!("DEFAULT '{}'", val),
+            DefaultValue::String(val) => &format!("DEFAULT {}", self.quote_literal(val)),
             DefaultValue::JsonArray => "DEFAULT '[]'",
             DefaultValue::CurrenTimestamp => "DEFAULT CURRENT_TIMESTAMP",
             DefaultValue::Bool(v) => {
@@ -196,12 +205,12 @@ impl SqlGenerator for SqliteGenerator {
 
         match column.data_type {
             ColumnDataType::DTId => {
-                column_sql = "`id` integer NOT NULL".to_string();
-                footer_sql = "PRIMARY KEY(`id` AUTOINCREMENT)".to_string();
+                column_sql = "\"id\" integer NOT NULL".to_string();
+                footer_sql = "PRIMARY KEY(\"id\" AUTOINCREMENT)".to_string();
             }
 
             ColumnDataType::DTBoolean => {
-                column_sql = format!("`{}` boolean {} {}", column.name, nullable, def);
+                column_sql = format!("{} boolean {} {}", name, nullable, def);
             }
 
             ColumnDataType::DTTinyInteger
@@ -209,11 +218,11 @@ impl SqlGenerator for SqliteGenerator {
             | ColumnDataType::DTSmallInteger
             | ColumnDataType::DTMediumInteger
             | ColumnDataType::DTBigInteger => {
-                column_sql = format!("`{}` integer {} {}", column.name, nullable, def);
+                column_sql = format!("{} integer {} {}", name, nullable, def);
             }
 
             ColumnDataType::DTFloat | ColumnDataType::DTDouble => {
-                column_sql = format!("`{}` float {} {}", column.name, nullable, def);
+                column_sql = format!("{} float {} {}", name, nullable, def);
             }
 
             ColumnDataType::DTDecimal => {
@@ -222,8 +231,8 @@ impl SqlGenerator for SqliteGenerator {
                     _ => (&20, &6),
                 };
                 column_sql = format!(
-                    "`{}` decimal({},{}) {} {}",
-                    column.name, p, s, nullable, def
+                    "{} decimal({},{}) {} {}",
+                    name, p, s, nullable, def
                 );
             }
 
@@ -232,20 +241,20 @@ impl SqlGenerator for SqliteGenerator {
                     ColumnOption::Length(l) => l,
                     _ => 255,
                 };
-                column_sql = format!("`{}` varchar({}) {} {}", column.name, len, nullable, def);
+                column_sql = format!("{} varchar({}) {} {}", name, len, nullable, def);
             }
 
             ColumnDataType::DTText
             | ColumnDataType::DTTinyText
             | ColumnDataType::DTMediumText
             | ColumnDataType::DTLongText => {
-                column_sql = format!("`{}` text {} {}", column.name, nullable, def);
+                column_sql = format!("{} text {} {}", name, nullable, def);
             }
 
             ColumnDataType::DTJson => {
                 column_sql = format!(
-                    "`{}` json {} {} CHECK(json_valid(`{}`))",
-                    column.name, nullable, def, column.name
+                    "{} json {} {} CHECK(json_valid({}))",
+                    name, nullable, def, name
                 );
             }
 
@@ -253,43 +262,48 @@ impl SqlGenerator for SqliteGenerator {
             | ColumnDataType::DTDateTime
             | ColumnDataType::DTTime
             | ColumnDataType::DTTimestamp => {
-                column_sql = format!("`{}` datetime {} {}", column.name, nullable, def);
+                column_sql = format!("{} datetime {} {}", name, nullable, def);
             }
 
             ColumnDataType::DTTimestamps => {
-                column_sql = "`created_at` datetime, `updated_at` datetime".to_string();
+                column_sql = "\"created_at\" datetime, \"updated_at\" datetime".to_string();
             }
 
             ColumnDataType::DTSoftDelete => {
-                column_sql = "`deleted_at` datetime".to_string();
+                column_sql = "\"deleted_at\" datetime".to_string();
             }
 
             ColumnDataType::DTEnum | ColumnDataType::DTSet => {
                 let values = match &column.option {
                     ColumnOption::Values(items) => items
                         .iter()
-                        .map(|v| format!("'{}'", v))
+                        .map(|v| self.quote_literal(v))
                         .collect::<Vec<_>>()
                         .join(", "),
                     _ => String::new(),
                 };
 
                 column_sql = format!(
-                    "`{}` varchar {} {} CHECK(`{}` IN ({}))",
-                    column.name, nullable, def, column.name, values
+                    "{} varchar {} {} CHECK({} IN ({}))",
+                    name, nullable, def, name, values
                 );
             }
 
             ColumnDataType::DTMorph => {
+                let morph_type = self.quote_ident(&format!("{}_type", column.name));
+                let morph_id = self.quote_ident(&format!("{}_id", column.name));
                 column_sql = format!(
-                    "`{}_type` varchar(255) {} {}, `{}`_id integer {} {}",
-                    column.name, nullable, def, column.name, nullable, def
+                    "{} varchar(255) {} {}, {} integer {} {}",
+                    morph_type, nullable, def, morph_id, nullable, def
                 );
 
                 post_sql = format!(
-                    "CREATE INDEX `morph_{}_type_{}_id_index`
-                 ON `{}` (`{}_type`, `{}_id`)",
-                    column.name, column.name, table_name, column.name, column.name
+                    "CREATE INDEX {}
+                 ON {} ({}, {})",
+                    self.quote_ident(&format!("morph_{}_type_{}_id_index", column.name, column.name)),
+                    self.quote_ident(table_name),
+                    morph_type,
+                    morph_id
                 );
             }
 
@@ -298,15 +312,19 @@ impl SqlGenerator for SqliteGenerator {
 
         if column.unique {
             post_sql = format!(
-                "CREATE UNIQUE INDEX `{}_{}_unique`
-             ON `{}` (`{}`)",
-                table_name, column.name, table_name, column.name
+                "CREATE UNIQUE INDEX {}
+             ON {} ({})",
+                self.quote_ident(&format!("{}_{}_unique", table_name, column.name)),
+                self.quote_ident(table_name),
+                name
             );
         } else if column.index {
             post_sql = format!(
-                "CREATE INDEX `{}_{}_index`
-             ON `{}` (`{}`)",
-                table_name, column.name, table_name, column.name
+                "CREATE INDEX {}
+             ON {} ({})",
+                self.quote_ident(&format!("{}_{}_index", table_name, column.name)),
+                self.quote_ident(table_name),
+                name
             );
         }
 
@@ -335,8 +353,12 @@ impl SqlGenerator for SqliteGenerator {
         };
 
         format!(
-            "FOREIGN KEY(\"{}\") REFERENCES \"{}\"(\"{}\") {} {} ",
-            key.column_name, key.foreign_table, key.column_name, update, delete
+            "FOREIGN KEY({}) REFERENCES {}({}) {} {} ",
+            self.quote_ident(&key.column_name),
+            self.quote_ident(&key.foreign_table),
+            self.quote_ident(&key.referenced_column),
+            update,
+            delete
         )
     }
     fn drop_column(&self, column_name: &str) -> String {
@@ -346,59 +368,365 @@ impl SqlGenerator for SqliteGenerator {
         )
     }
 
-    fn table_sql(
-        &self,
-        table_name: &str,
-        body_sql: &str,
-        post_sql: &str,
-        action: &TableAction,
-    ) -> String {
-        match action {
+    fn table_sql(&self, table: &Table, body_sql: &str, post_sql: &str) -> String {
+        match table.action {
             TableAction::Create => {
+                let mut post_sql = post_sql.to_string();
+                // `id()` is AUTOINCREMENT, which continues after the highest `seq`; without it
+                // there is no `sqlite_sequence` row to start from, and maybe no such table
+                let has_id = table.columns.iter().any(|c| matches!(c.data_type, ColumnDataType::DTId));
+                if let Some(start) = table.auto_increment.filter(|start| *start > 1 && has_id) {
+                    // a temporary table counts in the `temp` schema's own sequence table
+                    post_sql = format!(
+                        "INSERT INTO {}sqlite_sequence (name, seq) VALUES ({}, {});\n {}",
+                        if table.temporary { "temp." } else { "" },
+                        self.quote_literal(&table.name),
+                        start - 1,
+                        post_sql
+                    );
+                }
+                // engine, charset and collation are MySQL table options
                 format!(
-                    "CREATE TABLE `{}` ( {} ) \n ;\n {}",
-                    table_name, body_sql, post_sql
+                    "CREATE {}TABLE {} ( {} ) \n ;\n {}",
+                    if table.temporary { "TEMPORARY " } else { "" },
+                    self.quote_ident(&table.name),
+                    body_sql,
+                    post_sql
                 )
             }
+            // e.g. only a full-text index was added
+            TableAction::Alter if body_sql.is_empty() => post_sql.to_string(),
             TableAction::Alter => {
                 format!(
-                    "ALTER TABLE `{} \n {} ; \n {}`",
-                    table_name, body_sql, post_sql
+                    "ALTER TABLE {} \n {} ; \n {}",
+                    self.quote_ident(&table.name),
+                    body_sql,
+                    post_sql
                 )
             }
             _ => "".to_string(),
         }
     }
 
+    fn get_ran(&self, table: &str) -> String {
+        format!("SELECT migration FROM {}", self.quote_ident(table))
+    }
 
+    fn get_ran_gt(&self, table: &str) -> String {
+        format!(
+            "SELECT migration FROM {} WHERE batch > ? ORDER BY batch DESC, id DESC",
+            self.quote_ident(table)
+        )
+    }
 
-    fn get_ran(&self) -> String {
-        "SELECT migration FROM migrations".to_string()
+    fn get_last_ran(&self, table: &str, count: u64) -> String {
+        format!(
+            "SELECT migration FROM {} ORDER BY batch DESC, id DESC LIMIT {}",
+            self.quote_ident(table),
+            count
+        )
     }
 
-    fn get_ran_gt(&self) -> String {
-        "SELECT migration FROM migrations WHERE batch > 0".to_string()
+
+    fn get_next_batch_number(&self, table: &str) -> String {
+        format!("SELECT MAX(batch) AS batch FROM {}", self.quote_ident(table))
     }
 
+    fn add_migrated_table(&self, table: &str) -> String {
+        format!("INSERT INTO {} (migration, batch) VALUES (?, ?)", self.quote_ident(table))
+    }
 
-    fn get_next_batch_number(&self) -> String {
-        "SELECT MAX(batch) AS batch FROM migrations".to_string()
+    fn rem_migrated_table(&self, table: &str) -> String {
+        format!("DELETE FROM {} WHERE migration = ?", self.quote_ident(table))
     }
 
-    fn add_migrated_table(&self) -> String {
-        "INSERT INTO migrations (migration, batch) VALUES (?, ?)".to_string()
+    fn set_migration_checksum(&self, table: &str) -> String {
+        format!("UPDATE {} SET checksum = ? WHERE migration = ?", self.quote_ident(table))
     }
 
-    fn rem_migrated_table(&self) -> String {
-        "DELETE FROM migrations WHERE migration = ?".to_string()
+    fn get_migration_records(&self, table: &str, checksum: bool) -> String {
+        format!(
+            "SELECT migration, batch, {} FROM {} ORDER BY batch, id",
+            if checksum { "checksum" } else { "NULL AS checksum" },
+            self.quote_ident(table)
+        )
     }
 
     fn record_exists(&self, table: &str, column: &str) -> String {
-        format!("SELECT COUNT(*) AS 'count' FROM '{}' WHERE '{}' = ?", table, column)
+        format!(
+            "SELECT COUNT(*) AS \"count\" FROM {} WHERE {} = ?",
+            self.quote_ident(table),
+            self.quote_ident(column)
+        )
     }
 
     fn record_exists_except(&self,table: &str,column: &str, except: &str) -> String{
-        format!("SELECT COUNT(*) AS 'count' FROM '{}' WHERE '{}' = ? AND '{}' <> ?", table, column,except)
+        format!(
+            "SELECT COUNT(*) AS \"count\" FROM {} WHERE {} = ? AND {} <> ?",
+            self.quote_ident(table),
+            self.quote_ident(column),
+            self.quote_ident(except)
+        )
+    }
+
+    fn insert(&self, table: &str, columns: &[&str]) -> String {
+        let cols = columns
+            .iter()
+            .map(|c| self.quote_ident(c))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let placeholders = vec!["?"; columns.len()].join(", ");
+        format!("INSERT INTO {} ({}) VALUES ({})", self.quote_ident(table), cols, placeholders)
+    }
+
+    fn update(&self, table: &str, columns: &[&str], key_column: &str) -> String {
+        let sets = columns
+            .iter()
+            .map(|c| format!("{} = ?", self.quote_ident(c)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "UPDATE {} SET {} WHERE {} = ?",
+            self.quote_ident(table),
+            sets,
+            self.quote_ident(key_column)
+        )
+    }
+
+    fn count_where(&self, table: &str, columns: &[&str]) -> String {
+        let mut sql = format!("SELECT COUNT(*) AS \"count\" FROM {}", self.quote_ident(table));
+        if !columns.is_empty() {
+            let wheres = columns
+                .iter()
+                .map(|c| format!("{} = ?", self.quote_ident(c)))
+                .collect::<Vec<_>>()
+                .join(" AND ");
+            sql.push_str(&format!(" WHERE {}", wheres));
+        }
+        sql
+    }
+
+    fn truncate(&self, table: &str) -> String {
+        // no TRUNCATE in SQLite, a DELETE without WHERE gets the truncate optimization
+        format!("DELETE FROM {};", self.quote_ident(table))
+    }
+
+    fn fulltext(&self, table_name: &str, key_column: &str, columns: &[String], _action: &TableAction) -> (String, String) {
+        let fts = self.quote_ident(&fts_table(table_name));
+        let table = self.quote_ident(table_name);
+        let key = self.quote_ident(key_column);
+        let names = columns.iter().map(|c| self.quote_ident(c)).collect::<Vec<_>>();
+        let list = names.join(", ");
+        let new = names.iter().map(|c| format!("new.{}", c)).collect::<Vec<_>>().join(", ");
+        let old = names.iter().map(|c| format!("old.{}", c)).collect::<Vec<_>>().join(", ");
+        let trigger = |event: &str| self.quote_ident(&format!("{}_{}", fts_table(table_name), event));
+
+        // external content: the index stores no copy of the text, the triggers keep it in sync
+        let post = format!(
+            "CREATE VIRTUAL TABLE {fts} USING fts5({list}, content={content}, content_rowid={rowid});
+CREATE TRIGGER {ai} AFTER INSERT ON {table} BEGIN
+  INSERT INTO {fts}(rowid, {list}) VALUES (new.{key}, {new});
+END;
+CREATE TRIGGER {ad} AFTER DELETE ON {table} BEGIN
+  INSERT INTO {fts}({fts}, rowid, {list}) VALUES ('delete', old.{key}, {old});
+END;
+CREATE TRIGGER {au} AFTER UPDATE ON {table} BEGIN
+  INSERT INTO {fts}({fts}, rowid, {list}) VALUES ('delete', old.{key}, {old});
+  INSERT INTO {fts}(rowid, {list}) VALUES (new.{key}, {new});
+END;
+INSERT INTO {fts}({fts}) VALUES ('rebuild')",
+            content = self.quote_literal(table_name),
+            rowid = self.quote_literal(key_column),
+            ai = trigger("insert"),
+            ad = trigger("delete"),
+            au = trigger("update"),
+        );
+        (String::new(), post)
+    }
+
+    fn fulltext_table(&self, table: &str) -> Option<String> {
+        Some(fts_table(table))
+    }
+
+    fn drop_fulltext(&self, table_name: &str) -> String {
+        let fts = fts_table(table_name);
+        format!(
+            "DROP TRIGGER IF EXISTS {}; DROP TRIGGER IF EXISTS {}; DROP TRIGGER IF EXISTS {}; DROP TABLE IF EXISTS {};",
+            self.quote_ident(&format!("{}_insert", fts)),
+            self.quote_ident(&format!("{}_delete", fts)),
+            self.quote_ident(&format!("{}_update", fts)),
+            self.quote_ident(&fts)
+        )
+    }
+
+    fn search(
+        &self,
+        table: &str,
+        key_column: &str,
+        columns: &[&str],
+        _searchable: &[&str],
+        terms: &str,
+        limit: u64,
+        offset: u64,
+    ) -> (String, Vec<DbValue>) {
+        let fts = self.quote_ident(&fts_table(table));
+        let source = self.quote_ident(table);
+        let select = columns
+            .iter()
+            .map(|c| format!("{}.{}", source, self.quote_ident(c)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        // bm25() is lower for better matches
+        let sql = format!(
+            "SELECT {select}, -bm25({fts}) AS \"score\" FROM {fts} JOIN {source} ON {source}.{key} = {fts}.rowid \
+             WHERE {fts} MATCH ? ORDER BY \"score\" DESC LIMIT {limit} OFFSET {offset}",
+            key = self.quote_ident(key_column),
+        );
+        (sql, vec![DbValue::Text(fts5_query(terms))])
+    }
+
+    fn search_count(&self, table: &str, _searchable: &[&str], terms: &str) -> (String, Vec<DbValue>) {
+        let fts = self.quote_ident(&fts_table(table));
+        (
+            format!("SELECT COUNT(*) FROM {} WHERE {} MATCH ?", fts, fts),
+            vec![DbValue::Text(fts5_query(terms))],
+        )
+    }
+
+    fn dump_structure(&self, tables: &[String], views: &[String]) -> Vec<String> {
+        if tables.is_empty() && views.is_empty() {
+            return vec![];
+        }
+        let names = tables
+            .iter()
+            .chain(views)
+            .map(|name| self.quote_literal(name))
+            .collect::<Vec<_>>()
+            .join(", ");
+        // tables before the indexes, triggers and views that depend on them
+        vec![format!(
+            "SELECT name, sql FROM {} \
+             WHERE sql IS NOT NULL AND name NOT LIKE 'sqlite_%' AND name <> {} AND tbl_name IN ({}) \
+             ORDER BY CASE type WHEN 'table' THEN 0 WHEN 'index' THEN 1 ELSE 2 END, rowid",
+            self.master(),
+            self.quote_literal(LOCK_TABLE),
+            names
+        )]
+    }
+
+    fn drop_view_if_exists(&self, view_name: &str) -> String {
+        format!("DROP VIEW IF EXISTS {};", self.quote_ident(view_name))
+    }
+
+    fn quote_ident(&self, name: &str) -> String {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    }
+
+    fn quote_literal(&self, value: &str) -> String {
+        format!("'{}'", value.replace('\'', "''"))
+    }
+
+    fn dump_select(&self, table: &str, columns: &[ColumnInfo], order_by: &[&str], limit: usize, offset: usize) -> String {
+        let select = columns
+            .iter()
+            .map(|c| {
+                if c.is_binary() {
+                    // hex(NULL) is '', keep NULL apart from an empty blob
+                    format!("CASE WHEN {0} IS NULL THEN NULL ELSE hex({0}) END", self.quote_ident(&c.name))
+                } else {
+                    self.quote_ident(&c.name)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut sql = format!("SELECT {} FROM {}", select, self.quote_ident(table));
+        if !order_by.is_empty() {
+            let order = order_by.iter().map(|c| self.quote_ident(c)).collect::<Vec<_>>().join(", ");
+            sql.push_str(&format!(" ORDER BY {}", order));
+        }
+        sql.push_str(&format!(" LIMIT {} OFFSET {}", limit, offset));
+        sql
+    }
+
+    fn dump_insert(&self, table: &str, columns: &[&str], rows: &[Vec<String>]) -> String {
+        let cols = columns.iter().map(|c| self.quote_ident(c)).collect::<Vec<_>>().join(", ");
+        let values = rows
+            .iter()
+            .map(|row| format!("({})", row.join(", ")))
+            .collect::<Vec<_>>()
+            .join(",\n");
+        format!("INSERT INTO {} ({}) VALUES\n{}", self.quote_ident(table), cols, values)
+    }
+
+    fn introspect_table(&self, _table: &str) -> String {
+        // SQLite keeps no comments
+        "SELECT ''".to_string()
+    }
+
+    fn introspect_columns(&self, table: &str) -> String {
+        format!(
+            "SELECT name, type, \"notnull\" = 0, dflt_value, pk > 0, '' \
+             FROM pragma_table_info({}) ORDER BY cid",
+            self.quote_literal(table)
+        )
     }
 
+    fn introspect_foreign_keys(&self, table: &str) -> String {
+        // `to` is NULL when the key points at the primary key implicitly
+        format!(
+            "SELECT \"from\", \"table\", COALESCE(\"to\", 'id') \
+             FROM pragma_foreign_key_list({}) ORDER BY id, seq",
+            self.quote_literal(table)
+        )
+    }
+
+    fn engine(&self) -> &'static str {
+        "SQLite"
+    }
+
+    fn server_version(&self) -> String {
+        "SELECT sqlite_version()".to_string()
+    }
+
+    fn database_size(&self) -> String {
+        "SELECT page_count * page_size FROM pragma_page_count(), pragma_page_size()".to_string()
+    }
+
+    fn open_connections(&self) -> Option<String> {
+        None
+    }
+
+    fn introspect_indexes(&self, table: &str) -> String {
+        // an INTEGER PRIMARY KEY is the rowid and has no index of its own
+        format!(
+            "SELECT il.name, ii.name, il.\"unique\" \
+             FROM pragma_index_list({}) il JOIN pragma_index_info(il.name) ii \
+             ORDER BY il.name, ii.seqno",
+            self.quote_literal(table)
+        )
+    }
+
+    fn dump_migration_record(&self, table: &str, migration: &str, batch: i64, checksum: Option<&str>) -> String {
+        format!(
+            "INSERT INTO {} (\"migration\", \"batch\", \"checksum\") VALUES ({}, {}, {})",
+            self.quote_ident(table),
+            self.quote_literal(migration),
+            batch,
+            checksum.map_or("NULL".to_string(), |c| self.quote_literal(c))
+        )
+    }
+}
+
+/// FTS5 table holding the full-text index of `table`.
+fn fts_table(table: &str) -> String {
+    format!("{}_fts", table)
+}
+
+/// Every word as a quoted FTS5 string, so user input can't use the query syntax; all words must match.
+fn fts5_query(terms: &str) -> String {
+    terms
+        .split_whitespace()
+        .map(|word| format!("\"{}\"", word.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" ")
 }
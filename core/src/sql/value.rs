@@ -0,0 +1,401 @@
+//! Typed values going into and coming out of the database.
+//!
+//! Every `DatabaseClient` method takes its bind parameters as [`DbValue`]s, and
+//! `fetch_rows` returns [`DbRow`]s of them, decoded from the column's SQL type.
+//! `#[derive(Model)]` uses [`ToSqlValue`] to build `Model::to_row()` and
+//! [`FromSqlValue`] to build `Model::from_row()`, so every non-skipped field
+//! type has to implement both.
+//!
+//! # Examples
+//! ```rust
+//! use rustavel_core::sql::value::DbValue;
+//!
+//! assert_eq!(DbValue::from(42), DbValue::I64(42));
+//! assert_eq!(DbValue::from(Some("x")), DbValue::Text("x".to_string()));
+//! assert_eq!(DbValue::from(None::<i32>), DbValue::Null);
+//! assert_eq!(DbValue::from(true).as_text().as_deref(), Some("1"));
+//! ```
+
+use std::fmt;
+use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
+use crate::facades::datetime::{format_hms, format_ymd, format_ymd_hms, parse_ymd, parse_ymd_hms};
+use crate::sql::database_client::DbError;
+
+/// One SQL value.
+///
+/// Dates and times are bound as `YYYY-MM-DD HH:MM:SS` text, which MySQL and
+/// SQLite both store and compare the same way.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DbValue {
+    Null,
+    Bool(bool),
+    I64(i64),
+    F64(f64),
+    Text(String),
+    Bytes(Vec<u8>),
+    Date(Date),
+    Time(Time),
+    DateTime(PrimitiveDateTime),
+}
+
+impl DbValue {
+    pub fn is_null(&self) -> bool {
+        matches!(self, DbValue::Null)
+    }
+
+    /// The value as the database would store it in a text column, `None` for `NULL`.
+    ///
+    /// Booleans become `1` / `0`, bytes are read as (lossy) UTF-8.
+    pub fn as_text(&self) -> Option<String> {
+        match self {
+            DbValue::Null => None,
+            DbValue::Bool(b) => Some(if *b { "1" } else { "0" }.to_string()),
+            DbValue::I64(v) => Some(v.to_string()),
+            DbValue::F64(v) => Some(v.to_string()),
+            DbValue::Text(s) => Some(s.clone()),
+            DbValue::Bytes(b) => Some(String::from_utf8_lossy(b).into_owned()),
+            DbValue::Date(d) => Some(format_ymd(d)),
+            DbValue::Time(t) => Some(format_hms(t)),
+            DbValue::DateTime(dt) => Some(format_ymd_hms(dt)),
+        }
+    }
+
+    /// Integers, booleans and numeric text; `None` for anything else.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            DbValue::Bool(b) => Some(*b as i64),
+            DbValue::I64(v) => Some(*v),
+            DbValue::Text(s) => s.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            DbValue::I64(v) => Some(*v as f64),
+            DbValue::F64(v) => Some(*v),
+            DbValue::Text(s) => s.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// `true` for booleans, non-zero numbers and `"1"` / `"true"` text.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            DbValue::Bool(b) => Some(*b),
+            DbValue::I64(v) => Some(*v != 0),
+            DbValue::F64(v) => Some(*v != 0.0),
+            DbValue::Text(s) => match s.trim().to_lowercase().as_str() {
+                "1" | "true" => Some(true),
+                "0" | "false" | "" => Some(false),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Same text as `as_text()`, `NULL` for `Null`.
+impl fmt::Display for DbValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.as_text() {
+            Some(text) => f.write_str(&text),
+            None => f.write_str("NULL"),
+        }
+    }
+}
+
+macro_rules! int_db_value {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl From<$ty> for DbValue {
+                fn from(value: $ty) -> Self {
+                    DbValue::I64(value as i64)
+                }
+            }
+        )*
+    };
+}
+
+int_db_value!(i8, i16, i32, i64, isize, u8, u16, u32);
+
+/// Wider than `i64`: kept exact as text when it doesn't fit.
+macro_rules! wide_int_db_value {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl From<$ty> for DbValue {
+                fn from(value: $ty) -> Self {
+                    i64::try_from(value).map_or_else(|_| DbValue::Text(value.to_string()), DbValue::I64)
+                }
+            }
+        )*
+    };
+}
+
+wide_int_db_value!(u64, usize, i128, u128);
+
+impl From<f32> for DbValue {
+    fn from(value: f32) -> Self {
+        DbValue::F64(value as f64)
+    }
+}
+
+impl From<f64> for DbValue {
+    fn from(value: f64) -> Self {
+        DbValue::F64(value)
+    }
+}
+
+impl From<bool> for DbValue {
+    fn from(value: bool) -> Self {
+        DbValue::Bool(value)
+    }
+}
+
+impl From<char> for DbValue {
+    fn from(value: char) -> Self {
+        DbValue::Text(value.to_string())
+    }
+}
+
+impl From<String> for DbValue {
+    fn from(value: String) -> Self {
+        DbValue::Text(value)
+    }
+}
+
+impl From<&String> for DbValue {
+    fn from(value: &String) -> Self {
+        DbValue::Text(value.clone())
+    }
+}
+
+impl From<&str> for DbValue {
+    fn from(value: &str) -> Self {
+        DbValue::Text(value.to_string())
+    }
+}
+
+impl From<Vec<u8>> for DbValue {
+    fn from(value: Vec<u8>) -> Self {
+        DbValue::Bytes(value)
+    }
+}
+
+impl From<&[u8]> for DbValue {
+    fn from(value: &[u8]) -> Self {
+        DbValue::Bytes(value.to_vec())
+    }
+}
+
+impl From<Date> for DbValue {
+    fn from(value: Date) -> Self {
+        DbValue::Date(value)
+    }
+}
+
+impl From<Time> for DbValue {
+    fn from(value: Time) -> Self {
+        DbValue::Time(value)
+    }
+}
+
+impl From<PrimitiveDateTime> for DbValue {
+    fn from(value: PrimitiveDateTime) -> Self {
+        DbValue::DateTime(value)
+    }
+}
+
+/// Stored as UTC, same as the `datetime` facade helpers.
+impl From<OffsetDateTime> for DbValue {
+    fn from(value: OffsetDateTime) -> Self {
+        let utc = value.to_offset(time::UtcOffset::UTC);
+        DbValue::DateTime(PrimitiveDateTime::new(utc.date(), utc.time()))
+    }
+}
+
+impl<T: Into<DbValue>> From<Option<T>> for DbValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(DbValue::Null, Into::into)
+    }
+}
+
+/// Conversion of a model field into its bind value.
+pub trait ToSqlValue {
+    fn to_sql_value(&self) -> DbValue;
+}
+
+impl<T: Clone + Into<DbValue>> ToSqlValue for T {
+    fn to_sql_value(&self) -> DbValue {
+        self.clone().into()
+    }
+}
+
+impl ToSqlValue for str {
+    fn to_sql_value(&self) -> DbValue {
+        DbValue::Text(self.to_string())
+    }
+}
+
+/// Conversion of a fetched value into a model field, used by `Model::from_row()`.
+///
+/// `None` when the value doesn't fit the type, e.g. `NULL` for a non-`Option` field.
+pub trait FromSqlValue: Sized {
+    fn from_sql_value(value: &DbValue) -> Option<Self>;
+}
+
+macro_rules! int_from_sql_value {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl FromSqlValue for $ty {
+                fn from_sql_value(value: &DbValue) -> Option<Self> {
+                    match value {
+                        // wide unsigned values arrive as text
+                        DbValue::Text(s) => s.trim().parse().ok(),
+                        other => other.as_i64().and_then(|v| <$ty>::try_from(v).ok()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+int_from_sql_value!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl FromSqlValue for f64 {
+    fn from_sql_value(value: &DbValue) -> Option<Self> {
+        value.as_f64()
+    }
+}
+
+impl FromSqlValue for f32 {
+    fn from_sql_value(value: &DbValue) -> Option<Self> {
+        value.as_f64().map(|v| v as f32)
+    }
+}
+
+impl FromSqlValue for bool {
+    fn from_sql_value(value: &DbValue) -> Option<Self> {
+        value.as_bool()
+    }
+}
+
+impl FromSqlValue for String {
+    fn from_sql_value(value: &DbValue) -> Option<Self> {
+        value.as_text()
+    }
+}
+
+impl FromSqlValue for Vec<u8> {
+    fn from_sql_value(value: &DbValue) -> Option<Self> {
+        match value {
+            DbValue::Bytes(b) => Some(b.clone()),
+            other => other.as_text().map(String::into_bytes),
+        }
+    }
+}
+
+/// SQLite keeps dates as text, so text in the bound format is read back too.
+impl FromSqlValue for Date {
+    fn from_sql_value(value: &DbValue) -> Option<Self> {
+        match value {
+            DbValue::Date(d) => Some(*d),
+            DbValue::DateTime(dt) => Some(dt.date()),
+            DbValue::Text(s) => parse_ymd(s.get(..10)?).ok(),
+            _ => None,
+        }
+    }
+}
+
+impl FromSqlValue for Time {
+    fn from_sql_value(value: &DbValue) -> Option<Self> {
+        match value {
+            DbValue::Time(t) => Some(*t),
+            DbValue::DateTime(dt) => Some(dt.time()),
+            DbValue::Text(s) => parse_ymd_hms(&format!("2000-01-01 {}", s.trim())).ok().map(|dt| dt.time()),
+            _ => None,
+        }
+    }
+}
+
+impl FromSqlValue for PrimitiveDateTime {
+    fn from_sql_value(value: &DbValue) -> Option<Self> {
+        match value {
+            DbValue::DateTime(dt) => Some(*dt),
+            DbValue::Date(d) => Some(d.midnight()),
+            DbValue::Text(s) => parse_ymd_hms(s.get(..19)?).ok(),
+            _ => None,
+        }
+    }
+}
+
+/// `NULL` is `Some(None)`, anything else must fit `T`.
+impl<T: FromSqlValue> FromSqlValue for Option<T> {
+    fn from_sql_value(value: &DbValue) -> Option<Self> {
+        match value {
+            DbValue::Null => Some(None),
+            other => T::from_sql_value(other).map(Some),
+        }
+    }
+}
+
+/// One fetched row: column names and their typed values, in select order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DbRow {
+    columns: Vec<String>,
+    values: Vec<DbValue>,
+}
+
+impl DbRow {
+    pub fn new(columns: Vec<String>, values: Vec<DbValue>) -> Self {
+        DbRow { columns, values }
+    }
+
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    pub fn values(&self) -> &[DbValue] {
+        &self.values
+    }
+
+    pub fn into_values(self) -> Vec<DbValue> {
+        self.values
+    }
+
+    /// Value of the named column.
+    pub fn get(&self, column: &str) -> Option<&DbValue> {
+        let index = self.columns.iter().position(|c| c == column)?;
+        self.values.get(index)
+    }
+
+    /// The named column converted to `T`.
+    ///
+    /// Fails with `DbError::InvalidQuery` when the column is missing or doesn't fit `T`.
+    pub fn try_get<T: FromSqlValue>(&self, column: &str) -> Result<T, DbError> {
+        let value = self
+            .get(column)
+            .ok_or_else(|| DbError::InvalidQuery(format!("column `{}` is not in the row", column)))?;
+        T::from_sql_value(value).ok_or_else(|| {
+            DbError::InvalidQuery(format!(
+                "column `{}` can't be read as {}: {:?}",
+                column,
+                std::any::type_name::<T>(),
+                value
+            ))
+        })
+    }
+
+    /// Value at a zero-based position.
+    pub fn get_index(&self, index: usize) -> Option<&DbValue> {
+        self.values.get(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
@@ -0,0 +1,218 @@
+//! Helpers for database integration tests.
+//!
+//! `TestDatabase` gives every test its own SQLite database (in memory or in a
+//! temp file), migrated with the given migration list, so tests don't depend on
+//! `.env` / `CONFIG` and can't see each other's rows.
+//!
+//! # Examples
+//! ```rust,ignore
+//! use rustavel_core::testing::TestDatabase;
+//! use rustavel_db::migrations::get_all_migrations;
+//!
+//! #[tokio::test]
+//! async fn creates_todo() {
+//!     let db = TestDatabase::memory(get_all_migrations).await.unwrap();
+//!     db.schema().insert("todos", &[("title", "x"), ("done", "0")]).await.unwrap();
+//!     db.assert_database_has("todos", [("title", "x")]).await;
+//!     db.assert_database_missing("todos", [("title", "y")]).await;
+//! }
+//! ```
+
+use crate::db::migration::{checksum, Migration};
+use crate::db::schema::Schema;
+use crate::db::table::Table;
+use crate::sql::database_client::DbError;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+/// Table the migration runner keeps its bookkeeping in, never truncated.
+const MIGRATIONS_TABLE: &str = "migrations";
+
+static TEMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+pub struct TestDatabase {
+    schema: Schema,
+    migrations: fn() -> Vec<Box<dyn Migration>>,
+    path: Option<PathBuf>,
+}
+
+impl TestDatabase {
+    /// Fresh in-memory SQLite database with all migrations applied.
+    ///
+    /// The pool holds a single connection, because every SQLite memory
+    /// connection is a separate database.
+    pub async fn memory(migrations: fn() -> Vec<Box<dyn Migration>>) -> Result<Self, DbError> {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .min_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with(SqliteConnectOptions::new().in_memory(true))
+            .await?;
+
+        Self::boot(Schema::from_sqlite_pool(pool, ""), migrations, None).await
+    }
+
+    /// Fresh SQLite database in the temp directory with all migrations applied.
+    ///
+    /// Useful when the code under test opens its own connections; the file is
+    /// removed when the `TestDatabase` is dropped.
+    pub async fn temp_file(migrations: fn() -> Vec<Box<dyn Migration>>) -> Result<Self, DbError> {
+        let path = std::env::temp_dir().join(format!(
+            "rustavel-test-{}-{}.sqlite",
+            std::process::id(),
+            TEMP_COUNTER.fetch_add(1, Ordering::SeqCst)
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        let pool = SqlitePoolOptions::new()
+            .connect_with(
+                SqliteConnectOptions::new()
+                    .filename(&path)
+                    .create_if_missing(true),
+            )
+            .await?;
+
+        Self::boot(Schema::from_sqlite_pool(pool, ""), migrations, Some(path)).await
+    }
+
+    /// In-memory database without migrations holding one table, for tests of a single model.
+    ///
+    /// # Examples
+    /// ```rust,ignore
+    /// let db = TestDatabase::with_table("items", |table| {
+    ///     table.id();
+    ///     table.string("name", 50);
+    /// })
+    /// .await
+    /// .unwrap();
+    /// ```
+    pub async fn with_table<F>(name: &str, define: F) -> Result<Self, DbError>
+    where
+        F: FnOnce(&mut Table),
+    {
+        let mut db = Self::memory(Vec::new).await?;
+        db.create_table(name, define).await?;
+        Ok(db)
+    }
+
+    /// Create table `name` right away, without recording a migration.
+    pub async fn create_table<F>(&mut self, name: &str, define: F) -> Result<(), DbError>
+    where
+        F: FnOnce(&mut Table),
+    {
+        self.schema.clear_current();
+        self.schema.create(name, define);
+        let result = self.schema.execute_migration(&format!("create_{}", name), &Instant::now().into()).await;
+        self.schema.clear_current();
+        result
+    }
+
+    async fn boot(
+        schema: Schema,
+        migrations: fn() -> Vec<Box<dyn Migration>>,
+        path: Option<PathBuf>,
+    ) -> Result<Self, DbError> {
+        let mut db = TestDatabase {
+            schema,
+            migrations,
+            path,
+        };
+        db.migrate().await?;
+        Ok(db)
+    }
+
+    /// Run every migration as batch `1`, like `artisan migrate` on an empty database.
+    async fn migrate(&mut self) -> Result<(), DbError> {
+        self.schema.create_migration_table().await?;
+        for mig in (self.migrations)() {
+            let start = Instant::now();
+            self.schema.clear_current();
+            mig.up(&mut self.schema).await?;
+            let sql = self.schema.migration_sql().unwrap_or_default();
+            self.schema.execute_migration(mig.name(), &start.into()).await?;
+            self.schema.add_migrated_table(mig.name(), 1).await?;
+            self.schema.set_migration_checksum(mig.name(), &checksum(&sql)).await?;
+        }
+        Ok(())
+    }
+
+    pub fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    pub fn schema_mut(&mut self) -> &mut Schema {
+        &mut self.schema
+    }
+
+    /// Drop every table and run all migrations again.
+    pub async fn refresh(&mut self) -> Result<(), DbError> {
+        self.schema.drop_all_tables().await?;
+        self.migrate().await
+    }
+
+    /// Empty every table except `migrations`; much faster than `refresh()`.
+    pub async fn truncate(&self) -> Result<(), DbError> {
+        self.schema.disable_foreign_key_constraints().await;
+        for table in self.schema.get_tables().await? {
+            if table == MIGRATIONS_TABLE {
+                continue;
+            }
+            self.schema.truncate(table).await?;
+        }
+        self.schema.enable_foreign_key_constraints().await;
+        Ok(())
+    }
+
+    /// Panics unless `table` has at least one row matching all `values`.
+    pub async fn assert_database_has<'a>(&self, table: &str, values: impl AsRef<[(&'a str, &'a str)]>) {
+        let values = values.as_ref();
+        let count = self.count(table, values).await;
+        assert!(
+            count > 0,
+            "failed asserting that table `{}` has a row matching {:?}",
+            table,
+            values
+        );
+    }
+
+    /// Panics if `table` has any row matching all `values`.
+    pub async fn assert_database_missing<'a>(&self, table: &str, values: impl AsRef<[(&'a str, &'a str)]>) {
+        let values = values.as_ref();
+        let count = self.count(table, values).await;
+        assert!(
+            count == 0,
+            "failed asserting that table `{}` has no row matching {:?}, found {}",
+            table,
+            values,
+            count
+        );
+    }
+
+    /// Panics unless `table` has exactly `expected` rows.
+    pub async fn assert_database_count(&self, table: &str, expected: i64) {
+        let count = self.count(table, &[]).await;
+        assert_eq!(
+            count, expected,
+            "failed asserting that table `{}` has {} rows, found {}",
+            table, expected, count
+        );
+    }
+
+    async fn count(&self, table: &str, values: &[(&str, &str)]) -> i64 {
+        self.schema
+            .count_where(table, values)
+            .await
+            .unwrap_or_else(|e| panic!("can't query table `{}`: {:?}", table, e))
+    }
+}
+
+impl Drop for TestDatabase {
+    fn drop(&mut self) {
+        if let Some(path) = &self.path {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
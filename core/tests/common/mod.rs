@@ -0,0 +1,61 @@
+// every test binary compiles this module, not all of them use all of it
+#![allow(dead_code)]
+
+use rustavel_core::mvc::model::{Col, Model};
+use rustavel_core::sql::database_client::DbError;
+use rustavel_core::sql::value::{DbRow, DbValue};
+use rustavel_core::testing::TestDatabase;
+
+/// Model of the `items` table of `items_database()`, written by hand since the
+/// derive lives in the `macros` crate.
+#[derive(Debug)]
+pub struct Item {
+    pub id: i64,
+    pub name: String,
+}
+
+/// What `#[derive(Model)]` would generate, so `col!(Item::name)` works.
+pub struct ItemColumns {
+    pub id: Col<Item>,
+    pub name: Col<Item>,
+}
+
+impl Item {
+    pub const COLUMNS: ItemColumns = ItemColumns {
+        id: Col::raw("id"),
+        name: Col::raw("name"),
+    };
+}
+
+impl Model for Item {
+    type PrimaryKey = i64;
+
+    fn table() -> &'static str {
+        "items"
+    }
+    fn primary_key() -> &'static str {
+        "id"
+    }
+    fn columns() -> &'static [&'static str] {
+        &["id", "name"]
+    }
+    fn to_row(&self) -> Vec<(&'static str, DbValue)> {
+        vec![("id", DbValue::I64(self.id)), ("name", DbValue::Text(self.name.clone()))]
+    }
+    fn from_row(row: &DbRow) -> Result<Self, DbError> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            name: row.try_get("name")?,
+        })
+    }
+}
+
+/// In-memory database with an empty `items (id, name)` table.
+pub async fn items_database() -> TestDatabase {
+    TestDatabase::with_table("items", |table| {
+        table.id();
+        table.string("name", 50);
+    })
+    .await
+    .unwrap()
+}
@@ -0,0 +1,21 @@
+use rustavel_core::sql::database_client::{DatabaseClient, DbError, SqliteClient};
+use sqlx::sqlite::SqlitePoolOptions;
+
+async fn client() -> SqliteClient {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    SqliteClient { pool }
+}
+
+#[tokio::test]
+async fn unexpected_column_types_are_errors() {
+    let client = client().await;
+
+    assert_eq!(client.fetch_strings("SELECT 'a' UNION ALL SELECT 'b'").await.unwrap(), ["a", "b"]);
+    assert!(matches!(client.fetch_strings_params("SELECT 1", &[]).await, Err(DbError::Sqlx(_))));
+    assert!(matches!(client.fetch_numbers("SELECT 'x'").await, Err(DbError::Sqlx(_))));
+    assert_eq!(client.fetch_numbers("SELECT MAX(1) WHERE 0").await.unwrap(), Vec::<i64>::new());
+}
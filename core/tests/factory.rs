@@ -0,0 +1,53 @@
+mod common;
+
+use common::{items_database, Item};
+use rustavel_core::facades::faker::Faker;
+use rustavel_core::mvc::factory::HasFactory;
+
+impl HasFactory for Item {
+    fn definition(faker: &mut Faker) -> Self {
+        Item {
+            id: 0,
+            name: faker.word(),
+        }
+    }
+}
+
+#[test]
+fn make_applies_count_state_and_sequence() {
+    let items = Item::factory()
+        .count(3)
+        .state(|item, _| item.name = "x".to_string())
+        .sequence(|item, i| item.name = format!("{}{}", item.name, i))
+        .make();
+    let names: Vec<&str> = items.iter().map(|i| i.name.as_str()).collect();
+    assert_eq!(names, vec!["x0", "x1", "x2"]);
+
+    let first: Vec<String> = Item::factory().count(5).seed(7).make().into_iter().map(|i| i.name).collect();
+    let second: Vec<String> = Item::factory().count(5).seed(7).make().into_iter().map(|i| i.name).collect();
+    assert_eq!(first, second);
+}
+
+#[tokio::test]
+async fn create_persists_rows_and_returns_their_keys() {
+    let db = items_database().await;
+
+    let items = Item::factory()
+        .count(3)
+        .sequence(|item, i| item.name = format!("item {}", i))
+        .create(db.schema())
+        .await
+        .unwrap();
+    assert_eq!(items.iter().map(|i| i.id).collect::<Vec<_>>(), vec![1, 2, 3]);
+    db.assert_database_count("items", 3).await;
+    db.assert_database_has("items", [("id", "3"), ("name", "item 2")]).await;
+
+    // an explicit key is inserted as is
+    let item = Item::factory()
+        .state(|item, _| item.id = 10)
+        .create_one(db.schema())
+        .await
+        .unwrap();
+    assert_eq!(item.id, 10);
+    db.assert_database_has("items", [("id", "10"), ("name", item.name.as_str())]).await;
+}
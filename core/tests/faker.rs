@@ -0,0 +1,36 @@
+use rustavel_core::facades::faker::Faker;
+use time::macros::{date, datetime};
+
+#[test]
+fn faker_is_deterministic_under_seed() {
+    let mut a = Faker::seeded(7);
+    let mut b = Faker::seeded(7);
+    for _ in 0..20 {
+        assert_eq!(a.name(), b.name());
+        assert_eq!(a.email(), b.email());
+        assert_eq!(a.sentence(6), b.sentence(6));
+        assert_eq!(a.persian_name(), b.persian_name());
+    }
+    assert_ne!(Faker::seeded(1).next_u64(), Faker::seeded(2).next_u64());
+}
+
+#[test]
+fn faker_values_stay_in_range() {
+    let mut faker = Faker::seeded(99);
+    for _ in 0..200 {
+        let n = faker.number_between(-3, 3);
+        assert!((-3..=3).contains(&n));
+
+        let day = faker.date_between(date!(2024 - 01 - 01), date!(2024 - 01 - 31));
+        assert!(day >= date!(2024 - 01 - 01) && day <= date!(2024 - 01 - 31));
+
+        let at = faker.date_time_between(datetime!(2024-01-01 0:00), datetime!(2024-01-02 0:00));
+        assert!(at >= datetime!(2024-01-01 0:00) && at <= datetime!(2024-01-02 0:00));
+
+        let phone = faker.persian_phone_number();
+        assert_eq!(phone.len(), 11);
+        assert!(phone.starts_with("09") && phone.chars().all(|c| c.is_ascii_digit()));
+
+        assert!(faker.email().contains("@example."));
+    }
+}
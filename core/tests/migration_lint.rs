@@ -0,0 +1,233 @@
+mod common;
+
+use common::items_database;
+use rustavel_core::config::database::DatabaseEngine;
+use rustavel_core::db::introspection::{ColumnInfo, TableInfo};
+use rustavel_core::db::lint::{LintIssue, MigrationLinter, Severity};
+use rustavel_core::db::migration::Migration;
+use rustavel_core::db::schema::Schema;
+use rustavel_core::db::table::{Table, TableAction};
+use rustavel_core::facades::db::DB;
+use rustavel_core::sql::database_client::DbError;
+use std::sync::{Arc, Mutex};
+
+fn define(name: &str, action: TableAction, f: impl FnOnce(&mut Table)) -> Table {
+    let mut table = Table::new(name);
+    table.action = action;
+    f(&mut table);
+    table
+}
+
+fn rules(issues: &[LintIssue]) -> Vec<(&str, Option<&str>)> {
+    issues.iter().map(|i| (i.rule, i.column.as_deref())).collect()
+}
+
+#[test]
+fn migrations_are_linted_in_order() {
+    let mut linter = MigrationLinter::new(DatabaseEngine::Mysql, "");
+
+    let users = define("users", TableAction::Create, |table| {
+        table.id();
+        table.string("email", 320).unique();
+        table.text("bio").index();
+        table.string("name", 100);
+    });
+    let issues = linter.lint("m_1_create_users", &users);
+    assert_eq!(rules(&issues), vec![("index-too-long", Some("email")), ("text-index", Some("bio"))]);
+    assert!(issues.iter().all(LintIssue::is_error));
+    assert!(issues[0].to_string().starts_with("m_1_create_users: users.email: "));
+
+    let posts = define("posts", TableAction::Create, |table| {
+        table.id();
+        table.big_integer("user_id").unsigned();
+        table.integer("editor_id");
+        table.big_integer("team_id").unsigned();
+        table.foreign("user_id").reference("id").on("users");
+        table.foreign("editor_id").reference("id").on("users");
+        table.foreign("team_id").reference("id").on("teams");
+        table.foreign("tag_id").reference("id").on("tags");
+    });
+    assert_eq!(
+        rules(&linter.lint("m_2_create_posts", &posts)),
+        vec![
+            ("foreign-key-type-mismatch", Some("editor_id")),
+            ("foreign-key-missing-table", Some("team_id")),
+            ("foreign-key-missing-column", Some("tag_id")),
+        ]
+    );
+
+    // the linter remembers `posts` from the previous migration
+    let alter = define("posts", TableAction::Alter, |table| {
+        table.string("slug", 100);
+        table.string("title", 100).nullable();
+        table.boolean("draft").default_bool(true);
+        table.drop_column("editor_id");
+    });
+    let issues = linter.lint("m_3_alter_posts", &alter);
+    assert_eq!(
+        rules(&issues),
+        vec![("not-null-without-default", Some("slug")), ("mysql-table-rebuild", Some("editor_id"))]
+    );
+    assert_eq!(issues[1].severity, Severity::Warning);
+
+    // SQLite compares integer keys by affinity and rebuilds nothing itself
+    let mut linter = MigrationLinter::new(DatabaseEngine::Sqlite, "app_");
+    linter.lint("m_1_create_users", &define("app_users", TableAction::Create, |table| {
+        table.id();
+    }));
+    let comments = define("app_comments", TableAction::Alter, |table| {
+        table.integer("user_id").nullable();
+        table.foreign("user_id").reference("id").on("users");
+    });
+    assert!(linter.lint("m_2_alter_comments", &comments).is_empty());
+}
+
+/// An existing table, as `Schema::introspect()` would report it.
+fn existing(name: &str, columns: &[(&str, &str)]) -> TableInfo {
+    TableInfo {
+        name: name.to_string(),
+        comment: String::new(),
+        columns: columns
+            .iter()
+            .map(|(name, data_type)| ColumnInfo {
+                name: name.to_string(),
+                data_type: data_type.to_string(),
+                nullable: false,
+                default: None,
+                primary: *name == "id",
+                comment: String::new(),
+            })
+            .collect(),
+        indexes: vec![],
+        foreign_keys: vec![],
+    }
+}
+
+#[test]
+fn sqlite_compares_affinities_and_reports_its_own_limits() {
+    let mut linter = MigrationLinter::new(DatabaseEngine::Sqlite, "");
+    linter.lint("m_1_create_users", &define("users", TableAction::Create, |table| {
+        table.id();
+    }));
+
+    // `bigint` and `integer` share an affinity, text and integer don't
+    let posts = define("posts", TableAction::Create, |table| {
+        table.id();
+        table.big_integer("user_id");
+        table.string("author", 20);
+        table.foreign("user_id").reference("id").on("users");
+        table.foreign("author").reference("id").on("users");
+    });
+    let issues = linter.lint("m_2_create_posts", &posts);
+    assert_eq!(rules(&issues), vec![("foreign-key-type-mismatch", Some("author"))]);
+    assert!(issues[0].message.contains("is text but references `users.id` of type integer"));
+
+    let alter = define("posts", TableAction::Alter, |table| {
+        table.string("slug", 100);
+        table.drop_column("author");
+    });
+    let issues = linter.lint("m_3_alter_posts", &alter);
+    assert_eq!(rules(&issues), vec![("not-null-without-default", Some("slug"))]);
+    assert!(issues[0].message.contains("SQLite can't add a NOT NULL column without a default"));
+}
+
+#[test]
+fn existing_tables_are_known_and_unknown_ones_can_be_allowed() {
+    let users = existing("users", &[("id", "bigint(20) unsigned"), ("email", "varchar(320)")]);
+    let posts = define("posts", TableAction::Create, |table| {
+        table.id();
+        table.big_integer("user_id").unsigned();
+        table.big_integer("team_id").unsigned();
+        table.string("email", 320);
+        table.foreign("user_id").reference("id").on("users");
+        table.foreign("team_id").reference("id").on("teams");
+        table.foreign("email").reference("mail").on("users");
+    });
+
+    let mut linter = MigrationLinter::new(DatabaseEngine::Mysql, "").with_tables(std::slice::from_ref(&users));
+    assert_eq!(
+        rules(&linter.lint("m_2_create_posts", &posts)),
+        vec![("foreign-key-missing-table", Some("team_id")), ("foreign-key-missing-reference", Some("email"))]
+    );
+
+    // a table the linter can't see may exist, a missing column of a known one still can't
+    let mut linter = MigrationLinter::new(DatabaseEngine::Mysql, "")
+        .with_tables(&[users])
+        .allow_unknown_tables();
+    assert_eq!(
+        rules(&linter.lint("m_2_create_posts", &posts)),
+        vec![("foreign-key-missing-reference", Some("email"))]
+    );
+}
+
+#[test]
+fn foreign_tables_resolve_with_or_without_the_prefix() {
+    let mut linter =
+        MigrationLinter::new(DatabaseEngine::Mysql, "app_").with_tables(&[existing("app_users", &[("id", "bigint unsigned")])]);
+    let posts = define("app_posts", TableAction::Create, |table| {
+        table.id();
+        table.big_integer("user_id").unsigned();
+        table.big_integer("editor_id").unsigned();
+        table.big_integer("parent_id").unsigned().nullable();
+        table.big_integer("team_id").unsigned();
+        table.foreign("user_id").reference("id").on("users");
+        table.foreign("editor_id").reference("id").on("app_users");
+        table.foreign("parent_id").reference("id").on("posts");
+        table.foreign("team_id").reference("id").on("teams");
+    });
+    let issues = linter.lint("m_2_create_posts", &posts);
+    assert_eq!(rules(&issues), vec![("foreign-key-missing-table", Some("team_id"))]);
+    assert!(issues[0].message.contains("`teams`"));
+}
+
+/// A migration whose `up()` also runs SQL of its own.
+struct BackfillItems;
+
+#[async_trait::async_trait]
+impl Migration for BackfillItems {
+    async fn up(&self, schema: &mut Schema) -> Result<(), DbError> {
+        schema.table("items", |table| {
+            table.string("sku", 20);
+        });
+        schema.insert("items", &[("name", "backfilled")]).await?;
+        schema.drop_table("items").await
+    }
+
+    async fn down(&self, _schema: &mut Schema) -> Result<(), DbError> {
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "m_4_backfill_items"
+    }
+}
+
+#[tokio::test]
+async fn linting_runs_no_migration_sql() {
+    let mut db = items_database().await;
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let sink = seen.clone();
+    let id = DB::listen(move |query| {
+        if query.sql.contains("items") {
+            sink.lock().unwrap().push(query.sql.clone());
+        }
+    });
+
+    // what `migrate --lint` does with a pending migration
+    let schema = db.schema_mut();
+    let tables = schema.introspect().await.unwrap();
+    schema.clear_current();
+    schema.begin_dry_run();
+    BackfillItems.up(schema).await.unwrap();
+    let recorded = schema.end_dry_run();
+    let mut linter = MigrationLinter::new(DatabaseEngine::Sqlite, "").with_tables(&tables);
+    let issues = linter.lint(BackfillItems.name(), schema.current_table().unwrap());
+    DB::forget(id);
+
+    assert_eq!(rules(&issues), vec![("not-null-without-default", Some("sku"))]);
+    assert_eq!(recorded.len(), 2);
+    assert!(recorded[0].starts_with("INSERT INTO"));
+    assert!(seen.lock().unwrap().iter().all(|sql| !sql.starts_with("INSERT") && !sql.starts_with("DROP")));
+    assert!(db.schema().has_table("items").await.unwrap());
+    db.assert_database_count("items", 0).await;
+}
@@ -0,0 +1,48 @@
+mod common;
+
+use common::{items_database, Item};
+use futures::{StreamExt, TryStreamExt};
+use rustavel_core::col;
+use rustavel_core::sql::query::QueryDsl;
+use rustavel_core::sql::value::DbValue;
+use std::sync::Mutex;
+
+#[tokio::test]
+async fn chunk_and_cursor_read_every_row_once() {
+    let db = items_database().await;
+    for i in 1..=25 {
+        Item::insert().set(col!(Item::name), format!("item {}", i)).execute(db.schema()).await.unwrap();
+    }
+    let schema = db.schema();
+
+    let sizes = Mutex::new(Vec::new());
+    Item::all()
+        .chunk(schema, 10, |items| {
+            sizes.lock().unwrap().push(items.len());
+            async { Ok(()) }
+        })
+        .await
+        .unwrap();
+    assert_eq!(*sizes.lock().unwrap(), vec![10, 10, 5]);
+
+    let ids: Vec<i64> = Item::all().cursor(schema).map_ok(|item| item.id).try_collect().await.unwrap();
+    assert_eq!(ids, (1..=25).collect::<Vec<_>>());
+    assert_eq!(Item::all().cursor(schema).next().await.unwrap().unwrap().name, "item 1");
+
+    // deleting what was read doesn't make `chunk_by_id` skip rows
+    let seen = Mutex::new(0);
+    Item::all()
+        .chunk_by_id(schema, 10, |items| {
+            *seen.lock().unwrap() += items.len();
+            async move {
+                for item in items {
+                    schema.execute_params("DELETE FROM items WHERE id = ?", &[DbValue::I64(item.id)]).await?;
+                }
+                Ok(())
+            }
+        })
+        .await
+        .unwrap();
+    assert_eq!(*seen.lock().unwrap(), 25);
+    assert_eq!(Item::all().cursor(schema).count().await, 0);
+}
@@ -0,0 +1,59 @@
+use rustavel_core::db::table::{ForeignKey, TableAction};
+use rustavel_core::sql::generator::SqlGenerator;
+use rustavel_core::sql::mysql::MySqlGenerator;
+use rustavel_core::sql::sqlite::SqliteGenerator;
+
+#[test]
+fn identifiers_and_literals_are_escaped() {
+    let mysql = MySqlGenerator;
+    assert_eq!(mysql.quote_ident("we`ird"), "`we``ird`");
+    assert_eq!(mysql.quote_literal("it's \\"), "'it''s \\\\'");
+
+    let sqlite = SqliteGenerator;
+    assert_eq!(sqlite.quote_ident("we\"ird"), "\"we\"\"ird\"");
+    assert_eq!(sqlite.quote_literal("it's"), "'it''s'");
+
+    // columns are compared, not string literals
+    assert_eq!(
+        sqlite.record_exists("users", "email"),
+        "SELECT COUNT(*) AS \"count\" FROM \"users\" WHERE \"email\" = ?"
+    );
+    assert_eq!(
+        mysql.record_exists_except("users", "email", "id"),
+        "SELECT COUNT(*) AS `count` FROM `users` WHERE `email` = ? AND `id` <> ?"
+    );
+}
+
+/// `sql` with runs of whitespace collapsed, the generators pad optional parts with spaces.
+fn squash(sql: &str) -> String {
+    sql.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[test]
+fn foreign_keys_name_the_referenced_column_and_actions() {
+    let key = ForeignKey {
+        column_name: "author_id".to_string(),
+        foreign_table: "users".to_string(),
+        referenced_column: "uuid".to_string(),
+        on_delete: true,
+        on_update: false,
+    };
+    assert_eq!(
+        squash(&SqliteGenerator.foreign_key(&key, "posts", &TableAction::Create)),
+        "FOREIGN KEY(\"author_id\") REFERENCES \"users\"(\"uuid\") on delete cascade"
+    );
+    assert_eq!(
+        squash(&MySqlGenerator.foreign_key(&key, "posts", &TableAction::Alter)),
+        "ADD CONSTRAINT `posts_author_id_foreign` FOREIGN KEY (`author_id`) REFERENCES `users` (`uuid`) ON DELETE CASCADE"
+    );
+
+    let key = ForeignKey { on_delete: false, on_update: true, ..key };
+    assert_eq!(
+        squash(&SqliteGenerator.foreign_key(&key, "posts", &TableAction::Create)),
+        "FOREIGN KEY(\"author_id\") REFERENCES \"users\"(\"uuid\") on update cascade"
+    );
+    assert_eq!(
+        squash(&MySqlGenerator.foreign_key(&key, "posts", &TableAction::Create)),
+        "CONSTRAINT `posts_author_id_foreign` FOREIGN KEY (`author_id`) REFERENCES `users` (`uuid`) ON UPDATE CASCADE"
+    );
+}
@@ -0,0 +1,75 @@
+use rustavel_core::config::CONFIG;
+use rustavel_core::db::table::{Table, TableAction};
+use rustavel_core::sql::generator::SqlGenerator;
+use rustavel_core::sql::mysql::MySqlGenerator;
+use rustavel_core::sql::sqlite::SqliteGenerator;
+use rustavel_core::testing::TestDatabase;
+
+fn create(name: &str) -> Table {
+    let mut table = Table::new(name);
+    table.action = TableAction::Create;
+    table
+}
+
+#[test]
+fn table_options_are_rendered() {
+    let mysql = MySqlGenerator;
+    let table = create("users");
+    assert!(mysql.table_sql(&table, "", "").contains(&format!(
+        ") COLLATE='{}' ENGINE=InnoDB;",
+        CONFIG.database.collection
+    )));
+
+    let mut table = create("logs");
+    table
+        .engine("MyISAM")
+        .collation("latin1_swedish_ci")
+        .temporary()
+        .auto_increment_from(1000)
+        .table_comment("it's a log");
+    let sql = mysql.table_sql(&table, "", "");
+    assert!(sql.starts_with("CREATE TEMPORARY TABLE `logs`"));
+    assert!(sql.contains(
+        ") COLLATE='latin1_swedish_ci' ENGINE=MyISAM AUTO_INCREMENT=1000 COMMENT='it''s a log';"
+    ));
+
+    let mut table = create("legacy");
+    table.charset("latin1");
+    assert!(mysql.table_sql(&table, "", "").contains(") DEFAULT CHARSET=latin1 ENGINE=InnoDB;"));
+
+    // anything but a plain name is quoted instead of ending up in the statement
+    let mut table = create("odd");
+    table.charset("latin1; DROP TABLE users").engine("InnoDB -- x");
+    assert!(mysql.table_sql(&table, "", "").contains(
+        ") DEFAULT CHARSET=`latin1; DROP TABLE users` ENGINE=`InnoDB -- x`;"
+    ));
+
+    let mut table = create("logs");
+    table.id();
+    table.auto_increment_from(1000);
+    let sql = SqliteGenerator.table_sql(&table, "", "");
+    assert!(sql.contains("INSERT INTO sqlite_sequence (name, seq) VALUES ('logs', 999);"));
+
+    table.temporary();
+    let sql = SqliteGenerator.table_sql(&table, "", "");
+    assert!(sql.starts_with("CREATE TEMPORARY TABLE \"logs\""));
+    assert!(sql.contains("INSERT INTO temp.sqlite_sequence (name, seq) VALUES ('logs', 999);"));
+
+    // no AUTOINCREMENT key, nothing to start
+    let mut table = create("logs");
+    table.auto_increment_from(1000);
+    assert!(!SqliteGenerator.table_sql(&table, "", "").contains("sqlite_sequence"));
+}
+
+#[tokio::test]
+async fn sqlite_temporary_tables_start_their_own_sequence() {
+    let db = TestDatabase::with_table("logs", |table| {
+        table.id();
+        table.string("line", 50);
+        table.temporary().auto_increment_from(1000);
+    })
+    .await
+    .unwrap();
+    db.schema().insert("logs", &[("line", "first")]).await.unwrap();
+    db.assert_database_has("logs", [("id", "1000"), ("line", "first")]).await;
+}
@@ -0,0 +1,87 @@
+use rustavel_core::mvc::model::{Col, Model};
+use rustavel_core::sql::database_client::DbError;
+use rustavel_core::sql::query::QueryDsl;
+use rustavel_core::sql::value::{DbRow, DbValue};
+use rustavel_core::testing::TestDatabase;
+
+struct Note {
+    id: i64,
+    body: String,
+}
+
+impl Model for Note {
+    type PrimaryKey = i64;
+
+    fn table() -> &'static str {
+        "notes"
+    }
+    fn primary_key() -> &'static str {
+        "id"
+    }
+    fn columns() -> &'static [&'static str] {
+        &["id", "body"]
+    }
+    fn timestamps() -> bool {
+        true
+    }
+    fn to_row(&self) -> Vec<(&'static str, DbValue)> {
+        vec![("id", DbValue::I64(self.id)), ("body", DbValue::Text(self.body.clone()))]
+    }
+    fn from_row(row: &DbRow) -> Result<Self, DbError> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            body: row.try_get("body")?,
+        })
+    }
+}
+
+const OLD: &str = "2001-01-01 00:00:00";
+
+async fn stamps(db: &TestDatabase, id: i64) -> (Option<String>, Option<String>) {
+    let rows = db
+        .schema()
+        .fetch_rows("SELECT created_at, updated_at FROM notes WHERE id = ?", &[DbValue::I64(id)])
+        .await
+        .unwrap();
+    (rows[0].try_get("created_at").unwrap(), rows[0].try_get("updated_at").unwrap())
+}
+
+#[tokio::test]
+async fn write_builders_fill_timestamps() {
+    let db = TestDatabase::with_table("notes", |table| {
+        table.id();
+        table.string("body", 50);
+        table.timestamps();
+    })
+    .await
+    .unwrap();
+
+    Note::insert().set(Col::raw("body"), "a").execute(db.schema()).await.unwrap();
+    let (created, updated) = stamps(&db, 1).await;
+    assert!(created.is_some());
+    assert_eq!(created, updated);
+
+    Note::insert().set(Col::raw("body"), "b").without_timestamps().execute(db.schema()).await.unwrap();
+    assert_eq!(stamps(&db, 2).await, (None, None));
+
+    // an explicit value wins, and update only refreshes `updated_at`
+    Note::insert()
+        .set(Col::raw("body"), "c")
+        .set(Col::raw("created_at"), OLD)
+        .set(Col::raw("updated_at"), OLD)
+        .execute(db.schema())
+        .await
+        .unwrap();
+    assert_eq!(stamps(&db, 3).await, (Some(OLD.to_string()), Some(OLD.to_string())));
+    Note::update(3).set(Col::raw("body"), "d").execute(db.schema()).await.unwrap();
+    let (created, updated) = stamps(&db, 3).await;
+    assert_eq!(created.as_deref(), Some(OLD));
+    assert_ne!(updated.as_deref(), Some(OLD));
+
+    Note::update(3).set(Col::raw("updated_at"), OLD).without_timestamps().execute(db.schema()).await.unwrap();
+    assert_eq!(stamps(&db, 3).await.1.as_deref(), Some(OLD));
+    Note::touch(3).execute(db.schema()).await.unwrap();
+    let (created, updated) = stamps(&db, 3).await;
+    assert_eq!(created.as_deref(), Some(OLD));
+    assert_ne!(updated.as_deref(), Some(OLD));
+}
@@ -0,0 +1,52 @@
+mod common;
+
+use common::{items_database, Item};
+use futures::TryStreamExt;
+use rustavel_core::col;
+use rustavel_core::sql::database_client::DbError;
+use rustavel_core::sql::query::QueryDsl;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+#[tokio::test]
+async fn transaction_commits_rolls_back_and_nests() {
+    let db = items_database().await;
+    let schema = db.schema();
+
+    schema
+        .transaction(|tx| async move { Item::insert().set(col!(Item::name), "kept").execute(&tx).await })
+        .await
+        .unwrap();
+
+    let attempts = AtomicU32::new(0);
+    let failed: Result<(), DbError> = schema
+        .transaction_with_retries(3, |tx| {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            async move {
+                Item::insert().set(col!(Item::name), "undone").execute(&tx).await?;
+                Err(DbError::InvalidQuery("stop".to_string()))
+            }
+        })
+        .await;
+    assert!(failed.is_err());
+    // only deadlocks and serialization failures are retried
+    assert_eq!(attempts.load(Ordering::SeqCst), 1);
+
+    schema
+        .transaction(|tx| async move {
+            Item::insert().set(col!(Item::name), "outer").execute(&tx).await?;
+            let inner = tx
+                .transaction(|tx| async move {
+                    assert_eq!(tx.depth(), 1);
+                    Item::insert().set(col!(Item::name), "inner").execute(&tx).await?;
+                    Err::<(), _>(DbError::InvalidQuery("undo the savepoint".to_string()))
+                })
+                .await;
+            assert!(inner.is_err());
+            Ok(())
+        })
+        .await
+        .unwrap();
+
+    let names: Vec<String> = Item::all().cursor(schema).map_ok(|a| a.name).try_collect().await.unwrap();
+    assert_eq!(names, vec!["kept", "outer"]);
+}
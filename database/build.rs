@@ -0,0 +1,164 @@
+//! Discovers migrations in `src/migrations` and generates `$OUT_DIR/migrations.rs`,
+//! which `src/migrations/mod.rs` includes.
+//!
+//! - files are ordered by their `m_YYYY_MM_DD_HHMM_` prefix
+//! - the struct is whatever `impl Migration for <Struct>` names
+//! - a bad prefix or two files returning the same `name()` fail the build
+//! - `MIGRATION_FILES` maps each `name()` to its file, relative to the workspace, for `schema:dump --prune`
+//! - `SCHEMA_DUMPS` embeds `schema/{engine}-schema.sql`, so the binary loads it wherever it runs
+//! - `SQUASHED_MIGRATIONS` lists what `--prune` deleted (`src/migrations/squashed.txt`)
+
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+struct Found {
+    stem: String,
+    name: String,
+    path: PathBuf,
+    struct_name: String,
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/migrations");
+    println!("cargo:rerun-if-changed=schema");
+
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set"));
+    let migrations_dir = manifest_dir.join("src/migrations");
+    // `artisan` runs from the workspace root, so runtime paths start at this crate's directory name
+    let crate_dir = manifest_dir.file_name().map(PathBuf::from).unwrap_or_default();
+
+    let mut found: Vec<Found> = Vec::new();
+    let mut names: HashMap<String, String> = HashMap::new();
+
+    let entries = fs::read_dir(&migrations_dir)
+        .unwrap_or_else(|e| panic!("could not read {}: {}", migrations_dir.display(), e));
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_file() || path.extension().is_none_or(|e| e != "rs") {
+            continue;
+        }
+        let stem = match path.file_stem().and_then(|s| s.to_str()) {
+            Some(s) if s != "mod" => s.to_string(),
+            _ => continue,
+        };
+
+        if !has_migration_prefix(&stem) {
+            panic!(
+                "migration file `{}.rs` must start with `m_YYYY_MM_DD_HHMM_`, that prefix decides the run order",
+                stem
+            );
+        }
+
+        let source = fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("could not read {}: {}", path.display(), e));
+
+        let struct_name = ident_after(&source, "impl Migration for ").unwrap_or_else(|| {
+            panic!("`{}.rs` has no `impl Migration for <Struct>`", stem)
+        });
+
+        // `name()` is what ends up in the `migrations` table, so it has to be unique
+        let name = name_literal(&source).unwrap_or_else(|| stem.clone());
+        if let Some(other) = names.insert(name.clone(), stem.clone()) {
+            panic!(
+                "duplicate migration name `{}` in `{}.rs` and `{}.rs`",
+                name, other, stem
+            );
+        }
+
+        found.push(Found {
+            stem,
+            name,
+            path,
+            struct_name,
+        });
+    }
+
+    found.sort_by(|a, b| a.stem.cmp(&b.stem));
+
+    let mut content = String::from("// @generated by database/build.rs, do not edit\n\n");
+    for mig in &found {
+        content.push_str(&format!(
+            "#[path = {:?}]\npub mod {};\n",
+            mig.path.to_string_lossy(),
+            mig.stem
+        ));
+    }
+    content.push_str("\npub fn get_all_migrations() -> Vec<Box<dyn Migration>> {\n    vec![\n");
+    for mig in &found {
+        content.push_str(&format!(
+            "        Box::new({}::{} {{}}),\n",
+            mig.stem, mig.struct_name
+        ));
+    }
+    content.push_str("    ]\n}\n");
+
+    // lets `schema:dump --prune` find the file of a squashed migration by its name
+    let relative = crate_dir.join("src/migrations");
+    content.push_str(&format!("\npub const MIGRATIONS_DIR: &str = {:?};\n", relative.to_string_lossy()));
+    content.push_str("\npub const MIGRATION_FILES: &[(&str, &str)] = &[\n");
+    for mig in &found {
+        let file = relative.join(mig.path.file_name().unwrap_or_default());
+        content.push_str(&format!("    ({:?}, {:?}),\n", mig.name, file.to_string_lossy()));
+    }
+    content.push_str("];\n");
+
+    // the dump replaces the pruned migrations, so it travels inside the binary
+    content.push_str("\npub const SCHEMA_DUMPS: &[(&str, &str)] = &[\n");
+    for engine in ["mysql", "sqlite"] {
+        let dump = manifest_dir.join("schema").join(format!("{}-schema.sql", engine));
+        if dump.is_file() {
+            content.push_str(&format!("    ({:?}, include_str!({:?})),\n", engine, dump.to_string_lossy()));
+        }
+    }
+    content.push_str("];\n");
+
+    let squashed = fs::read_to_string(migrations_dir.join("squashed.txt")).unwrap_or_default();
+    content.push_str("\npub const SQUASHED_MIGRATIONS: &[&str] = &[\n");
+    for name in squashed.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        content.push_str(&format!("    {:?},\n", name));
+    }
+    content.push_str("];\n");
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR not set"));
+    write_if_changed(&out_dir.join("migrations.rs"), &content);
+}
+
+/// `m_YYYY_MM_DD_HHMM_<name>`
+fn has_migration_prefix(stem: &str) -> bool {
+    let bytes = stem.as_bytes();
+    let pattern = b"m_dddd_dd_dd_dddd_";
+    if bytes.len() <= pattern.len() {
+        return false;
+    }
+    pattern.iter().zip(bytes).all(|(p, b)| match p {
+        b'd' => b.is_ascii_digit(),
+        _ => p == b,
+    })
+}
+
+/// Identifier right after `marker`, e.g. the struct in `impl Migration for CreateTodos`.
+fn ident_after(source: &str, marker: &str) -> Option<String> {
+    let start = source.find(marker)? + marker.len();
+    let ident: String = source[start..]
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_')
+        .collect();
+    if ident.is_empty() { None } else { Some(ident) }
+}
+
+/// First string literal inside `fn name(...)`.
+fn name_literal(source: &str) -> Option<String> {
+    let body = &source[source.find("fn name(")?..];
+    let start = body.find('"')? + 1;
+    let end = start + body[start..].find('"')?;
+    Some(body[start..end].to_string())
+}
+
+fn write_if_changed(path: &Path, content: &str) {
+    if fs::read_to_string(path).map_or(true, |old| old != content) {
+        fs::write(path, content).expect("could not write migrations.rs");
+    }
+}
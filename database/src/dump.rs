@@ -0,0 +1,46 @@
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use rustavel_core::db::schema::Schema;
+use rustavel_core::facades::datetime::now_compact;
+use rustavel_core::facades::terminal_ui::{*};
+use rustavel_core::sql::database_client::DbError;
+
+pub use rustavel_core::sql::script::DumpMode;
+
+/// `database/dumps/dump-YYYY_MM_DD_HHMM.sql` under the working directory, where `db:dump` writes unless told otherwise.
+pub fn default_dump_path() -> PathBuf {
+    PathBuf::from("database")
+        .join("dumps")
+        .join(format!("dump-{}.sql", now_compact()))
+}
+
+/// Write `tables` (every table when empty) to `path` as plain SQL.
+pub async fn dump(tables: &[String], mode: DumpMode, path: &Path) -> Result<(), DbError> {
+    let start = Instant::now();
+    let schema = Schema::new().await?;
+    let script = schema.dump_database(tables, mode).await?;
+
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, script)?;
+    operation(&format!("Dumped database to {}", path.display()), start.elapsed(), Status::Done);
+    Ok(())
+}
+
+/// Run a file written by `dump` (or any plain SQL script) against the database.
+pub async fn restore(path: &Path) -> Result<(), DbError> {
+    let start = Instant::now();
+    let script = std::fs::read_to_string(path)?;
+    let schema = Schema::new().await?;
+    match schema.load_schema(&script).await {
+        Ok(_) => {
+            operation(&format!("Restored {}", path.display()), start.elapsed(), Status::Done);
+            Ok(())
+        }
+        Err(e) => {
+            operation(&format!("Restoring {}", path.display()), start.elapsed(), Status::Failed);
+            Err(e)
+        }
+    }
+}
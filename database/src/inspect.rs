@@ -0,0 +1,145 @@
+use std::time::Instant;
+use colored::Colorize;
+use rustavel_core::config::CONFIG;
+use rustavel_core::config::database::DatabaseEngine;
+use rustavel_core::db::introspection::TableInfo;
+use rustavel_core::db::schema::Schema;
+use rustavel_core::facades::terminal_ui::{*};
+use rustavel_core::sql::database_client::DbError;
+use serde::Serialize;
+
+/// `db:table` output: the introspected table plus its row count.
+#[derive(Debug, Serialize)]
+struct TableReport {
+    #[serde(flatten)]
+    table: TableInfo,
+    rows: i64,
+}
+
+/// Print engine, version, size, table / view counts and open connections.
+pub async fn show(json: bool) -> Result<(), DbError> {
+    let schema = Schema::new().await?;
+    let overview = schema.overview().await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&overview).unwrap_or_default());
+        return Ok(());
+    }
+
+    title(TitleKind::Info, "Database overview.");
+    line("Engine", &overview.engine);
+    line("Version", &overview.version);
+    line("Database", &overview.database);
+    line("Size", &human_size(overview.size_bytes));
+    line("Tables", &overview.tables.to_string());
+    line("Views", &overview.views.to_string());
+    if let Some(connections) = overview.open_connections {
+        line("Open connections", &connections.to_string());
+    }
+    Ok(())
+}
+
+/// Print columns, indexes, foreign keys and row count of one table.
+pub async fn table(name: &str, json: bool) -> Result<(), DbError> {
+    let schema = Schema::new().await?;
+    if !schema.has_table(name).await? {
+        title(TitleKind::Error, &format!("Table `{}` does not exist.", name));
+        return Err(DbError::InvalidTable);
+    }
+    let report = TableReport {
+        table: schema.get_table_info(name).await?,
+        rows: schema.count_where(name, &[]).await?,
+    };
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report).unwrap_or_default());
+        return Ok(());
+    }
+
+    let table = &report.table;
+    title(TitleKind::Info, &format!("Table {}.", table.name));
+    if !table.comment.is_empty() {
+        line("Comment", &table.comment);
+    }
+    line("Rows", &report.rows.to_string());
+
+    println!("\n {}", "Columns".bold());
+    for column in &table.columns {
+        let mut attributes = Vec::new();
+        if column.primary {
+            attributes.push("primary".to_string());
+        }
+        if column.nullable {
+            attributes.push("nullable".to_string());
+        }
+        if let Some(default) = &column.default {
+            attributes.push(format!("default {}", default));
+        }
+        let name = if attributes.is_empty() {
+            column.name.clone()
+        } else {
+            format!("{} {}", column.name, attributes.join(", ").dimmed())
+        };
+        line(&name, &column.data_type);
+    }
+
+    if !table.indexes.is_empty() {
+        println!("\n {}", "Indexes".bold());
+        for index in &table.indexes {
+            let name = if index.unique {
+                format!("{} {}", index.name, "unique".dimmed())
+            } else {
+                index.name.clone()
+            };
+            line(&name, &index.columns.join(", "));
+        }
+    }
+
+    if !table.foreign_keys.is_empty() {
+        println!("\n {}", "Foreign keys".bold());
+        for key in &table.foreign_keys {
+            line(&key.column, &format!("{}.{}", key.foreign_table, key.referenced_column));
+        }
+    }
+    Ok(())
+}
+
+/// Create `DB_DATABASE`; on SQLite, create its file.
+pub async fn create() -> Result<(), DbError> {
+    let start = Instant::now();
+    let name = &CONFIG.database.database;
+    let schema = Schema::without_database().await?;
+    // connecting already created the SQLite file
+    let sqlite = CONFIG.database.connection == DatabaseEngine::Sqlite;
+    if sqlite || schema.create_database(name).await {
+        operation(&format!("Created database {}", name), start.elapsed(), Status::Done);
+        Ok(())
+    } else {
+        operation(&format!("Creating database {}", name), start.elapsed(), Status::Failed);
+        Err(DbError::InvalidQuery(format!("could not create database `{}`, does it exist already?", name)))
+    }
+}
+
+/// Drop every view and table of the database.
+pub async fn wipe() -> Result<(), DbError> {
+    let schema = Schema::new().await?;
+
+    let start = Instant::now();
+    schema.drop_all_views().await?;
+    operation("Dropped all views", start.elapsed(), Status::Done);
+
+    let start = Instant::now();
+    schema.drop_all_tables().await?;
+    operation("Dropped all tables", start.elapsed(), Status::Done);
+    Ok(())
+}
+
+/// Bytes as B / KB / MB / GB with one decimal.
+fn human_size(bytes: i64) -> String {
+    let mut size = bytes as f64;
+    for unit in ["B", "KB", "MB"] {
+        if size < 1024.0 {
+            return if unit == "B" { format!("{} B", bytes) } else { format!("{:.1} {}", size, unit) };
+        }
+        size /= 1024.0;
+    }
+    format!("{:.1} GB", size)
+}
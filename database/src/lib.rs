@@ -0,0 +1,8 @@
+pub mod dump;
+pub mod inspect;
+pub mod migrator;
+pub mod migrations;
+pub mod schema_docs;
+pub mod seeder;
+pub mod seeders;
+pub mod tenants;
@@ -1,19 +1,105 @@
 // use std::process::exit;
 use clap::Parser;
-use migrator::run_migrations;
+use rustavel_db::dump::{default_dump_path, dump, restore, DumpMode};
+use rustavel_db::inspect;
+use rustavel_db::migrator::{run_migrations, MigrateMode};
+use rustavel_db::schema_docs::{default_docs_dir, generate_docs, DocsFormat};
+use rustavel_db::seeder::run_seeders;
+use rustavel_db::tenants::run_tenant_migrations;
 use tokio::runtime::Runtime;
 use rustavel_core::logger;
 
-mod migrator;
-mod migrations;
-
 #[derive(Parser, Debug)]
 #[command(name = "migration")]
 struct Cli {
-    /// rollback step count
+    /// rollback batch count
     #[arg(long, default_value_t = 0)]
     rollback: i64,
 
+    /// rollback this many migrations, whatever batch they're in
+    #[arg(long, default_value_t = 0)]
+    step: i64,
+
+    /// Rollback all migrations
+    #[arg(long)]
+    reset: bool,
+
+    /// Rollback all migrations and run them again
+    #[arg(long)]
+    refresh: bool,
+
+    /// Show which migrations ran and which were edited since
+    #[arg(long)]
+    status: bool,
+
+    /// Store the current checksum of every applied migration
+    #[arg(long)]
+    repair_checksums: bool,
+
+    /// Check pending migrations for unsafe or invalid definitions, run nothing
+    #[arg(long)]
+    lint: bool,
+
+    /// Write the database structure to database/schema
+    #[arg(long)]
+    schema_dump: bool,
+
+    /// With --schema-dump, delete the migration files the dump contains (not with tenancy)
+    #[arg(long)]
+    prune: bool,
+
+    /// Write an ER diagram in this format plus a markdown table reference
+    #[arg(long, value_enum)]
+    schema_docs: Option<DocsFormat>,
+
+    /// Where --schema-docs (directory, default: database/docs) or --db-dump (file) writes
+    #[arg(long)]
+    output: Option<std::path::PathBuf>,
+
+    /// Dump tables and rows as plain SQL
+    #[arg(long)]
+    db_dump: bool,
+
+    /// With --db-dump, only these tables (comma separated)
+    #[arg(long, value_delimiter = ',')]
+    tables: Vec<String>,
+
+    /// With --db-dump, rows only
+    #[arg(long, conflicts_with = "schema_only")]
+    data_only: bool,
+
+    /// With --db-dump, structure only
+    #[arg(long)]
+    schema_only: bool,
+
+    /// Run an SQL file written by --db-dump
+    #[arg(long)]
+    db_restore: Option<std::path::PathBuf>,
+
+    /// Show engine, version, size, table count and open connections
+    #[arg(long)]
+    db_show: bool,
+
+    /// Show columns, indexes, foreign keys and row count of a table
+    #[arg(long)]
+    db_table: Option<String>,
+
+    /// With --db-show or --db-table, print JSON
+    #[arg(long)]
+    json: bool,
+
+    /// Create DB_DATABASE
+    #[arg(long)]
+    db_create: bool,
+
+    /// Drop all views and tables
+    #[arg(long)]
+    db_wipe: bool,
+
+    /// Migrate every tenant in TENANTS instead of DB_DATABASE
+    #[arg(long)]
+    tenants: bool,
+
     /// Run migrations in passive mode ( Effective just in up mode)
     #[arg(long)]
     passive: bool,
@@ -21,6 +107,18 @@ struct Cli {
     ///  Drop all tables and re-run all migrations
     #[arg(long)]
     fresh: bool,
+
+    /// Run seeders after migrations
+    #[arg(long)]
+    seed: bool,
+
+    /// Run seeders only, skip migrations
+    #[arg(long)]
+    seed_only: bool,
+
+    /// Seeder to run instead of `DatabaseSeeder`
+    #[arg(long)]
+    class: Option<String>,
 }
 
 
@@ -32,10 +130,97 @@ fn main() {
     let rt = Runtime::new().expect("failed to create tokio runtime");
     rt.block_on(async {
 
-        println!("Running database migrations{}",cli.rollback);
-        run_migrations(cli.rollback, cli.passive, cli.fresh)
-            .await.unwrap_or_else(|e|{
-            logger::error(&format!("{:?}", e));
-        });
+        let inspected = if cli.db_show {
+            Some(inspect::show(cli.json).await)
+        } else if let Some(table) = &cli.db_table {
+            Some(inspect::table(table, cli.json).await)
+        } else if cli.db_create {
+            Some(inspect::create().await)
+        } else if cli.db_wipe {
+            Some(inspect::wipe().await)
+        } else {
+            None
+        };
+        if let Some(result) = inspected {
+            if let Err(e) = result {
+                logger::error(&format!("{:?}", e));
+                // like a failed migrate, so scripts can tell
+                std::process::exit(1);
+            }
+            return;
+        }
+
+        if cli.db_dump {
+            let mode = if cli.data_only {
+                DumpMode::DataOnly
+            } else if cli.schema_only {
+                DumpMode::SchemaOnly
+            } else {
+                DumpMode::Full
+            };
+            let path = cli.output.clone().unwrap_or_else(default_dump_path);
+            if let Err(e) = dump(&cli.tables, mode, &path).await {
+                logger::error(&format!("{:?}", e));
+                std::process::exit(1);
+            }
+            return;
+        }
+
+        if let Some(path) = &cli.db_restore {
+            if let Err(e) = restore(path).await {
+                logger::error(&format!("{:?}", e));
+                std::process::exit(1);
+            }
+            return;
+        }
+
+        if let Some(format) = cli.schema_docs {
+            let dir = cli.output.clone().unwrap_or_else(default_docs_dir);
+            if let Err(e) = generate_docs(format, &dir).await {
+                logger::error(&format!("{:?}", e));
+                std::process::exit(1);
+            }
+            return;
+        }
+
+        if !cli.seed_only {
+            let mode = if cli.status {
+                MigrateMode::Status
+            } else if cli.schema_dump {
+                MigrateMode::SchemaDump { prune: cli.prune }
+            } else if cli.lint {
+                MigrateMode::Lint
+            } else if cli.repair_checksums {
+                MigrateMode::RepairChecksums
+            } else if cli.refresh {
+                MigrateMode::Refresh
+            } else if cli.reset {
+                MigrateMode::Reset
+            } else if cli.step > 0 {
+                MigrateMode::Step(cli.step)
+            } else if cli.rollback > 0 {
+                MigrateMode::Rollback(cli.rollback)
+            } else {
+                MigrateMode::Up { passive: cli.passive, fresh: cli.fresh }
+            };
+            let result = if cli.tenants {
+                run_tenant_migrations(mode).await
+            } else {
+                run_migrations(mode).await
+            };
+            if let Err(e) = result {
+                logger::error(&format!("{:?}", e));
+                // any failed migrate mode (lint errors, a checksum mismatch, a failed
+                // migration) exits with 1 for CI, and `--seed` doesn't run on top of it
+                std::process::exit(1);
+            }
+        }
+
+        if cli.seed || cli.seed_only {
+            run_seeders(cli.class.as_deref())
+                .await.unwrap_or_else(|e|{
+                logger::error(&format!("{:?}", e));
+            });
+        }
     });
 }
@@ -1,67 +1,418 @@
 
-use std::time::Instant;
-use crate::migrations::get_all_migrations;
-use async_trait::async_trait;
+use std::time::{Duration, Instant};
+use crate::migrations::{get_all_migrations, MIGRATIONS_DIR, MIGRATION_FILES, SCHEMA_DUMPS, SQUASHED_MIGRATIONS};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use colored::Colorize;
+use rustavel_core::config::CONFIG;
+use rustavel_core::config::database::{ChecksumPolicy, DatabaseEngine};
+use rustavel_core::db::lint::MigrationLinter;
+use rustavel_core::db::migration::checksum;
 use rustavel_core::db::schema::Schema;
 use rustavel_core::facades::terminal_ui::{*};
 use rustavel_core::sql::database_client::DbError;
 
-#[async_trait]
-pub trait Migration: Send + Sync {
-    async fn up(&self, schema: &mut Schema) -> Result<(), DbError>;
-    async fn down(&self, schema: &mut Schema) -> Result<(), DbError>;
-    fn name(&self) -> &'static str;
+pub use rustavel_core::db::migration::Migration;
+
+/// What `run_migrations` should do.
+#[derive(Debug, Clone, Copy)]
+pub enum MigrateMode {
+    /// Run pending migrations; `passive` only calls `up()`, `fresh` drops all tables first
+    Up { passive: bool, fresh: bool },
+    /// Undo the last `n` batches
+    Rollback(i64),
+    /// Undo the last `n` migrations, whatever batch they're in
+    Step(i64),
+    /// Undo every migration
+    Reset,
+    /// Undo every migration, then run them all again
+    Refresh,
+    /// Print which migrations ran and whether they were edited afterwards
+    Status,
+    /// Store the current checksum of every applied migration
+    RepairChecksums,
+    /// Write the database structure to `schema_dump_path()`; `prune` deletes the migrations it contains
+    SchemaDump { prune: bool },
+    /// Check the pending migrations without running them, fail on errors
+    Lint,
+}
+
+fn engine_name() -> &'static str {
+    match CONFIG.database.connection {
+        DatabaseEngine::Mysql => "mysql",
+        DatabaseEngine::Sqlite => "sqlite",
+    }
+}
+
+/// `database/schema/{mysql|sqlite}-schema.sql` under the working directory, where `schema:dump` writes.
+///
+/// The next build embeds it, see `stored_schema()`.
+pub fn schema_dump_path() -> PathBuf {
+    PathBuf::from("database")
+        .join("schema")
+        .join(format!("{}-schema.sql", engine_name()))
+}
+
+/// The schema dump of the configured engine this binary was built with, loaded by `migrate` on an empty database.
+pub fn stored_schema() -> Option<&'static str> {
+    SCHEMA_DUMPS
+        .iter()
+        .find(|(engine, _)| *engine == engine_name())
+        .map(|(_, script)| *script)
+}
+
+pub async fn run_migrations(mode: MigrateMode) -> Result<(), DbError> {
+    let mut schema = Schema::new().await?;
+    run_migrations_on(&mut schema, mode).await
+}
+
+/// `run_migrations` on a given connection, e.g. a tenant's `Schema::for_tenant()`.
+pub async fn run_migrations_on(schema: &mut Schema, mode: MigrateMode) -> Result<(), DbError> {
+    // passive mode and status don't write, so they don't need to wait for other migrators
+    if let MigrateMode::Up { passive: true, .. } | MigrateMode::Status | MigrateMode::Lint = mode {
+        return migrate(schema, mode).await;
+    }
+
+    let timeout = Duration::from_secs(CONFIG.database.migration_lock_timeout);
+    let stale_after = Duration::from_secs(CONFIG.database.migration_lock_stale_after);
+    let lock = match schema.lock_migrations(timeout, stale_after).await {
+        Ok(lock) => lock,
+        Err(DbError::Locked(holder)) => {
+            title(TitleKind::Error, "Another migrator is running, try again later or raise `DB_MIGRATION_LOCK_TIMEOUT`.");
+            return Err(DbError::Locked(holder));
+        }
+        Err(e) => return Err(e),
+    };
+
+    let result = migrate(schema, mode).await;
+    lock.release().await?;
+    result
+}
+
+async fn migrate(schema: &mut Schema, mode: MigrateMode) -> Result<(), DbError> {
+    match mode {
+        MigrateMode::Up { passive, fresh } => migrate_up(schema, passive, fresh).await,
+        MigrateMode::Rollback(batches) => {
+            if !schema.repository_exists().await? {
+                return migrate_down(schema, vec![]).await;
+            }
+            let last_batch = schema.get_next_batch_number().await? - 1;
+            let downs = schema.get_ran_migrations_gt(last_batch - batches).await?;
+            migrate_down(schema, downs).await
+        }
+        MigrateMode::Step(steps) => {
+            if !schema.repository_exists().await? {
+                return migrate_down(schema, vec![]).await;
+            }
+            let downs = schema.get_last_ran_migrations(steps.max(0) as u64).await?;
+            migrate_down(schema, downs).await
+        }
+        MigrateMode::Reset => reset(schema).await,
+        MigrateMode::Refresh => {
+            reset(schema).await?;
+            migrate_up(schema, false, false).await
+        }
+        MigrateMode::Status => status(schema).await,
+        MigrateMode::RepairChecksums => repair_checksums(schema).await,
+        MigrateMode::SchemaDump { prune } => schema_dump(schema, prune).await,
+        MigrateMode::Lint => {
+            if lint(schema, false).await? {
+                title(TitleKind::Success, "No problems found in pending migrations.");
+            }
+            Ok(())
+        }
+    }
 }
 
-pub async fn run_migrations(rollback: i64, passive: bool, fresh: bool) -> Result<(), DbError> {
+async fn schema_dump(schema: &mut Schema, prune: bool) -> Result<(), DbError> {
+    // the dump only loads under `DB_PREFIX`, tenants would lose the pruned migrations
+    if prune && CONFIG.tenancy.enabled() {
+        title(TitleKind::Error, "`--prune` is not available with tenancy, tenants still migrate from the migration files.");
+        return Err(DbError::InvalidQuery("schema:dump --prune with TENANCY_MODE on".to_string()));
+    }
+    let start = Instant::now();
+    let path = schema_dump_path();
+    let dump = schema.dump_schema().await?;
+    let written = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| std::fs::write(&path, dump));
+    if let Err(e) = written {
+        operation(&format!("Writing {}", path.display()), start.elapsed(), Status::Failed);
+        return Err(e.into());
+    }
+    operation(&format!("Dumped schema to {}", path.display()), start.elapsed(), Status::Done);
+
+    if prune {
+        // only what the dump recorded as ran, pending migrations stay
+        let ran = if schema.repository_exists().await? { schema.get_ran_migrations().await? } else { vec![] };
+        // recorded first, so `migrate` knows a database without the dump would miss these tables
+        let mut squashed: Vec<&str> = SQUASHED_MIGRATIONS.to_vec();
+        for (name, _) in MIGRATION_FILES {
+            if ran.iter().any(|r| r == name) && !squashed.contains(name) {
+                squashed.push(name);
+            }
+        }
+        let list = PathBuf::from(MIGRATIONS_DIR).join("squashed.txt");
+        std::fs::write(&list, squashed.join("\n") + "\n")?;
+
+        title(TitleKind::Info, "Pruning squashed migrations.");
+        for (name, file) in MIGRATION_FILES {
+            if ran.iter().any(|r| r == name) {
+                let start = Instant::now();
+                let status = match std::fs::remove_file(file) {
+                    Ok(_) => Status::Done,
+                    Err(_) => Status::Failed,
+                };
+                operation(name, start.elapsed(), status);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Load `stored_schema()` into an empty database, its `migrations` rows mark the squashed migrations as ran.
+///
+/// Fails when migrations were pruned but there is no dump to load here, running
+/// only the remaining ones would leave the squashed tables out.
+async fn load_stored_schema(schema: &mut Schema) -> Result<(), DbError> {
+    // the dump names the tables of `DB_PREFIX`
+    let script = stored_schema().filter(|_| schema.prefix() == CONFIG.database.prefix);
+    match script {
+        Some(script) => {
+            let start = Instant::now();
+            schema.load_schema(script).await?;
+            operation("Loading stored database schema", start.elapsed(), Status::Done);
+            Ok(())
+        }
+        None if !SQUASHED_MIGRATIONS.is_empty() => {
+            let message = format!(
+                "{} squashed migration(s) need the {} schema dump of `DB_PREFIX`, run `schema:dump` where they still ran and rebuild",
+                SQUASHED_MIGRATIONS.len(),
+                engine_name()
+            );
+            title(TitleKind::Error, &message);
+            Err(DbError::InvalidQuery(message))
+        }
+        None => Ok(()),
+    }
+}
+
+/// Build a migration without running it and hash the SQL it would execute.
+async fn build_checksum(schema: &mut Schema, mig: &dyn Migration) -> Result<String, DbError> {
+    schema.clear_current();
+    // whatever `up()` runs itself is recorded, not executed
+    schema.begin_dry_run();
+    let built = mig.up(schema).await;
+    schema.end_dry_run();
+    built?;
+    Ok(checksum(&schema.migration_sql().unwrap_or_default()))
+}
+
+/// Applied migrations whose stored checksum differs from the current code.
+async fn changed_migrations(schema: &mut Schema) -> Result<Vec<String>, DbError> {
+    let stored: HashMap<String, Option<String>> = schema
+        .get_migration_records()
+        .await?
+        .into_iter()
+        .map(|record| (record.migration, record.checksum))
+        .collect();
+
+    let mut changed = vec![];
+    for mig in get_all_migrations() {
+        if let Some(Some(old)) = stored.get(mig.name()) {
+            let current = build_checksum(schema, mig.as_ref()).await?;
+            if &current != old {
+                changed.push(mig.name().to_string());
+            }
+        }
+    }
+    Ok(changed)
+}
+
+/// Report applied migrations that were edited after they ran, as `migrate` does
+/// before running new ones; `ChecksumPolicy::Fail` turns them into an error.
+pub async fn check_checksums(schema: &mut Schema, policy: &ChecksumPolicy) -> Result<(), DbError> {
+    if *policy == ChecksumPolicy::Off {
+        return Ok(());
+    }
+    let changed = changed_migrations(schema).await?;
+    if changed.is_empty() {
+        return Ok(());
+    }
+    let message = format!(
+        "Applied migrations were edited after they ran: {} (see `migrate:status`, or `migrate --repair-checksums` if intended)",
+        changed.join(", ")
+    );
+    if *policy == ChecksumPolicy::Fail {
+        title(TitleKind::Error, &message);
+        return Err(DbError::ChecksumMismatch(changed));
+    }
+    title(TitleKind::Warn, &message);
+    Ok(())
+}
+
+async fn status(schema: &mut Schema) -> Result<(), DbError> {
+    if !schema.repository_exists().await? {
+        title(TitleKind::Info,"Migration table not found.");
+        return Ok(());
+    }
+    // status is read-only and runs without the migration lock, `migrate` adds the column
+    let has_checksums = schema.has_column("migrations", "checksum").await?;
+
+    let records: HashMap<String, (i64, Option<String>)> = schema
+        .get_migration_records()
+        .await?
+        .into_iter()
+        .map(|record| (record.migration, (record.batch, record.checksum)))
+        .collect();
+
+    title(TitleKind::Info,"Migration status.");
+    for mig in get_all_migrations() {
+        let value = match records.get(mig.name()) {
+            None => "Pending".yellow().bold().to_string(),
+            Some((batch, _)) if !has_checksums => format!("[{}] {}", batch, "Ran, checksum unknown".yellow()),
+            Some((batch, stored)) => {
+                let current = build_checksum(schema, mig.as_ref()).await?;
+                match stored {
+                    Some(old) if old != &current => format!("[{}] {}", batch, "Changed".red().bold()),
+                    Some(_) => format!("[{}] {}", batch, "Ran".green().bold()),
+                    None => format!("[{}] {}", batch, "Ran, no checksum".yellow()),
+                }
+            }
+        };
+        line(mig.name(), &value);
+    }
+    Ok(())
+}
+
+async fn repair_checksums(schema: &mut Schema) -> Result<(), DbError> {
+    if !schema.repository_exists().await? {
+        title(TitleKind::Info,"Migration table not found.");
+        return Ok(());
+    }
+    schema.ensure_migration_checksum_column().await?;
+
+    let ran = schema.get_ran_migrations().await?;
+    title(TitleKind::Info,"Repairing migration checksums.");
+    for mig in get_all_migrations() {
+        if ran.contains(&mig.name().to_string()) {
+            let start = Instant::now();
+            let current = build_checksum(schema, mig.as_ref()).await?;
+            schema.set_migration_checksum(mig.name(), &current).await?;
+            operation(mig.name(), start.elapsed(), Status::Done);
+        }
+    }
+    Ok(())
+}
+
+/// Lint the migrations `migrate` would run, against the tables that exist now.
+///
+/// Prints every problem and returns whether there were none; errors fail with
+/// `DbError::MigrationLint`. `fresh` lints every migration against an empty database.
+async fn lint(schema: &mut Schema, fresh: bool) -> Result<bool, DbError> {
+    let exists = !fresh && schema.repository_exists().await?;
+    let ran = if exists { schema.get_ran_migrations().await? } else { vec![] };
+    let tables = if fresh { vec![] } else { schema.introspect().await? };
+    let mut linter = MigrationLinter::new(CONFIG.database.connection.clone(), schema.prefix()).with_tables(&tables);
+    if !exists && stored_schema().is_some() && schema.prefix() == CONFIG.database.prefix {
+        // `migrate` loads the dump first, its tables aren't known yet
+        linter = linter.allow_unknown_tables();
+    }
+
+    let mut issues = vec![];
+    // `up()` only builds the definitions, any SQL it runs itself is recorded, not executed
+    schema.begin_dry_run();
+    let mut built = Ok(());
+    for mig in get_all_migrations() {
+        if ran.contains(&mig.name().to_string()) {
+            continue;
+        }
+        schema.clear_current();
+        built = mig.up(schema).await;
+        if built.is_err() {
+            break;
+        }
+        if let Some(table) = schema.current_table() {
+            issues.extend(linter.lint(mig.name(), table));
+        }
+    }
+    schema.end_dry_run();
+    schema.clear_current();
+    built?;
+
+    if issues.is_empty() {
+        return Ok(true);
+    }
+    title(TitleKind::Warn, "Migration lint.");
+    for issue in &issues {
+        let label = if issue.is_error() { "error".red().bold() } else { "warning".yellow().bold() };
+        println!(" {} {}", label, issue);
+    }
+    let errors: Vec<String> = issues.iter().filter(|i| i.is_error()).map(ToString::to_string).collect();
+    if errors.is_empty() {
+        return Ok(false);
+    }
+    title(TitleKind::Error, &format!("{} migration lint error(s), nothing was migrated.", errors.len()));
+    Err(DbError::MigrationLint(errors))
+}
+
+async fn reset(schema: &mut Schema) -> Result<(), DbError> {
+    if !schema.repository_exists().await? {
+        return migrate_down(schema, vec![]).await;
+    }
+    let downs = schema.get_ran_migrations_gt(0).await?;
+    migrate_down(schema, downs).await
+}
+
+async fn migrate_up(schema: &mut Schema, passive: bool, fresh: bool) -> Result<(), DbError> {
     let migrations = get_all_migrations();
     let mut batch = 1;
-    let mut schema = Schema::new().await?;
     let mut migrated_count = 0;
-    let mut start = Instant::now();
+    // definitions are checked before anything, `--fresh` included, touches the database
+    if !passive {
+        lint(schema, fresh).await?;
+    }
     let migration_list : Vec<String> =  if !passive {
         if fresh {
-            start = Instant::now();
+            let start = Instant::now();
             schema.drop_all_tables().await?;
             operation( "Dropping all tables" ,start.elapsed(),Status::Done)
         }
         // check migration table
         if !schema.repository_exists().await? {
-            start = Instant::now();
             title(TitleKind::Info,"Preparing database.");
-            schema.create_migration_table().await?;
+            load_stored_schema(schema).await?;
+            if !schema.repository_exists().await? {
+                schema.create_migration_table().await?;
+            }
         }
+        schema.ensure_migration_checksum_column().await?;
         batch = schema.get_next_batch_number().await?;
         schema.get_ran_migrations().await?
     } else {
         vec![]
     };
-    let downs = schema.get_ran_migrations_gt(batch - (rollback + 1)).await?;
-
 
+    // edited migrations are reported before anything new runs
+    if !passive {
+        check_checksums(schema, &CONFIG.database.migration_checksum).await?;
+    }
 
     title(TitleKind::Info,"Running migrations.");
     for mig in migrations {
-        start = Instant::now();
-        if rollback <= 0 {
-            mig.up(&mut schema).await?;
-            if !passive && !migration_list.contains(&mig.name().to_string()) {
-                // run migration
-                schema.execute_migration(mig.name(),&start.into()).await?;
-                // add to table
-                schema.add_migrated_table(mig.name(),batch).await?;
-
-                migrated_count += 1;
+        let start = Instant::now();
+        schema.clear_current();
+        mig.up(schema).await?;
+        if !passive && !migration_list.contains(&mig.name().to_string()) {
+            let sql = schema.migration_sql().unwrap_or_default();
+            // run migration
+            schema.execute_migration(mig.name(),&start.into()).await?;
+            // add to table
+            schema.add_migrated_table(mig.name(),batch).await?;
+            schema.set_migration_checksum(mig.name(), &checksum(&sql)).await?;
 
-            }
-        } else {
-            // println!("Rolling back {}, {:?}, {} , {}", mig.name(), downs, batch, batch - (rollback + 1));
-            if downs.contains(&mig.name().to_string()) {
-                mig.down(&mut schema).await?;
-                migrated_count += 1;
-                schema.rem_migrated_table(mig.name()).await?;
-                operation(mig.name(), start.elapsed(), Status::Done);
-            }
+            migrated_count += 1;
         }
     }
 
@@ -70,3 +421,29 @@ pub async fn run_migrations(rollback: i64, passive: bool, fresh: bool) -> Result
     }
     Ok(())
 }
+
+/// Roll back the given migrations, in the given order (newest first).
+async fn migrate_down(schema: &mut Schema, downs: Vec<String>) -> Result<(), DbError> {
+    if downs.is_empty() {
+        title(TitleKind::Info,"Nothing to rollback");
+        return Ok(());
+    }
+
+    let migrations = get_all_migrations();
+    title(TitleKind::Info,"Rolling back migrations.");
+    for name in downs {
+        let start = Instant::now();
+        match migrations.iter().find(|mig| mig.name() == name) {
+            Some(mig) => {
+                mig.down(schema).await?;
+                schema.rem_migrated_table(mig.name()).await?;
+                operation(mig.name(), start.elapsed(), Status::Done);
+            }
+            None => {
+                // ran once but its file is gone, keep the record so it can be found later
+                operation(&format!("{} (migration not found)", name), start.elapsed(), Status::Failed);
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,252 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use crate::migrations::get_all_migrations;
+use rustavel_core::db::introspection::TableInfo;
+use rustavel_core::db::schema::Schema;
+use rustavel_core::facades::terminal_ui::{*};
+use rustavel_core::sql::database_client::DbError;
+
+/// Diagram flavour written by `schema:docs`; the table reference is always markdown.
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum DocsFormat {
+    /// `er-diagram.mmd`, rendered by GitHub / GitLab and most markdown viewers
+    Mermaid,
+    /// `er-diagram.dot`, for Graphviz (`dot -Tsvg er-diagram.dot`)
+    Dot,
+    /// `er-diagram.md`, the mermaid diagram wrapped in a markdown page
+    Markdown,
+}
+
+/// `database/docs`, where `schema:docs` writes unless told otherwise.
+pub fn default_docs_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("docs")
+}
+
+/// Introspect the database and write the ER diagram plus `tables.md` into `dir`.
+pub async fn generate_docs(format: DocsFormat, dir: &Path) -> Result<(), DbError> {
+    let start = Instant::now();
+    let mut schema = Schema::new().await?;
+
+    let declared = declared_comments(&mut schema).await?;
+    let mut tables: Vec<TableInfo> = schema
+        .introspect()
+        .await?
+        .into_iter()
+        // bookkeeping, not part of the application's model
+        .filter(|table| table.name != schema.migrations_table())
+        .collect();
+
+    // SQLite keeps no comments and MySQL gets no table comments, so fall back to the migrations
+    for table in &mut tables {
+        if let Some((table_comment, columns)) = declared.get(&table.name) {
+            if table.comment.is_empty() {
+                table.comment = table_comment.clone();
+            }
+            for column in &mut table.columns {
+                if column.comment.is_empty() && let Some(comment) = columns.get(&column.name) {
+                    column.comment = comment.clone();
+                }
+            }
+        }
+    }
+    operation(&format!("Introspected {} tables", tables.len()), start.elapsed(), Status::Done);
+
+    std::fs::create_dir_all(dir)?;
+    for (file, content) in render_docs(format, &tables) {
+        let start = Instant::now();
+        let path = dir.join(file);
+        std::fs::write(&path, content)?;
+        operation(&path.display().to_string(), start.elapsed(), Status::Done);
+    }
+    Ok(())
+}
+
+/// The files `schema:docs` writes for `tables`, as `(file name, content)`:
+/// the ER diagram in `format`, then `tables.md`.
+pub fn render_docs(format: DocsFormat, tables: &[TableInfo]) -> Vec<(&'static str, String)> {
+    let diagram = match format {
+        DocsFormat::Mermaid => ("er-diagram.mmd", mermaid(tables)),
+        DocsFormat::Dot => ("er-diagram.dot", dot(tables)),
+        DocsFormat::Markdown => (
+            "er-diagram.md",
+            format!("# ER diagram\n\n```mermaid\n{}```\n", mermaid(tables)),
+        ),
+    };
+    vec![diagram, ("tables.md", markdown_reference(tables))]
+}
+
+/// Table and column comments as written in the migrations, keyed by table name.
+async fn declared_comments(
+    schema: &mut Schema,
+) -> Result<HashMap<String, (String, HashMap<String, String>)>, DbError> {
+    let mut declared: HashMap<String, (String, HashMap<String, String>)> = HashMap::new();
+    for mig in get_all_migrations() {
+        schema.clear_current();
+        schema.begin_dry_run();
+        let built = mig.up(schema).await;
+        schema.end_dry_run();
+        built?;
+        let Some(table) = schema.current_table() else { continue };
+
+        let entry = declared.entry(table.name.clone()).or_default();
+        if !table.comment.is_empty() {
+            entry.0 = table.comment.clone();
+        }
+        for column in table.columns.iter().filter(|c| !c.comment.is_empty()) {
+            entry.1.insert(column.name.clone(), column.comment.clone());
+        }
+    }
+    schema.clear_current();
+    Ok(declared)
+}
+
+fn mermaid(tables: &[TableInfo]) -> String {
+    let mut out = String::from("erDiagram\n");
+    for table in tables {
+        out.push_str(&format!("    {} {{\n", table.name));
+        for column in &table.columns {
+            let keys = match (column.primary, table.is_foreign(&column.name)) {
+                (true, true) => " PK, FK",
+                (true, false) => " PK",
+                (false, true) => " FK",
+                (false, false) => "",
+            };
+            let comment = if column.comment.is_empty() {
+                String::new()
+            } else {
+                format!(" \"{}\"", column.comment.replace('"', "'"))
+            };
+            out.push_str(&format!(
+                "        {} {}{}{}\n",
+                mermaid_type(&column.data_type),
+                column.name,
+                keys,
+                comment
+            ));
+        }
+        out.push_str("    }\n");
+    }
+    for table in tables {
+        for key in &table.foreign_keys {
+            let nullable = table.column(&key.column).is_some_and(|c| c.nullable);
+            out.push_str(&format!(
+                "    {} }}o--{} {} : \"{}\"\n",
+                table.name,
+                if nullable { "o|" } else { "||" },
+                key.foreign_table,
+                key.column
+            ));
+        }
+    }
+    out
+}
+
+/// Mermaid types must be one word: `varchar(127)` becomes `varchar`.
+fn mermaid_type(data_type: &str) -> String {
+    let word: String = data_type
+        .split(|c: char| c == '(' || c.is_whitespace())
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    if word.is_empty() { "unknown".to_string() } else { word }
+}
+
+fn dot(tables: &[TableInfo]) -> String {
+    let mut out = String::from(
+        "digraph schema {\n    rankdir=LR;\n    node [shape=plaintext, fontname=\"Helvetica\"];\n\n",
+    );
+    for table in tables {
+        let mut label = format!(
+            "<table border=\"0\" cellborder=\"1\" cellspacing=\"0\"><tr><td bgcolor=\"lightgrey\"><b>{}</b></td></tr>",
+            html_escape(&table.name)
+        );
+        for column in &table.columns {
+            let name = if column.primary {
+                format!("<u>{}</u>", html_escape(&column.name))
+            } else {
+                html_escape(&column.name)
+            };
+            label.push_str(&format!(
+                "<tr><td align=\"left\" port=\"{}\">{} : {}</td></tr>",
+                html_escape(&column.name),
+                name,
+                html_escape(&column.data_type)
+            ));
+        }
+        label.push_str("</table>");
+        out.push_str(&format!("    \"{}\" [label=<{}>];\n", table.name, label));
+    }
+    out.push('\n');
+    for table in tables {
+        for key in &table.foreign_keys {
+            out.push_str(&format!(
+                "    \"{}\":\"{}\" -> \"{}\":\"{}\";\n",
+                table.name, key.column, key.foreign_table, key.referenced_column
+            ));
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn markdown_reference(tables: &[TableInfo]) -> String {
+    let mut out = String::from("# Database tables\n\nGenerated by `artisan schema:docs`, do not edit.\n\n");
+    for table in tables {
+        out.push_str(&format!("- [{}](#{})\n", table.name, table.name.to_lowercase()));
+    }
+
+    for table in tables {
+        out.push_str(&format!("\n## {}\n\n", table.name));
+        if !table.comment.is_empty() {
+            out.push_str(&format!("{}\n\n", table.comment));
+        }
+        out.push_str("| Column | Type | Nullable | Default | Key | Comment |\n");
+        out.push_str("|--------|------|----------|---------|-----|---------|\n");
+        for column in &table.columns {
+            let key = match (column.primary, table.is_foreign(&column.name)) {
+                (true, true) => "PK, FK",
+                (true, false) => "PK",
+                (false, true) => "FK",
+                (false, false) => "",
+            };
+            out.push_str(&format!(
+                "| `{}` | {} | {} | {} | {} | {} |\n",
+                column.name,
+                cell(&column.data_type),
+                if column.nullable { "yes" } else { "no" },
+                column.default.as_deref().map(cell).unwrap_or_default(),
+                key,
+                cell(&column.comment)
+            ));
+        }
+
+        if !table.foreign_keys.is_empty() {
+            out.push_str("\n**Foreign keys**\n\n");
+            for key in &table.foreign_keys {
+                out.push_str(&format!(
+                    "- `{}` → [`{}.{}`](#{})\n",
+                    key.column,
+                    key.foreign_table,
+                    key.referenced_column,
+                    key.foreign_table.to_lowercase()
+                ));
+            }
+        }
+    }
+    out
+}
+
+/// Keep `|` and line breaks from breaking the markdown table.
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
@@ -0,0 +1,51 @@
+use std::time::Instant;
+use crate::seeders::get_all_seeders;
+use async_trait::async_trait;
+use rustavel_core::db::schema::Schema;
+use rustavel_core::facades::terminal_ui::{*};
+use rustavel_core::sql::database_client::DbError;
+
+/// Root seeder used when no `--class` is given.
+pub const ROOT_SEEDER: &str = "DatabaseSeeder";
+
+#[async_trait]
+pub trait Seeder: Send + Sync {
+    async fn run(&self, schema: &mut Schema) -> Result<(), DbError>;
+    fn name(&self) -> &'static str;
+}
+
+/// Run the given child seeders in order, like Laravel's `$this->call([...])`.
+pub async fn call(schema: &mut Schema, seeders: Vec<Box<dyn Seeder>>) -> Result<(), DbError> {
+    for seeder in seeders {
+        let start = Instant::now();
+        match seeder.run(schema).await {
+            Ok(_) => operation(seeder.name(), start.elapsed(), Status::Done),
+            Err(e) => {
+                operation(seeder.name(), start.elapsed(), Status::Failed);
+                return Err(e);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Run `DatabaseSeeder`, or the seeder registered under `class`.
+pub async fn run_seeders(class: Option<&str>) -> Result<(), DbError> {
+    let wanted = class.unwrap_or(ROOT_SEEDER);
+    let mut schema = Schema::new().await?;
+
+    let seeder = get_all_seeders()
+        .into_iter()
+        .find(|seeder| seeder.name() == wanted);
+
+    match seeder {
+        Some(seeder) => {
+            title(TitleKind::Info, "Seeding database.");
+            call(&mut schema, vec![seeder]).await
+        }
+        None => {
+            title(TitleKind::Error, &format!("Seeder `{}` not found.", wanted));
+            Err(DbError::NotFound)
+        }
+    }
+}
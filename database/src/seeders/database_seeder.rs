@@ -0,0 +1,18 @@
+use rustavel_core::db::schema::Schema;
+use crate::seeder::{call, Seeder};
+use async_trait::async_trait;
+use rustavel_core::sql::database_client::DbError;
+
+pub struct DatabaseSeeder;
+
+#[async_trait]
+impl Seeder for DatabaseSeeder {
+    async fn run(&self, schema: &mut Schema) -> Result<(), DbError> {
+        // list child seeders here, e.g. `Box::new(super::todo_seeder::TodoSeeder {})`
+        call(schema, vec![]).await
+    }
+
+    fn name(&self) -> &'static str {
+        "DatabaseSeeder"
+    }
+}
@@ -0,0 +1,10 @@
+use crate::seeder::Seeder;
+pub mod database_seeder;
+// #[placeholder-add-seeder-mods] DO NOT REMOVE THIS COMMENT, OTHERWISE AUTOMATIC ADD WILL BREAK
+
+pub fn get_all_seeders() -> Vec<Box<dyn Seeder>> {
+    vec![
+        Box::new(database_seeder::DatabaseSeeder {}),
+        // #[placeholder-add-seeder-trait] DO NOT REMOVE THIS COMMENT, OTHERWISE AUTOMATIC ADD WILL BREAK
+    ]
+}
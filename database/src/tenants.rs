@@ -0,0 +1,48 @@
+use std::time::Instant;
+use crate::migrator::{run_migrations_on, MigrateMode};
+use rustavel_core::config::CONFIG;
+use rustavel_core::config::database::DatabaseEngine;
+use rustavel_core::config::tenancy::TenancyMode;
+use rustavel_core::db::schema::Schema;
+use rustavel_core::db::tenancy::{registered, Tenant};
+use rustavel_core::facades::terminal_ui::{*};
+use rustavel_core::sql::database_client::DbError;
+
+/// Run `mode` for every tenant in `TENANTS`, each recorded in its own `migrations` table.
+///
+/// Stops at the first tenant that fails, the ones before it stay migrated.
+pub async fn run_tenant_migrations(mode: MigrateMode) -> Result<(), DbError> {
+    if !CONFIG.tenancy.enabled() {
+        title(TitleKind::Error, "Tenancy is off, set `TENANCY_MODE` to `prefix` or `database`.");
+        return Err(DbError::InvalidQuery("TENANCY_MODE is off".to_string()));
+    }
+    let tenants = registered();
+    if tenants.is_empty() {
+        title(TitleKind::Warn, "No tenants registered in `TENANTS`.");
+        return Ok(());
+    }
+
+    for tenant in tenants {
+        title(TitleKind::Info, &format!("Tenant {}.", tenant.id()));
+        let mut schema = connect(&tenant).await?;
+        run_migrations_on(&mut schema, mode).await?;
+    }
+    Ok(())
+}
+
+/// Connect to a tenant, creating its MySQL database on first use.
+async fn connect(tenant: &Tenant) -> Result<Schema, DbError> {
+    if CONFIG.tenancy.mode == TenancyMode::Database && CONFIG.database.connection == DatabaseEngine::Mysql {
+        if let Ok(schema) = Schema::for_tenant(tenant).await {
+            return Ok(schema);
+        }
+        let start = Instant::now();
+        let name = tenant.database();
+        if !Schema::without_database().await?.create_database(&name).await {
+            operation(&format!("Creating database {}", name), start.elapsed(), Status::Failed);
+            return Err(DbError::InvalidQuery(format!("could not create database `{}`", name)));
+        }
+        operation(&format!("Created database {}", name), start.elapsed(), Status::Done);
+    }
+    Ok(Schema::for_tenant(tenant).await?)
+}
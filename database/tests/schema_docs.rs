@@ -0,0 +1,73 @@
+use rustavel_core::db::introspection::{ColumnInfo, ForeignKeyInfo, TableInfo};
+use rustavel_db::schema_docs::{render_docs, DocsFormat};
+use std::path::PathBuf;
+
+fn column(name: &str, data_type: &str, nullable: bool, primary: bool, comment: &str) -> ColumnInfo {
+    ColumnInfo {
+        name: name.to_string(),
+        data_type: data_type.to_string(),
+        nullable,
+        default: None,
+        primary,
+        comment: comment.to_string(),
+    }
+}
+
+/// `users` and `posts`, where `posts.user_id` references `users.id`.
+fn tables() -> Vec<TableInfo> {
+    let mut users = TableInfo {
+        name: "users".to_string(),
+        comment: "Registered accounts".to_string(),
+        columns: vec![
+            column("id", "bigint unsigned", false, true, ""),
+            column("email", "varchar(127)", false, false, "login | contact"),
+        ],
+        indexes: vec![],
+        foreign_keys: vec![],
+    };
+    users.columns[1].default = Some("''".to_string());
+    let posts = TableInfo {
+        name: "posts".to_string(),
+        comment: String::new(),
+        columns: vec![
+            column("id", "bigint unsigned", false, true, ""),
+            column("user_id", "bigint unsigned", true, false, "\"author\""),
+            column("title", "varchar(255)", false, false, ""),
+        ],
+        indexes: vec![],
+        foreign_keys: vec![ForeignKeyInfo {
+            column: "user_id".to_string(),
+            foreign_table: "users".to_string(),
+            referenced_column: "id".to_string(),
+        }],
+    };
+    vec![users, posts]
+}
+
+/// Compares `actual` with `tests/golden/<file>`; `GOLDEN_OVERWRITE=1` rewrites the file instead.
+fn assert_golden(file: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden").join(file);
+    if std::env::var_os("GOLDEN_OVERWRITE").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+    assert_eq!(actual, expected, "output differs from {}", path.display());
+}
+
+#[test]
+fn docs_match_golden_files() {
+    let tables = tables();
+    for (format, diagram) in [
+        (DocsFormat::Mermaid, "er-diagram.mmd"),
+        (DocsFormat::Dot, "er-diagram.dot"),
+        (DocsFormat::Markdown, "er-diagram.md"),
+    ] {
+        let files = render_docs(format, &tables);
+        let names: Vec<&str> = files.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, vec![diagram, "tables.md"]);
+        assert_golden(diagram, &files[0].1);
+        assert_golden("tables.md", &files[1].1);
+    }
+}
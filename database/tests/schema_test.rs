@@ -0,0 +1,336 @@
+use rustavel_core::facades::db::DB;
+use rustavel_core::sql::database_client::DbError;
+use rustavel_core::sql::value::DbValue;
+use rustavel_core::sql::script::DumpMode;
+use rustavel_core::testing::TestDatabase;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use rustavel_db::migrations::get_all_migrations;
+use rustavel_db::migrator::{check_checksums, run_migrations_on, MigrateMode};
+use rustavel_core::config::database::ChecksumPolicy;
+use rustavel_core::db::schema::Schema;
+
+#[tokio::test]
+async fn migrations_run_on_test_database() {
+    let db = TestDatabase::memory(get_all_migrations).await.unwrap();
+    assert!(db.schema().has_table("todos").await.unwrap());
+    assert!(db.schema().has_column("todos", "title").await.unwrap());
+    db.assert_database_count("migrations", get_all_migrations().len() as i64).await;
+}
+
+#[tokio::test]
+async fn assertions_truncate_and_refresh() {
+    let mut db = TestDatabase::temp_file(get_all_migrations).await.unwrap();
+    db.schema()
+        .insert("todos", &[("title", "x"), ("done", "0")])
+        .await
+        .unwrap();
+    db.assert_database_has("todos", [("title", "x"), ("done", "0")]).await;
+    db.assert_database_missing("todos", [("title", "y")]).await;
+
+    db.truncate().await.unwrap();
+    db.assert_database_missing("todos", [("title", "x")]).await;
+    db.assert_database_count("migrations", get_all_migrations().len() as i64).await;
+
+    db.schema().insert("todos", &[("title", "z")]).await.unwrap();
+    db.refresh().await.unwrap();
+    db.assert_database_count("todos", 0).await;
+}
+
+const HOUR: Duration = Duration::from_secs(3600);
+
+#[tokio::test]
+async fn migration_lock_is_exclusive() {
+    let db = TestDatabase::memory(get_all_migrations).await.unwrap();
+    let lock = db.schema().lock_migrations(Duration::ZERO, HOUR).await.unwrap();
+
+    match db.schema().lock_migrations(Duration::ZERO, HOUR).await {
+        Err(DbError::Locked(holder)) => assert!(holder.contains(&std::process::id().to_string())),
+        other => panic!("expected Locked, got {:?}", other),
+    }
+
+    lock.release().await.unwrap();
+    db.schema()
+        .lock_migrations(Duration::ZERO, HOUR)
+        .await
+        .unwrap()
+        .release()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn stale_migration_lock_is_taken_over() {
+    let db = TestDatabase::memory(get_all_migrations).await.unwrap();
+    db.schema().lock_migrations(Duration::ZERO, HOUR).await.unwrap();
+
+    // a fresh lock left behind still blocks, one older than `stale_after` doesn't
+    match db.schema().lock_migrations(Duration::ZERO, HOUR).await {
+        Err(DbError::Locked(message)) => assert!(message.contains("DELETE FROM migrations_lock")),
+        other => panic!("expected Locked, got {:?}", other),
+    }
+    db.schema()
+        .execute_params("UPDATE migrations_lock SET acquired_at = '2001-01-01 00:00:00'", &[])
+        .await
+        .unwrap();
+    db.schema()
+        .lock_migrations(Duration::ZERO, HOUR)
+        .await
+        .unwrap()
+        .release()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn ran_migrations_come_back_newest_first() {
+    let mut db = TestDatabase::memory(Vec::new).await.unwrap();
+    assert_eq!(db.schema().get_next_batch_number().await.unwrap(), 1);
+
+    let schema = db.schema_mut();
+    schema.add_migrated_table("m_a", 1).await.unwrap();
+    schema.add_migrated_table("m_b", 2).await.unwrap();
+    schema.add_migrated_table("m_c", 2).await.unwrap();
+    schema.add_migrated_table("m_d", 3).await.unwrap();
+
+    assert_eq!(db.schema().get_next_batch_number().await.unwrap(), 4);
+    assert_eq!(
+        db.schema().get_ran_migrations_gt(1).await.unwrap(),
+        vec!["m_d", "m_c", "m_b"]
+    );
+    assert_eq!(
+        db.schema().get_last_ran_migrations(2).await.unwrap(),
+        vec!["m_d", "m_c"]
+    );
+}
+
+#[tokio::test]
+async fn schema_dump_loads_into_empty_database() {
+    let source = TestDatabase::memory(get_all_migrations).await.unwrap();
+    let dump = source.schema().dump_schema().await.unwrap();
+
+    let target = TestDatabase::memory(Vec::new).await.unwrap();
+    target.schema().drop_all_tables().await.unwrap();
+    target.schema().load_schema(&dump).await.unwrap();
+
+    assert!(target.schema().has_table("todos").await.unwrap());
+    assert_eq!(
+        target.schema().get_migration_records().await.unwrap(),
+        source.schema().get_migration_records().await.unwrap()
+    );
+}
+
+#[tokio::test]
+async fn table_info_reads_columns_back() {
+    let db = TestDatabase::memory(get_all_migrations).await.unwrap();
+    let todos = db.schema().get_table_info("todos").await.unwrap();
+
+    assert!(todos.column("id").unwrap().primary);
+    let title = todos.column("title").unwrap();
+    assert_eq!(title.data_type, "varchar(127)");
+    assert!(!title.nullable);
+    assert!(todos.column("created_at").unwrap().nullable);
+    assert!(todos.foreign_keys.is_empty());
+
+    let index = todos.indexes.iter().find(|i| i.name == "todos_title_index").unwrap();
+    assert_eq!(index.columns, ["title"]);
+    assert!(!index.unique);
+}
+
+#[tokio::test]
+async fn database_dump_restores_rows() {
+    let source = TestDatabase::memory(get_all_migrations).await.unwrap();
+    let tricky = "it's a \\ test; -- not a comment";
+    source.schema().insert("todos", &[("title", tricky), ("done", "1")]).await.unwrap();
+    let dump = source.schema().dump_database(&[], DumpMode::Full).await.unwrap();
+
+    let target = TestDatabase::memory(get_all_migrations).await.unwrap();
+    target.schema().load_schema(&dump).await.unwrap();
+    target.assert_database_has("todos", [("title", tricky), ("done", "1")]).await;
+    target.assert_database_count("migrations", get_all_migrations().len() as i64).await;
+}
+
+#[tokio::test]
+async fn dump_tables_are_named_without_the_prefix() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    let mut acme = Schema::from_sqlite_pool(pool, "acme_");
+    run_migrations_on(&mut acme, MigrateMode::Up { passive: false, fresh: false }).await.unwrap();
+    acme.insert("todos", &[("title", "acme"), ("done", "0")]).await.unwrap();
+
+    let dump = acme.dump_database(&["todos".to_string()], DumpMode::DataOnly).await.unwrap();
+    assert!(dump.contains("acme_todos") && dump.contains("'acme'"));
+    assert!(!dump.contains("acme_migrations"));
+
+    match acme.dump_database(&["todos".to_string(), "todo".to_string()], DumpMode::Full).await {
+        Err(DbError::InvalidQuery(message)) => assert!(message.ends_with("todo")),
+        other => panic!("expected an unknown table error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn listeners_see_statements_and_bindings() {
+    let db = TestDatabase::memory(get_all_migrations).await.unwrap();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let sink = seen.clone();
+    // other tests run concurrently, keep only this test's insert
+    let id = DB::listen(move |query| {
+        if query.bindings.contains(&DbValue::from("listened")) {
+            sink.lock().unwrap().push(query.clone());
+        }
+    });
+
+    db.schema().insert("todos", &[("title", "listened")]).await.unwrap();
+    DB::forget(id);
+    db.schema().insert("todos", &[("title", "listened")]).await.unwrap();
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), 1);
+    assert!(seen[0].sql.starts_with("INSERT INTO \"todos\""));
+    assert_eq!(seen[0].connection, "sqlite");
+    assert!(!seen[0].failed);
+}
+
+#[tokio::test]
+async fn prefixed_tenants_keep_their_own_migrations() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    let mut acme = Schema::from_sqlite_pool(pool.clone(), "acme_");
+    let mut globex = Schema::from_sqlite_pool(pool, "globex_");
+    let up = MigrateMode::Up { passive: false, fresh: false };
+
+    run_migrations_on(&mut acme, up).await.unwrap();
+    assert!(acme.has_table("todos").await.unwrap());
+    assert!(!globex.has_table("todos").await.unwrap());
+    assert_eq!(acme.migrations_table(), "acme_migrations");
+    assert_eq!(acme.get_ran_migrations().await.unwrap().len(), get_all_migrations().len());
+
+    run_migrations_on(&mut globex, up).await.unwrap();
+    assert_eq!(globex.get_ran_migrations().await.unwrap(), acme.get_ran_migrations().await.unwrap());
+    assert_eq!(globex.get_next_batch_number().await.unwrap(), 2);
+}
+
+#[tokio::test]
+async fn fresh_only_drops_the_tenants_own_tables() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    let mut app = Schema::from_sqlite_pool(pool.clone(), "");
+    let mut acme = Schema::from_sqlite_pool(pool.clone(), "acme_");
+    let mut globex = Schema::from_sqlite_pool(pool, "globex_");
+    let up = MigrateMode::Up { passive: false, fresh: false };
+    run_migrations_on(&mut app, up).await.unwrap();
+    run_migrations_on(&mut acme, up).await.unwrap();
+    run_migrations_on(&mut globex, up).await.unwrap();
+    acme.insert("todos", &[("title", "acme"), ("done", "0")]).await.unwrap();
+    globex.insert("todos", &[("title", "globex"), ("done", "0")]).await.unwrap();
+
+    run_migrations_on(&mut acme, MigrateMode::Up { passive: false, fresh: true }).await.unwrap();
+    assert!(!acme.exists_record("todos", "title", &DbValue::from("acme")).await);
+    assert_eq!(acme.get_next_batch_number().await.unwrap(), 2);
+    assert!(globex.exists_record("todos", "title", &DbValue::from("globex")).await);
+    assert_eq!(globex.get_ran_migrations().await.unwrap().len(), get_all_migrations().len());
+    assert!(app.has_table("todos").await.unwrap());
+    assert!(app.repository_exists().await.unwrap());
+}
+
+#[tokio::test]
+async fn wiping_views_keeps_other_prefixes() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    let acme = Schema::from_sqlite_pool(pool.clone(), "acme_");
+    let globex = Schema::from_sqlite_pool(pool, "globex_");
+    acme.load_schema("CREATE VIEW acme_numbers AS SELECT 1 AS n; CREATE VIEW globex_numbers AS SELECT 2 AS n;")
+        .await
+        .unwrap();
+
+    acme.drop_all_views().await.unwrap();
+    assert_eq!(globex.get_views().await.unwrap(), vec!["globex_numbers".to_string()]);
+}
+
+#[tokio::test]
+async fn edited_migrations_fail_checksums_until_repaired() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    let mut schema = Schema::from_sqlite_pool(pool, "");
+    run_migrations_on(&mut schema, MigrateMode::Up { passive: false, fresh: false }).await.unwrap();
+
+    let records = schema.get_migration_records().await.unwrap();
+    assert_eq!(records.len(), get_all_migrations().len());
+    assert!(records.iter().all(|r| r.batch == 1 && r.checksum.as_ref().is_some_and(|c| c.len() == 8)));
+    check_checksums(&mut schema, &ChecksumPolicy::Fail).await.unwrap();
+
+    // what an edited migration looks like: its stored checksum no longer matches
+    let edited = records[0].migration.clone();
+    schema.set_migration_checksum(&edited, "00000000").await.unwrap();
+    check_checksums(&mut schema, &ChecksumPolicy::Warn).await.unwrap();
+    check_checksums(&mut schema, &ChecksumPolicy::Off).await.unwrap();
+    match check_checksums(&mut schema, &ChecksumPolicy::Fail).await {
+        Err(DbError::ChecksumMismatch(changed)) => assert_eq!(changed, vec![edited]),
+        other => panic!("expected a checksum mismatch, got {:?}", other),
+    }
+
+    run_migrations_on(&mut schema, MigrateMode::RepairChecksums).await.unwrap();
+    check_checksums(&mut schema, &ChecksumPolicy::Fail).await.unwrap();
+    assert_eq!(schema.get_migration_records().await.unwrap()[0].checksum, records[0].checksum);
+}
+
+#[tokio::test]
+async fn status_leaves_a_table_without_checksums_alone() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    let mut schema = Schema::from_sqlite_pool(pool, "");
+    // a `migrations` table written before checksums existed
+    schema
+        .load_schema(
+            "CREATE TABLE migrations (id INTEGER PRIMARY KEY AUTOINCREMENT, migration VARCHAR(255) NOT NULL, batch INTEGER NOT NULL);
+             INSERT INTO migrations (migration, batch) VALUES ('m_2025_01_15_1945_create_todos', 1);",
+        )
+        .await
+        .unwrap();
+
+    run_migrations_on(&mut schema, MigrateMode::Status).await.unwrap();
+    assert!(!schema.has_column("migrations", "checksum").await.unwrap());
+    let records = schema.get_migration_records().await.unwrap();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].checksum, None);
+}
+
+#[tokio::test]
+async fn schema_metadata_is_refreshed_after_migrations() {
+    let mut db = TestDatabase::memory(get_all_migrations).await.unwrap();
+    assert!(db.schema().verify_columns(&[("todos", "title")]).await.is_ok());
+    assert_eq!(
+        db.schema().verify_columns(&[("tags", "name")]).await,
+        Err(vec!["table `tags` does not exist".to_string()])
+    );
+    assert!(!db.schema().exists_record("tags", "name", &DbValue::from("rust")).await);
+
+    let schema = db.schema_mut();
+    schema.create("tags", |table| {
+        table.id();
+        table.string("name", 50);
+    });
+    schema.execute_migration("create_tags", &std::time::Instant::now().into()).await.unwrap();
+    schema.insert("tags", &[("name", "rust")]).await.unwrap();
+
+    assert!(db.schema().verify_columns(&[("tags", "name")]).await.is_ok());
+    assert!(db.schema().exists_record("tags", "name", &DbValue::from("rust")).await);
+}
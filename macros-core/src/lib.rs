@@ -38,6 +38,12 @@ pub struct ValidationErrors {
     pub errors: HashMap<String, Vec<String>>,
 }
 
+impl Default for ValidationErrors {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ValidationErrors {
     pub fn new() -> Self {
         Self {
@@ -194,7 +200,7 @@ pub fn is_valid_date(value: &str) -> bool {
 
 /// Validate if the given string is a valid datetime (YYYY-MM-DD HH:MM:SS).
 pub fn is_valid_datetime(value: &str) -> bool {
-    match PrimitiveDateTime::parse(&value, &DATETIME_FORMAT) {
+    match PrimitiveDateTime::parse(value, &DATETIME_FORMAT) {
         Ok(_) => {
             true
         }
@@ -220,15 +226,15 @@ pub fn is_valid_time(value: &str) -> bool {
 
 /// Validate if `value` is after the given date (YYYY-MM-DD).
 pub fn is_after(value: &str, target: &str) -> bool {
-    let value_date = PrimitiveDateTime::parse(value, &*DATETIME_FORMAT).unwrap();
-    let target_date = PrimitiveDateTime::parse(target, &*DATETIME_FORMAT).unwrap();
+    let value_date = PrimitiveDateTime::parse(value, DATETIME_FORMAT).unwrap();
+    let target_date = PrimitiveDateTime::parse(target, DATETIME_FORMAT).unwrap();
     value_date > target_date
 }
 
 /// Validate if `value` is before the given date (YYYY-MM-DD).
 pub fn is_before(value: &str, target: &str) -> bool {
-    let value_date = PrimitiveDateTime::parse(value, &*DATETIME_FORMAT).unwrap();
-    let target_date = PrimitiveDateTime::parse(target, &*DATETIME_FORMAT).unwrap();
+    let value_date = PrimitiveDateTime::parse(value, DATETIME_FORMAT).unwrap();
+    let target_date = PrimitiveDateTime::parse(target, DATETIME_FORMAT).unwrap();
     value_date < target_date
 }
 
@@ -243,8 +249,8 @@ pub fn is_after_option(value: &str, target: &str) -> Option<bool> {
     if value_temp.len() == 10 {
         value_temp = format!("{} 00:00:00", target);
     }
-    let value_date = PrimitiveDateTime::parse(&value_temp, &*DATETIME_FORMAT).ok()?;
-    let target_date = PrimitiveDateTime::parse(&target_temp, &*DATETIME_FORMAT).ok()?;
+    let value_date = PrimitiveDateTime::parse(&value_temp, DATETIME_FORMAT).ok()?;
+    let target_date = PrimitiveDateTime::parse(&target_temp, DATETIME_FORMAT).ok()?;
     Some(value_date > target_date)
 }
 
@@ -287,8 +293,8 @@ pub fn is_before_option(value: &str, target: &str) -> Option<bool> {
     if value_temp.len() == 10 {
         value_temp = format!("{} 00:00:00", target);
     }
-    let value_date = PrimitiveDateTime::parse(&value_temp, &*DATETIME_FORMAT).ok()?;
-    let target_date = PrimitiveDateTime::parse(&target_temp, &*DATETIME_FORMAT).ok()?;
+    let value_date = PrimitiveDateTime::parse(&value_temp, DATETIME_FORMAT).ok()?;
+    let target_date = PrimitiveDateTime::parse(&target_temp, DATETIME_FORMAT).ok()?;
     Some(value_date < target_date)
 }
 
@@ -0,0 +1,104 @@
+//! `#[derive(DbEnum)]`: implements `rustavel_core::sql::enums::DbEnum` for a
+//! unit-only enum, plus `From<Enum> for DbValue` and `FromSqlValue`, so the
+//! enum can be a `#[derive(Model)]` field.
+//!
+//! The stored value of a variant is read from `#[serde(rename = "..")]` or
+//! `#[sqlx(rename = "..")]`, the variant name is used without either.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, LitStr, Result, Variant};
+
+pub fn expand(ast: &DeriveInput) -> Result<TokenStream> {
+    let enum_name = &ast.ident;
+    let variants = match &ast.data {
+        Data::Enum(data) => &data.variants,
+        _ => {
+            return Err(Error::new_spanned(
+                enum_name,
+                "DbEnum can only be derived for enums",
+            ));
+        }
+    };
+
+    let mut idents = Vec::new();
+    let mut values = Vec::new();
+    for variant in variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new_spanned(
+                variant,
+                "DbEnum variants can't hold data",
+            ));
+        }
+        idents.push(&variant.ident);
+        values.push(stored_value(variant)?);
+    }
+
+    Ok(quote! {
+        impl rustavel_core::sql::enums::DbEnum for #enum_name {
+            const VARIANTS: &'static [&'static str] = &[#(#values),*];
+
+            fn as_str(&self) -> &'static str {
+                match self {
+                    #(Self::#idents => #values,)*
+                }
+            }
+            fn parse(value: &str) -> ::std::option::Option<Self> {
+                match value {
+                    #(#values => ::std::option::Option::Some(Self::#idents),)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+        }
+
+        impl ::std::convert::From<#enum_name> for rustavel_core::sql::value::DbValue {
+            fn from(value: #enum_name) -> Self {
+                let text = rustavel_core::sql::enums::DbEnum::as_str(&value);
+                rustavel_core::sql::value::DbValue::Text(text.to_string())
+            }
+        }
+
+        impl rustavel_core::sql::value::FromSqlValue for #enum_name {
+            fn from_sql_value(value: &rustavel_core::sql::value::DbValue) -> ::std::option::Option<Self> {
+                <Self as rustavel_core::sql::enums::DbEnum>::parse(&value.as_text()?)
+            }
+        }
+
+        impl ::std::fmt::Display for #enum_name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.write_str(rustavel_core::sql::enums::DbEnum::as_str(self))
+            }
+        }
+    })
+}
+
+/// `rename` of the variant's `serde` / `sqlx` attribute, else its name.
+fn stored_value(variant: &Variant) -> Result<String> {
+    let mut rename: Option<LitStr> = None;
+    for attr in &variant.attrs {
+        if !(attr.path().is_ident("serde") || attr.path().is_ident("sqlx")) {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") && meta.input.peek(syn::Token![=]) {
+                let value: LitStr = meta.value()?.parse()?;
+                if rename.as_ref().is_some_and(|r| r.value() != value.value()) {
+                    return Err(Error::new(value.span(), "serde and sqlx rename differ"));
+                }
+                rename = Some(value);
+            } else if meta.input.peek(syn::Token![=]) {
+                // other options belong to serde / sqlx
+                meta.value()?.parse::<syn::Expr>()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                meta.parse_nested_meta(|nested| {
+                    if nested.input.peek(syn::Token![=]) {
+                        nested.value()?.parse::<syn::Expr>()?;
+                    }
+                    Ok(())
+                })?;
+            }
+            Ok(())
+        })?;
+    }
+    Ok(rename.map_or_else(|| variant.ident.to_string(), |r| r.value()))
+}
@@ -36,6 +36,9 @@ use syn::{Attribute, DeriveInput, Field, LitStr, parse_macro_input};
 use syn::{Error, Result};
 use syn::{GenericArgument, PathArguments, Type, TypePath};
 
+mod db_enum;
+mod model;
+
 // Define an enum for the validation rules
 // This enum represents all supported rules, making it easy to extend later by adding new variants
 // Each variant can hold parameters if needed (e.g., Min holds the min value as u32)
@@ -62,6 +65,7 @@ enum Rule {
     UpperCase,
     In(String),
     NotIn(String),
+    Enum(String), // path of a `DbEnum` type, its variants are the allowed values
     // db
     Unique(String),
     Exists(String),
@@ -81,6 +85,11 @@ enum Rule {
     Json,
 }
 
+/// Generate `validate()` from the `#[validating("…")]` rules of each field.
+///
+/// Every rule but `required` skips an `Option` field that is `None`, the database
+/// lookups of `unique:` and `exists:` included. The tables and columns those two
+/// look up are registered for `rustavel_core::db::rules::verify_all()`.
 #[proc_macro_derive(CheckMate, attributes(validating))]
 pub fn mate_validate(input: TokenStream) -> TokenStream {
     // Parse the input tokens into a syntax tree (DeriveInput represents the struct)
@@ -91,6 +100,8 @@ pub fn mate_validate(input: TokenStream) -> TokenStream {
 
     // Check if the derive is on a struct (we assume it is, but in full version, add error handling)
     let mut validations = Vec::new();
+    // (table, column) of every `unique:` / `exists:` rule, for `verify_db_rules()`
+    let mut db_columns: Vec<(String, String)> = Vec::new();
 
     if let syn::Data::Struct(data_struct) = &mut ast.data {
         // collect all fields name required in safe validation like confirm
@@ -110,18 +121,16 @@ pub fn mate_validate(input: TokenStream) -> TokenStream {
                 match parse_rules(&attr, &field_names) {
                     Ok(rules) => {
                         // check nullable can't be a non Option<_> type
-                        if rules.iter().any(|r| matches!(r, Rule::Nullable)) {
-                            if !is_option_type(&field.ty) {
-                                return Error::new(
-                                    field.ty.__span(),
-                                    format!(
-                                        "Field '{}' is marked as nullable but is not Option<T>",
-                                        field.ident.as_ref().unwrap()
-                                    ),
-                                )
-                                .to_compile_error()
-                                .into();
-                            }
+                        if rules.iter().any(|r| matches!(r, Rule::Nullable)) && !is_option_type(&field.ty) {
+                            return Error::new(
+                                field.ty.__span(),
+                                format!(
+                                    "Field '{}' is marked as nullable but is not Option<T>",
+                                    field.ident.as_ref().unwrap()
+                                ),
+                            )
+                            .to_compile_error()
+                            .into();
                         }
 
                         let field_ident = field.ident.as_ref().unwrap();
@@ -133,6 +142,15 @@ pub fn mate_validate(input: TokenStream) -> TokenStream {
                             validations.push(rule.expand(field_ident, field_ty, &field_name));
                         }
 
+                        for rule in &rules {
+                            if let Rule::Unique(meta) | Rule::Exists(meta) = rule {
+                                let parts: Vec<&str> = meta.split(',').map(str::trim).collect();
+                                for column in parts.iter().skip(1) {
+                                    db_columns.push((parts[0].to_string(), column.to_string()));
+                                }
+                            }
+                        }
+
                         // let rules_str: Vec<String> = rules.iter().map(|r| r.as_str()).collect();
                         // rules_display.push_str(&format!(
                         //     "{}: {}\n",
@@ -152,6 +170,29 @@ pub fn mate_validate(input: TokenStream) -> TokenStream {
     // This is static (no &self needed) since rules are compile-time known
     // In tests, we can call Struct::display_parsed_rules()
     let struct_name = &ast.ident;
+    let db_tables = db_columns.iter().map(|(table, _)| table);
+    let db_names = db_columns.iter().map(|(_, column)| column);
+    let db_pairs = quote! { &[#((#db_tables, #db_names)),*] };
+    // structs without db rules don't need `rustavel_core`
+    let (verify_body, register) = if db_columns.is_empty() {
+        (quote! { Ok(()) }, quote! {})
+    } else {
+        let owner = struct_name.to_string();
+        (
+            quote! {
+                let schema = rustavel_core::db::try_get_static_schema()
+                    .await
+                    .map_err(|e| vec![e.to_string()])?;
+                schema.verify_columns(#db_pairs).await
+            },
+            // picked up by `rustavel_core::db::rules::verify_all()`
+            quote! {
+                rustavel_core::inventory::submit! {
+                    rustavel_core::db::rules::DbRules { owner: #owner, columns: #db_pairs }
+                }
+            },
+        )
+    };
     // let lit = LitStr::new(&rules_display, Span::call_site());
     let r#gen = quote! {
 
@@ -177,12 +218,63 @@ pub fn mate_validate(input: TokenStream) -> TokenStream {
                     Err(errors)
                 }
             }
+
+            /// Checks that the tables and columns of the `unique:` / `exists:` rules exist,
+            /// e.g. at startup; returns one message per missing name.
+            #[allow(dead_code)]
+            pub async fn verify_db_rules() -> Result<(), Vec<String>> {
+                #verify_body
+            }
         }
+
+        #register
     };
 
     r#gen.into()
 }
 
+/// Derive `rustavel_core::sql::enums::DbEnum` for a unit-only enum, so it can be
+/// the field of an `enums()` column (or, in an `EnumSet`, of a `sets()` column).
+///
+/// ```rust,ignore
+/// #[derive(Clone, Copy, serde::Serialize, serde::Deserialize, sqlx::Type, DbEnum)]
+/// pub enum PostStatus {
+///     #[serde(rename = "draft")]
+///     #[sqlx(rename = "draft")]
+///     Draft,
+///     Published, // stored as `Published`
+/// }
+/// ```
+#[proc_macro_derive(DbEnum)]
+pub fn derive_db_enum(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    match db_enum::expand(&ast) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Derive `rustavel_core::mvc::model::Model` from the struct definition.
+///
+/// ```rust,ignore
+/// #[derive(Model)]
+/// #[model(table = "todos", primary_key = "id", timestamps)]
+/// pub struct Todo {
+///     pub id: u64,
+///     pub title: String,
+///     #[model(skip)]
+///     pub cached_label: String,
+/// }
+/// ```
+#[proc_macro_derive(Model, attributes(model, searchable))]
+pub fn derive_model(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    match model::expand(&ast) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
 /// Helper function to find the #[validating] attribute on a field
 /// Clones the attribute if found
 fn find_validating_attr(field: &Field) -> Option<Attribute> {
@@ -197,6 +289,7 @@ fn find_validating_attr(field: &Field) -> Option<Attribute> {
 /// Supports two formats:
 /// - Single string: #[validating("required|email|max:180")] -> split by '|' and parse each
 /// - List of strings: #[validating("required", "email", "max:180")] -> parse each
+///
 /// Returns syn::Error if any rule is invalid or unsupported
 fn parse_rules(attr: &Attribute, fields_name: &HashSet<String>) -> Result<Vec<Rule>> {
     let mut rules = Vec::new();
@@ -245,6 +338,12 @@ fn parse_single_rule(raw: &str, span: Span, fields_name: &HashSet<String>) -> Re
             "ends_with" => Ok(Rule::EndsWith(param.to_string())),
             "in" => Ok(Rule::In(param.to_string())),
             "not_in" => Ok(Rule::NotIn(param.to_string())),
+            "enum" => {
+                if syn::parse_str::<syn::Path>(param).is_err() {
+                    return Err(Error::new(span, format!("Invalid enum type '{}' in rule '{}'", param, raw)));
+                }
+                Ok(Rule::Enum(param.to_string()))
+            }
             "mimetypes" => Ok(Rule::MimeTypes(param.to_string())),
             "extensions" => Ok(Rule::Extensions(param.to_string())),
             "after" => {
@@ -267,8 +366,22 @@ fn parse_single_rule(raw: &str, span: Span, fields_name: &HashSet<String>) -> Re
                     ))
                 }
             }
-            "unique" => Ok(Rule::Unique(param.to_string())),
-            "exists" => Ok(Rule::Exists(param.to_string())),
+            "unique" | "exists" => {
+                // table and column names end up in SQL, so only plain identifiers are accepted
+                let names: Vec<&str> = param.split(',').map(str::trim).collect();
+                if let Some(bad) = names.iter().find(|n| !is_sql_identifier(n)) {
+                    return Err(Error::new(
+                        span,
+                        format!("Invalid table or column name '{}' in rule '{}'", bad, raw),
+                    ));
+                }
+                let param = names.join(",");
+                if name == "unique" {
+                    Ok(Rule::Unique(param))
+                } else {
+                    Ok(Rule::Exists(param))
+                }
+            }
             "min" => {
                 let val: i64 = param
                     .parse()
@@ -329,11 +442,38 @@ fn parse_single_rule(raw: &str, span: Span, fields_name: &HashSet<String>) -> Re
     }
 }
 
-fn is_option_type(ty: &Type) -> bool {
-    if let Type::Path(TypePath { path, .. }) = ty {
-        if let Some(segment) = path.segments.last() {
-            return segment.ident == "Option";
+/// `[A-Za-z_][A-Za-z0-9_]*`, what `unique:` and `exists:` accept as table and column names.
+fn is_sql_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Wraps the lookup of a `unique:` / `exists:` rule, which reads the field as `wanted`.
+///
+/// The value is bound with its own type, so integer columns compare as integers,
+/// and an `Option` field only runs the lookup when it is `Some`.
+fn db_lookup(field_ident: &syn::Ident, field_ty: &Type, lookup: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    if is_option_type(field_ty) {
+        quote! {
+            if let Some(value) = &self.#field_ident {
+                let wanted = rustavel_core::sql::value::ToSqlValue::to_sql_value(value);
+                #lookup
+            }
         }
+    } else {
+        quote! {
+            {
+                let wanted = rustavel_core::sql::value::ToSqlValue::to_sql_value(&self.#field_ident);
+                #lookup
+            }
+        }
+    }
+}
+
+fn is_option_type(ty: &Type) -> bool {
+    if let Type::Path(TypePath { path, .. }) = ty && let Some(segment) = path.segments.last() {
+        return segment.ident == "Option";
     }
     false
 }
@@ -414,16 +554,13 @@ fn is_valid_datetime(s: &str) -> bool {
 /// If the given type is `Option<T>`, returns `Some(&T)`.
 /// Otherwise returns `None`.
 fn extract_option_inner_type(ty: &Type) -> Option<&Type> {
-    if let Type::Path(type_path) = ty {
-        if let Some(segment) = type_path.path.segments.last() {
-            if segment.ident == "Option" {
-                if let PathArguments::AngleBracketed(ref args) = segment.arguments {
-                    if let Some(GenericArgument::Type(inner_ty)) = args.args.first() {
-                        return Some(inner_ty);
-                    }
-                }
-            }
-        }
+    if let Type::Path(type_path) = ty
+        && let Some(segment) = type_path.path.segments.last()
+        && segment.ident == "Option"
+        && let PathArguments::AngleBracketed(ref args) = segment.arguments
+        && let Some(GenericArgument::Type(inner_ty)) = args.args.first()
+    {
+        return Some(inner_ty);
     }
     None
 }
@@ -432,10 +569,8 @@ fn extract_option_inner_type(ty: &Type) -> Option<&Type> {
 fn is_string_type(ty: &Type) -> bool {
     let ty = extract_option_inner_type(ty).unwrap_or(ty);
 
-    if let Type::Path(type_path) = ty {
-        if let Some(seg) = type_path.path.segments.last() {
-            return seg.ident == "String";
-        }
+    if let Type::Path(type_path) = ty && let Some(seg) = type_path.path.segments.last() {
+        return seg.ident == "String";
     }
 
     false
@@ -447,15 +582,13 @@ fn is_string_type(ty: &Type) -> bool {
 fn is_numeric_type(ty: &Type) -> bool {
     let ty = extract_option_inner_type(ty).unwrap_or(ty);
 
-    if let Type::Path(type_path) = ty {
-        if let Some(seg) = type_path.path.segments.last() {
-            let ident = seg.ident.to_string();
+    if let Type::Path(type_path) = ty && let Some(seg) = type_path.path.segments.last() {
+        let ident = seg.ident.to_string();
 
-            return matches!(
-                ident.as_str(),
-                "i8" | "i16" | "i32" | "i64" | "u8" | "u16" | "u32" | "u64" | "f32" | "f64"
-            );
-        }
+        return matches!(
+            ident.as_str(),
+            "i8" | "i16" | "i32" | "i64" | "u8" | "u16" | "u32" | "u64" | "f32" | "f64"
+        );
     }
 
     false
@@ -466,37 +599,29 @@ fn is_numeric_type(ty: &Type) -> bool {
 fn is_string_collection_type(ty: &Type) -> bool {
     let ty = extract_option_inner_type(ty).unwrap_or(ty);
 
-    if let Type::Path(type_path) = ty {
-        if let Some(seg) = type_path.path.segments.last() {
-            let ident = seg.ident.to_string();
-
-            // Check for Vec<String>
-            if ident == "Vec" {
-                if let syn::PathArguments::AngleBracketed(ref args) = seg.arguments {
-                    if let Some(syn::GenericArgument::Type(syn::Type::Path(inner_path))) =
-                        args.args.first()
-                    {
-                        if inner_path.path.is_ident("String") {
-                            return true;
-                        }
-                    }
-                }
-            }
+    if let Type::Path(type_path) = ty && let Some(seg) = type_path.path.segments.last() {
+        let ident = seg.ident.to_string();
 
-            // Check for HashMap<String, String>
-            if ident == "HashMap" {
-                if let syn::PathArguments::AngleBracketed(ref args) = seg.arguments {
-                    if let (
-                        Some(syn::GenericArgument::Type(syn::Type::Path(key_path))),
-                        Some(syn::GenericArgument::Type(syn::Type::Path(value_path))),
-                    ) = (args.args.get(0), args.args.get(1))
-                    {
-                        if key_path.path.is_ident("String") && value_path.path.is_ident("String") {
-                            return true;
-                        }
-                    }
-                }
-            }
+        // Check for Vec<String>
+        if ident == "Vec"
+            && let syn::PathArguments::AngleBracketed(ref args) = seg.arguments
+            && let Some(syn::GenericArgument::Type(syn::Type::Path(inner_path))) = args.args.first()
+            && inner_path.path.is_ident("String")
+        {
+            return true;
+        }
+
+        // Check for HashMap<String, String>
+        if ident == "HashMap"
+            && let syn::PathArguments::AngleBracketed(ref args) = seg.arguments
+            && let (
+                Some(syn::GenericArgument::Type(syn::Type::Path(key_path))),
+                Some(syn::GenericArgument::Type(syn::Type::Path(value_path))),
+            ) = (args.args.get(0), args.args.get(1))
+            && key_path.path.is_ident("String")
+            && value_path.path.is_ident("String")
+        {
+            return true;
         }
     }
 
@@ -509,12 +634,10 @@ fn is_string_collection_type(ty: &Type) -> bool {
 fn is_datetime_types(ty: &Type) -> bool {
     let ty = extract_option_inner_type(ty).unwrap_or(ty);
 
-    if let Type::Path(type_path) = ty {
-        if let Some(seg) = type_path.path.segments.last() {
-            let ident = seg.ident.to_string();
+    if let Type::Path(type_path) = ty && let Some(seg) = type_path.path.segments.last() {
+        let ident = seg.ident.to_string();
 
-            return matches!(ident.as_str(), "PrimitiveDateTime" | "Date");
-        }
+        return matches!(ident.as_str(), "PrimitiveDateTime" | "Date");
     }
 
     false
@@ -524,10 +647,8 @@ fn is_datetime_types(ty: &Type) -> bool {
 fn is_datetime_type(ty: &Type) -> bool {
     let ty = extract_option_inner_type(ty).unwrap_or(ty);
 
-    if let Type::Path(type_path) = ty {
-        if let Some(seg) = type_path.path.segments.last() {
-            return seg.ident == "PrimitiveDateTime";
-        }
+    if let Type::Path(type_path) = ty && let Some(seg) = type_path.path.segments.last() {
+        return seg.ident == "PrimitiveDateTime";
     }
 
     false
@@ -536,10 +657,8 @@ fn is_datetime_type(ty: &Type) -> bool {
 fn is_date_type(ty: &Type) -> bool {
     let ty = extract_option_inner_type(ty).unwrap_or(ty);
 
-    if let Type::Path(type_path) = ty {
-        if let Some(seg) = type_path.path.segments.last() {
-            return seg.ident == "Date";
-        }
+    if let Type::Path(type_path) = ty && let Some(seg) = type_path.path.segments.last() {
+        return seg.ident == "Date";
     }
 
     false
@@ -549,10 +668,8 @@ fn is_date_type(ty: &Type) -> bool {
 fn is_time_type(ty: &Type) -> bool {
     let ty = extract_option_inner_type(ty).unwrap_or(ty);
 
-    if let Type::Path(type_path) = ty {
-        if let Some(seg) = type_path.path.segments.last() {
-            return seg.ident == "Time";
-        }
+    if let Type::Path(type_path) = ty && let Some(seg) = type_path.path.segments.last() {
+        return seg.ident == "Time";
     }
 
     false
@@ -579,6 +696,7 @@ impl Rule {
             Rule::UpperCase => "uppercase".to_string(),
             Rule::In(val) => format!("in:{}", val),
             Rule::NotIn(val) => format!("not_in:{}", val),
+            Rule::Enum(ty) => format!("enum:{}", ty),
             Rule::Unique(map) => format!("unique:{}", map), // map: table_name,field or table_name,field,except_field
             Rule::Exists(map) => format!("exists:{}", map), // map: table,field
             Rule::File => "file".to_string(),
@@ -649,8 +767,7 @@ impl Rule {
                         field_name,
                         format!("Email must be string  `{}`", field_name),
                     )
-                    .to_compile_error()
-                    .into();
+                    .to_compile_error();
                 }
                 let is_option = is_option_type(field_ty);
                 if !is_option {
@@ -675,8 +792,7 @@ impl Rule {
                         field_name,
                         format!("Url must be string  `{}`", field_name),
                     )
-                    .to_compile_error()
-                    .into();
+                    .to_compile_error();
                 }
                 let is_option = is_option_type(field_ty);
                 if !is_option {
@@ -731,12 +847,11 @@ impl Rule {
                         }
                     }
                 } else {
-                    return Error::new_spanned(
+                    Error::new_spanned(
                         field_name,
                         format!("unsupported type for `min` rule:  `{}`", field_name),
                     )
                     .to_compile_error()
-                    .into();
                 }
             }
             Rule::Max(val) => {
@@ -776,12 +891,11 @@ impl Rule {
                         }
                     }
                 } else {
-                    return Error::new_spanned(
+                    Error::new_spanned(
                         field_name,
                         format!("unsupported type for `max` rule:  `{}`", field_name),
                     )
                     .to_compile_error()
-                    .into();
                 }
             }
             Rule::Size(val) => {
@@ -823,12 +937,11 @@ impl Rule {
                         }
                     }
                 } else {
-                    return Error::new_spanned(
+                    Error::new_spanned(
                         field_name,
                         format!("invalid data type for size  `{}`", field_name),
                     )
                     .to_compile_error()
-                    .into();
                 }
             }
             Rule::StartsWith(prefix) => {
@@ -851,12 +964,11 @@ impl Rule {
                         }
                     }
                 } else {
-                    return Error::new_spanned(
+                    Error::new_spanned(
                         field_name,
                         format!(" starts_with must be string  `{}`", field_name),
                     )
                     .to_compile_error()
-                    .into();
                 }
             }
             Rule::EndsWith(suffix) => {
@@ -879,12 +991,11 @@ impl Rule {
                         }
                     }
                 } else {
-                    return Error::new_spanned(
+                    Error::new_spanned(
                         field_name,
                         format!(" ends with must be string  `{}`", field_name),
                     )
                     .to_compile_error()
-                    .into();
                 }
             }
             Rule::Ascii => {
@@ -893,8 +1004,7 @@ impl Rule {
                         field_name,
                         format!(" ascii must be string  `{}`", field_name),
                     )
-                    .to_compile_error()
-                    .into();
+                    .to_compile_error();
                 }
                 let is_option = is_option_type(field_ty);
                 if !is_option {
@@ -919,8 +1029,7 @@ impl Rule {
                         field_name,
                         format!("alphanumeric must be string  `{}`", field_name),
                     )
-                    .to_compile_error()
-                    .into();
+                    .to_compile_error();
                 }
                 let is_option = is_option_type(field_ty);
                 if !is_option {
@@ -945,8 +1054,7 @@ impl Rule {
                         field_name,
                         format!(" hex_color must be string  `{}`", field_name),
                     )
-                    .to_compile_error()
-                    .into();
+                    .to_compile_error();
                 }
                 let is_option = is_option_type(field_ty);
                 if !is_option {
@@ -971,8 +1079,7 @@ impl Rule {
                         field_name,
                         format!(" lowercase must be string  `{}`", field_name),
                     )
-                    .to_compile_error()
-                    .into();
+                    .to_compile_error();
                 }
                 let is_option = is_option_type(field_ty);
                 if !is_option {
@@ -997,8 +1104,7 @@ impl Rule {
                         field_name,
                         format!(" uppercase must be string  `{}`", field_name),
                     )
-                    .to_compile_error()
-                    .into();
+                    .to_compile_error();
                 }
                 let is_option = is_option_type(field_ty);
                 if !is_option {
@@ -1036,6 +1142,25 @@ impl Rule {
                     }
                 }
             }
+            Rule::Enum(ty) => {
+                let ty: syn::Path = syn::parse_str(ty).unwrap();
+                let allowed = quote! { <#ty as rustavel_core::sql::enums::DbEnum>::VARIANTS };
+                if is_option_type(field_ty) {
+                    quote! {
+                        if let Some(value) = (&self.#field_ident).as_ref() {
+                            if !#allowed.contains(&value.as_str()) {
+                                errors.add(#field_name, format!("The provided value `{}` must be one of the allowed options: {}",&value,#allowed.join(",")));
+                            }
+                        }
+                    }
+                } else {
+                    quote! {
+                        if !#allowed.contains(&self.#field_ident.as_str()) {
+                           errors.add(#field_name, format!("The provided value `{}` must be one of the allowed options: {}",&self.#field_ident ,#allowed.join(",")));
+                        }
+                    }
+                }
+            }
             Rule::NotIn(values) => {
                 if is_option_type(field_ty) {
                     quote! {
@@ -1061,8 +1186,7 @@ impl Rule {
                         field_name,
                         format!(" ip must be string  `{}`", field_name),
                     )
-                    .to_compile_error()
-                    .into();
+                    .to_compile_error();
                 }
                 let is_option = is_option_type(field_ty);
                 if !is_option {
@@ -1088,8 +1212,7 @@ impl Rule {
                         field_name,
                         format!(" date must be string or date  `{}`", field_name),
                     )
-                    .to_compile_error()
-                    .into();
+                    .to_compile_error();
                 }
                 if is_string_type(field_ty) {
                     if !is_option {
@@ -1118,8 +1241,7 @@ impl Rule {
                         field_name,
                         format!(" datetime must be string or datetime  `{}`", field_name),
                     )
-                    .to_compile_error()
-                    .into();
+                    .to_compile_error();
                 }
                 if is_string_type(field_ty) {
                     if !is_option {
@@ -1147,8 +1269,7 @@ impl Rule {
                         field_name,
                         format!(" time must be string or time `{}`", field_name),
                     )
-                    .to_compile_error()
-                    .into();
+                    .to_compile_error();
                 }
                 let is_option = is_option_type(field_ty);
                 if !is_option {
@@ -1182,8 +1303,7 @@ impl Rule {
                         field_name,
                         format!("date must be string or date|time `{}`", field_name),
                     )
-                    .to_compile_error()
-                    .into();
+                    .to_compile_error();
                 }
                 let is_option = is_option_type(field_ty);
                 // non option dates
@@ -1278,8 +1398,7 @@ impl Rule {
                         field_name,
                         format!("date must be string or date|time `{}`", field_name),
                     )
-                    .to_compile_error()
-                    .into();
+                    .to_compile_error();
                 }
                 let is_option = is_option_type(field_ty);
                 // non option dates
@@ -1373,8 +1492,7 @@ impl Rule {
                         field_name,
                         format!(" json must be string  `{}`", field_name),
                     )
-                    .to_compile_error()
-                    .into();
+                    .to_compile_error();
                 }
                 let is_option = is_option_type(field_ty);
                 if !is_option {
@@ -1402,60 +1520,24 @@ impl Rule {
                             field_name
                         ),
                     )
-                    .to_compile_error()
-                    .into();
+                    .to_compile_error();
                 }
                 quote! {}
             }
             Rule::Exists(meta) => {
-                let mut tokens = TokenStream::new();
-                let mut wanted_token = TokenStream::new();
-
                 let db = meta.split(',').collect::<Vec<_>>();
                 if db.len() != 2 {
                     return Error::new_spanned(
                         field_name,
                         format!("Exists must have to value like: table,column. Exm: users,email. Your meta: {} ", &meta),
                     )
-                        .to_compile_error()
-                        .into();
+                        .to_compile_error();
                 }
 
-                let table = db.get(0).unwrap().to_string();
+                let table = db.first().unwrap().to_string();
                 let column = db.get(1).unwrap().to_string();
 
-                let is_option = is_option_type(field_ty);
-                if is_string_type(field_ty) {
-                    if is_option {
-                        wanted_token = quote! {
-                            let mut wanted = "";
-                            if let Some(value) = self.#field_ident{
-                                wanted = value;
-                            }
-                        }
-                        .into();
-                    } else {
-                        wanted_token = quote! {
-                            let wanted = &self.#field_ident.clone();
-                        }
-                        .into();
-                    }
-                } else if is_numeric_type(field_ty) {
-                    if is_option {
-                        wanted_token = quote! {
-                            let mut wanted = "";
-                            if let Some(value) = self.#field_ident{
-                                wanted = value.to_string();
-                            }
-                        }
-                        .into();
-                    } else {
-                        wanted_token = quote! {
-                            let wanted = &self.#field_ident.to_string();
-                        }
-                        .into();
-                    }
-                } else {
+                if !is_string_type(field_ty) && !is_numeric_type(field_ty) {
                     return Error::new_spanned(
                         field_name,
                         format!(
@@ -1463,72 +1545,32 @@ impl Rule {
                             field_name
                         ),
                     )
-                    .to_compile_error()
-                    .into();
+                    .to_compile_error();
                 }
-
-                let db_token: TokenStream = quote! {
+                let db_token = quote! {
                     if !rustavel_core::db::get_static_schema()
-                    .await.exists_record(#table, #column, wanted).await {
+                    .await.exists_record(#table, #column, &wanted).await {
                         errors.add(#field_name, format!("The record not exists: {}", wanted));
                     }
-                }
-                .into();
+                };
 
-                tokens.extend(wanted_token);
-                tokens.extend(db_token);
-                tokens.into()
+                db_lookup(field_ident, field_ty, db_token)
 
             }
             Rule::Unique(meta) => {
-                let mut tokens = TokenStream::new();
-                let mut wanted_token2 = TokenStream::new();
-
                 let db = meta.split(',').collect::<Vec<_>>();
                 let db_len = db.len();
-                if db_len < 2  || db_len > 3  {
+                if !(2..=3).contains(&db_len)  {
                     return Error::new_spanned(
                         field_name,
                         format!("Unique must have to value like: table,column or table,column,except_field. Exm: users,email or users,email,id . Your meta: {} ", &meta),
-                    ).to_compile_error()
-                        .into();
+                    ).to_compile_error();
                 }
-                let table = db.get(0).unwrap().to_string();
+                let table = db.first().unwrap().to_string();
                 let column = db.get(1).unwrap().to_string();
 
 
-                let is_option = is_option_type(field_ty);
-                if is_string_type(field_ty) {
-                    if is_option {
-                        wanted_token2 = quote! {
-                            let mut wanted = "";
-                            if let Some(value) = self.#field_ident{
-                                wanted = value;
-                            }
-                        }
-                            .into();
-                    } else {
-                        wanted_token2 = quote! {
-                            let wanted = &self.#field_ident.clone();
-                        }
-                            .into();
-                    }
-                } else if is_numeric_type(field_ty) {
-                    if is_option {
-                        wanted_token2 = quote! {
-                            let mut wanted = "";
-                            if let Some(value) = self.#field_ident{
-                                wanted = value.to_string();
-                            }
-                        }
-                            .into();
-                    } else {
-                        wanted_token2 = quote! {
-                            let wanted = &self.#field_ident.to_string();
-                        }
-                            .into();
-                    }
-                } else {
+                if !is_string_type(field_ty) && !is_numeric_type(field_ty) {
                     return Error::new_spanned(
                         field_name,
                         format!(
@@ -1536,36 +1578,32 @@ impl Rule {
                             field_name
                         ),
                     )
-                        .to_compile_error()
-                        .into();
+                        .to_compile_error();
                 }
-
-                let mut db_token: TokenStream = TokenStream::new();
+                let mut db_token = proc_macro2::TokenStream::new();
                 if db_len == 2  {
                     db_token = quote!{
                         if rustavel_core::db::get_static_schema()
-                        .await.exists_record(#table, #column, wanted).await {
+                        .await.exists_record(#table, #column, &wanted).await {
                             errors.add(#field_name, format!("The record exists: {}", wanted));
                         }
-                    }.into();
+                    };
                 } else if db_len == 3 {
 
                     let except_val =  db.get(2).unwrap().to_string();
                     let except = format_ident!("{}",&except_val);
                     db_token = quote!{
                         if rustavel_core::db::get_static_schema()
-                        .await.exists_record_except(#table, #column, wanted, #except_val,&macros_core::convert_to_string(&self.#except)).await {
+                        .await.exists_record_except(#table, #column, &wanted, #except_val, &rustavel_core::sql::value::ToSqlValue::to_sql_value(&self.#except)).await {
                             errors.add(#field_name, format!("The record exists: {}", wanted));
                         }
-                    }.into();
+                    };
 
 
                 }
 
 
-                tokens.extend(wanted_token2);
-                tokens.extend(db_token);
-                tokens.into()
+                db_lookup(field_ident, field_ty, db_token)
             }
             _ => quote! {},
         }
@@ -0,0 +1,255 @@
+//! `#[derive(Model)]`: generates the `rustavel_core::mvc::model::Model` impl
+//! from the struct itself, so `columns()` can never drift from the fields.
+//!
+//! Struct attribute:
+//! - `#[model(table = "todos")]` (required)
+//! - `#[model(primary_key = "id")]` (optional, default `id`)
+//! - `#[model(timestamps)]` (optional, opt-in for automatic `created_at` / `updated_at`)
+//! - `#[searchable(fields = "title,body")]` (optional, the columns of the table's
+//!   `fulltext` index, searched by `Model::search()`)
+//!
+//! Field attribute:
+//! - `#[model(skip)]` keeps the field out of `columns()` and `to_row()`, `from_row()`
+//!   fills it with `Default::default()`
+//!
+//! Every other field type must implement `rustavel_core::sql::value::ToSqlValue`
+//! and `rustavel_core::sql::value::FromSqlValue`.
+//!
+//! A non-generic struct also gets `COLUMNS`, a `Col` per column
+//! (`Todo::COLUMNS.title`, or `col!(Todo::title)`), of the type `<Struct>Columns`.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{Data, DeriveInput, Error, Fields, LitStr, Result, Type};
+
+struct ModelArgs {
+    table: Option<LitStr>,
+    primary_key: Option<LitStr>,
+    timestamps: bool,
+    searchable: Option<LitStr>,
+}
+
+pub fn expand(ast: &DeriveInput) -> Result<TokenStream> {
+    let struct_name = &ast.ident;
+    let args = parse_model_args(ast)?;
+
+    let table = match &args.table {
+        Some(table) => table.value(),
+        None => {
+            return Err(Error::new(
+                Span::call_site(),
+                format!(
+                    "missing table name, add `#[model(table = \"...\")]` to `{}`",
+                    struct_name
+                ),
+            ));
+        }
+    };
+    let primary_key = args
+        .primary_key
+        .as_ref()
+        .map(|pk| pk.value())
+        .unwrap_or_else(|| "id".to_string());
+
+    let fields = match &ast.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(named) => &named.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    struct_name,
+                    "Model can only be derived for structs with named fields",
+                ));
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                struct_name,
+                "Model can only be derived for structs",
+            ));
+        }
+    };
+
+    let mut columns: Vec<String> = Vec::new();
+    let mut idents: Vec<&syn::Ident> = Vec::new();
+    let mut skipped: Vec<&syn::Ident> = Vec::new();
+    let mut key_type: Option<&Type> = None;
+    let mut key_skipped = false;
+
+    for field in fields {
+        let name = field.ident.as_ref().unwrap().unraw().to_string();
+        let skip = is_skipped(field)?;
+
+        if name == primary_key {
+            if skip {
+                key_skipped = true;
+            } else {
+                key_type = Some(&field.ty);
+            }
+        }
+        if skip {
+            skipped.push(field.ident.as_ref().unwrap());
+        } else {
+            columns.push(name);
+            idents.push(field.ident.as_ref().unwrap());
+        }
+    }
+
+    let key_span = args
+        .primary_key
+        .as_ref()
+        .map(|pk| pk.span())
+        .unwrap_or_else(Span::call_site);
+
+    if key_skipped {
+        return Err(Error::new(
+            key_span,
+            format!("primary key field `{}` can't be `#[model(skip)]`", primary_key),
+        ));
+    }
+    let key_type = match key_type {
+        Some(ty) => ty,
+        None => {
+            return Err(Error::new(
+                key_span,
+                format!(
+                    "unknown primary key field `{}` on `{}`",
+                    primary_key, struct_name
+                ),
+            ));
+        }
+    };
+
+    let mut searchable: Vec<String> = Vec::new();
+    if let Some(fields) = &args.searchable {
+        for field in fields.value().split(',').map(str::trim) {
+            if !columns.iter().any(|column| column == field) {
+                return Err(Error::new(
+                    fields.span(),
+                    format!("searchable field `{}` is not a column of `{}`", field, struct_name),
+                ));
+            }
+            searchable.push(field.to_string());
+        }
+    }
+
+    let timestamps = args.timestamps;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    // `Col<Todo<T>>` would need the generics on the columns type too
+    let column_consts = if ast.generics.params.is_empty() {
+        let vis = &ast.vis;
+        let columns_type = format_ident!("{}Columns", struct_name);
+        let doc = format!("Typed columns of `{}`, see `{}::COLUMNS`.", struct_name, struct_name);
+        quote! {
+            #[doc = #doc]
+            #[allow(dead_code)]
+            #vis struct #columns_type {
+                #(pub #idents: rustavel_core::mvc::model::Col<#struct_name>,)*
+            }
+
+            #[allow(dead_code)]
+            impl #struct_name {
+                /// One `Col` per column, e.g. `Self::COLUMNS.id`; `col!(Model::field)` reads it.
+                pub const COLUMNS: #columns_type = #columns_type {
+                    #(#idents: rustavel_core::mvc::model::Col::__derived(#columns),)*
+                };
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    Ok(quote! {
+        #column_consts
+
+        impl #impl_generics rustavel_core::mvc::model::Model for #struct_name #ty_generics #where_clause {
+            type PrimaryKey = #key_type;
+
+            fn table() -> &'static str {
+                #table
+            }
+            fn primary_key() -> &'static str {
+                #primary_key
+            }
+            fn columns() -> &'static [&'static str] {
+                &[#(#columns),*]
+            }
+            fn timestamps() -> bool {
+                #timestamps
+            }
+            fn to_row(&self) -> ::std::vec::Vec<(&'static str, rustavel_core::sql::value::DbValue)> {
+                ::std::vec![
+                    #((#columns, rustavel_core::sql::value::ToSqlValue::to_sql_value(&self.#idents))),*
+                ]
+            }
+            fn from_row(
+                row: &rustavel_core::sql::value::DbRow,
+            ) -> ::std::result::Result<Self, rustavel_core::sql::database_client::DbError> {
+                ::std::result::Result::Ok(Self {
+                    #(#idents: row.try_get(#columns)?,)*
+                    #(#skipped: ::std::default::Default::default(),)*
+                })
+            }
+            fn searchable() -> &'static [&'static str] {
+                &[#(#searchable),*]
+            }
+        }
+    })
+}
+
+/// Collect `#[model(...)]` arguments from the struct attributes.
+fn parse_model_args(ast: &DeriveInput) -> Result<ModelArgs> {
+    let mut args = ModelArgs {
+        table: None,
+        primary_key: None,
+        timestamps: false,
+        searchable: None,
+    };
+
+    for attr in ast.attrs.iter().filter(|a| a.path().is_ident("model")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("table") {
+                args.table = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("primary_key") {
+                args.primary_key = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("timestamps") {
+                args.timestamps = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported model attribute, expected `table`, `primary_key` or `timestamps`"))
+            }
+        })?;
+    }
+
+    for attr in ast.attrs.iter().filter(|a| a.path().is_ident("searchable")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("fields") {
+                args.searchable = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported searchable attribute, expected `fields`"))
+            }
+        })?;
+    }
+
+    Ok(args)
+}
+
+/// Returns `true` if the field carries `#[model(skip)]`.
+fn is_skipped(field: &syn::Field) -> Result<bool> {
+    let mut skip = false;
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("model")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                skip = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported model field attribute, expected `skip`"))
+            }
+        })?;
+    }
+    Ok(skip)
+}
@@ -1,7 +1,9 @@
 use std::collections::HashMap;
-use std::time::Instant;
 use macros::CheckMate;
+use rustavel_core::db::rules;
+use rustavel_core::testing::TestDatabase;
 
+#[allow(dead_code)]
 #[derive(CheckMate, Debug)]
 struct FullRuleCoverage {
 
@@ -53,3 +55,35 @@ fn test_all_rules_parsed() {
     assert_eq!(true, true);
 
 }
+
+#[derive(CheckMate, Debug)]
+struct Invitation {
+    #[validating("exists:teams,id")]
+    team_id: Option<i64>,
+
+    #[validating("unique:users,email")]
+    email: Option<String>,
+}
+
+#[tokio::test]
+async fn db_rules_are_registered_for_startup() {
+    let invitation = rules::registered().into_iter().find(|r| r.owner == "Invitation").unwrap();
+    assert_eq!(invitation.columns, &[("teams", "id"), ("users", "email")]);
+
+    let db = TestDatabase::with_table("users", |table| {
+        table.id();
+        table.string("email", 100);
+    })
+    .await
+    .unwrap();
+    let missing = rules::verify_all_on(db.schema()).await.unwrap_err();
+    assert!(missing.contains(&"Invitation: table `teams` does not exist".to_string()), "{:?}", missing);
+    assert!(missing.iter().all(|m| !m.contains("users")), "{:?}", missing);
+}
+
+#[tokio::test]
+async fn db_rules_skip_none() {
+    // a lookup would need a database, `None` never gets that far
+    let invitation = Invitation { team_id: None, email: None };
+    assert!(invitation.validate().await.is_ok());
+}
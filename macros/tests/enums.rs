@@ -0,0 +1,95 @@
+use macros::{CheckMate, DbEnum, Model};
+use rustavel_core::col;
+use rustavel_core::db::table::Table;
+use rustavel_core::mvc::model::Model as _;
+use rustavel_core::sql::enums::{DbEnum as _, EnumSet};
+use rustavel_core::sql::query::QueryDsl;
+use rustavel_core::sql::value::{DbValue, FromSqlValue};
+use rustavel_core::testing::TestDatabase;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, sqlx::Type, DbEnum)]
+enum PostStatus {
+    #[serde(rename = "draft")]
+    #[sqlx(rename = "draft")]
+    Draft,
+    #[serde(rename = "in-review")]
+    #[sqlx(rename = "in-review")]
+    InReview,
+    Published,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, DbEnum)]
+enum PostTag {
+    #[serde(rename = "news")]
+    News,
+    #[serde(rename = "tech")]
+    Tech,
+}
+
+#[derive(Model, Debug)]
+#[model(table = "posts")]
+struct Post {
+    id: i64,
+    status: PostStatus,
+    tags: EnumSet<PostTag>,
+}
+
+#[derive(CheckMate)]
+struct PostPayload {
+    #[validating("required|enum:PostStatus")]
+    status: String,
+}
+
+#[test]
+fn db_enum_uses_the_renamed_values() {
+    assert_eq!(PostStatus::VARIANTS, &["draft", "in-review", "Published"]);
+    assert_eq!(PostStatus::parse("in-review"), Some(PostStatus::InReview));
+    assert_eq!(PostStatus::parse("review"), None);
+    assert_eq!(DbValue::from(PostStatus::Draft), DbValue::Text("draft".to_string()));
+
+    let tags = EnumSet::<PostTag>::from_sql_value(&DbValue::Text("news,tech".to_string())).unwrap();
+    assert_eq!(tags.0, vec![PostTag::News, PostTag::Tech]);
+    assert_eq!(serde_json::to_string(&tags).unwrap(), r#"["news","tech"]"#);
+    assert!(EnumSet::<PostTag>::parse("news,sports").is_none());
+}
+
+#[test]
+fn to_struct_writes_enums() {
+    let mut table = Table::new("post");
+    table.enums("status", ["draft", "in-review"]);
+    table.sets("tags", ["news"]);
+    let source = table.to_struct();
+    assert!(source.contains("pub enum PostStatus {\n    #[serde(rename = \"draft\")]\n    #[sqlx(rename = \"draft\")]\n    Draft,"));
+    assert!(source.contains("    InReview,"));
+    assert!(source.contains("pub status: PostStatus,"));
+    assert!(source.contains("pub tags: rustavel_core::sql::enums::EnumSet<PostTags>,"));
+}
+
+#[tokio::test]
+async fn enum_fields_round_trip() {
+    let db = TestDatabase::with_table("posts", |table| {
+        table.id();
+        table.enums("status", PostStatus::VARIANTS.iter().copied());
+        table.string("tags", 100);
+    })
+    .await
+    .unwrap();
+
+    Post::insert()
+        .set(col!(Post::status), PostStatus::InReview)
+        .set(col!(Post::tags), EnumSet(vec![PostTag::Tech, PostTag::News]))
+        .execute(db.schema())
+        .await
+        .unwrap();
+    let rows = db.schema().fetch_rows("SELECT * FROM posts", &[]).await.unwrap();
+    let post = Post::from_row(&rows[0]).unwrap();
+    assert_eq!(post.status, PostStatus::InReview);
+    assert_eq!(post.tags.0, vec![PostTag::Tech, PostTag::News]);
+
+    assert!(PostPayload { status: "draft".to_string() }.validate().await.is_ok());
+    let errors = PostPayload { status: "deleted".to_string() }.validate().await.unwrap_err();
+    assert_eq!(
+        errors.errors["status"],
+        vec!["The provided value `deleted` must be one of the allowed options: draft,in-review,Published"]
+    );
+}
@@ -0,0 +1,73 @@
+use macros::Model;
+use rustavel_core::col;
+use rustavel_core::mvc::model::Model as _;
+use rustavel_core::sql::query::QueryDsl;
+use rustavel_core::sql::value::DbValue;
+
+#[derive(Model)]
+#[model(table = "posts", primary_key = "slug", timestamps)]
+#[allow(dead_code)]
+struct Post {
+    slug: String,
+    title: String,
+    #[model(skip)]
+    preview: String,
+    r#type: String,
+}
+
+#[derive(Model)]
+#[model(table = "tags")]
+#[allow(dead_code)]
+struct Tag {
+    id: i64,
+    name: String,
+}
+
+mod blog {
+    use macros::Model;
+
+    #[derive(Model)]
+    #[model(table = "comments")]
+    #[allow(dead_code)]
+    pub struct Comment {
+        id: i64,
+        body: String,
+    }
+}
+
+#[test]
+fn model_derive_reads_struct() {
+    assert_eq!(Post::table(), "posts");
+    assert_eq!(Post::primary_key(), "slug");
+    assert_eq!(Post::columns(), &["slug", "title", "type"]);
+    assert!(Post::timestamps());
+}
+
+#[test]
+fn model_derive_defaults() {
+    assert_eq!(Tag::primary_key(), "id");
+    assert_eq!(Tag::columns(), &["id", "name"]);
+    assert!(!Tag::timestamps());
+    let _key: <Tag as rustavel_core::mvc::model::Model>::PrimaryKey = 1i64;
+}
+
+#[test]
+fn model_derive_to_row() {
+    let tag = Tag { id: 3, name: "rust".to_string() };
+    assert_eq!(
+        tag.to_row(),
+        vec![("id", DbValue::I64(3)), ("name", DbValue::Text("rust".to_string()))]
+    );
+}
+
+#[test]
+fn model_derive_column_constants() {
+    assert_eq!(col!(Post::title).name(), "title");
+    assert_eq!(col!(Post::r#type).name(), "type");
+    assert_eq!(Tag::COLUMNS.id.name(), Tag::primary_key());
+    assert_eq!(col!(blog::Comment::body).name(), "body");
+    assert_eq!(col!(crate::blog::Comment::id).name(), "id");
+
+    let insert = Tag::insert().set(col!(Tag::name), "rust");
+    assert_eq!(insert.values(), vec![("name".to_string(), DbValue::Text("rust".to_string()))]);
+}
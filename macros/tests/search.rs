@@ -0,0 +1,47 @@
+use macros::Model;
+use rustavel_core::col;
+use rustavel_core::mvc::model::Model as _;
+use rustavel_core::sql::query::QueryDsl;
+use rustavel_core::sql::value::DbValue;
+use rustavel_core::testing::TestDatabase;
+
+#[derive(Model, Debug)]
+#[model(table = "articles")]
+#[searchable(fields = "title,body")]
+struct Article {
+    id: i64,
+    title: String,
+    body: String,
+}
+
+#[tokio::test]
+async fn search_ranks_rows_and_follows_writes() {
+    assert_eq!(Article::searchable(), &["title", "body"]);
+
+    let db = TestDatabase::with_table("articles", |table| {
+        table.id();
+        table.string("title", 100);
+        table.text("body");
+        table.fulltext(["title", "body"]);
+    })
+    .await
+    .unwrap();
+
+    for (title, body) in [("Rust traits", "rust, rust and more rust"), ("Cooking", "a little rust on the pan"), ("Gardening", "soil")] {
+        Article::insert().set(col!(Article::title), title).set(col!(Article::body), body).execute(db.schema()).await.unwrap();
+    }
+
+    let page = Article::search("rust").paginate(db.schema(), 1, 10).await.unwrap();
+    assert_eq!(page.total, 2);
+    assert_eq!(page.items[0].title, "Rust traits");
+    assert_eq!(page.items[1].id, 2);
+
+    // the index follows updates and deletes
+    Article::update(3).set(col!(Article::body), "rust-proof tools").execute(db.schema()).await.unwrap();
+    db.schema().execute_params("DELETE FROM articles WHERE id = ?", &[DbValue::I64(1)]).await.unwrap();
+    let page = Article::search("rust").paginate(db.schema(), 2, 1).await.unwrap();
+    assert_eq!((page.total, page.items.len(), page.last_page()), (2, 1, 2));
+
+    // query syntax in user input is searched for, not run
+    assert_eq!(Article::search("rust\" OR body:").paginate(db.schema(), 1, 10).await.unwrap().total, 0);
+}
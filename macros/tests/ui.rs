@@ -0,0 +1,7 @@
+/// Definitions the macros must reject at compile time; `TRYBUILD=overwrite`
+/// regenerates the expected `.stderr` files.
+#[test]
+fn ui() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
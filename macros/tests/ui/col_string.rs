@@ -0,0 +1,5 @@
+use rustavel_core::col;
+
+fn main() {
+    let _ = col!("title");
+}
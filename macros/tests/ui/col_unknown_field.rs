@@ -0,0 +1,14 @@
+use macros::Model;
+use rustavel_core::col;
+use rustavel_core::sql::query::QueryDsl;
+
+#[derive(Model)]
+#[model(table = "todos")]
+struct Todo {
+    id: i64,
+    title: String,
+}
+
+fn main() {
+    let _ = Todo::insert().set(col!(Todo::titel), "write docs");
+}
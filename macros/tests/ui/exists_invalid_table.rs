@@ -0,0 +1,9 @@
+use macros::CheckMate;
+
+#[derive(CheckMate)]
+struct Invite {
+    #[validating("exists:team-members,id")]
+    team_id: i64,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use macros::CheckMate;
+
+#[derive(CheckMate)]
+struct Signup {
+    #[validating("unique:users; drop table users,email")]
+    email: String,
+}
+
+fn main() {}
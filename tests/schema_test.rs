@@ -1,7 +0,0 @@
-use rustavel::core::schema::*;
-
-// #[test]
-// fn simple_schema_test() {
-//     let s = Schema::new();
-//     let sql = s.await.drop_table_if_exists("test_table").await;
-// }